@@ -1,5 +1,7 @@
 use clap::CommandFactory;
 
+// Only the clap definitions are used here; runtime helpers are not.
+#[allow(dead_code)]
 #[path = "src/cli.rs"]
 mod cli;
 
@@ -11,12 +11,14 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use sd_300::collectors::disk_health::DiskHealthStatus;
 use sd_300::collectors::{self, DiagnosticWarning, SystemSnapshot, WarningSeverity};
+pub use sd_300::topics::Topic;
+use sd_300::topics::{FastProjection, MediumProjection, SlowProjection, StaticProjection};
 use sd_300::types::ProcessSortKey;
 use serde::Serialize;
 use serde_json::json;
 
-pub const ABI_VERSION: u32 = 1;
-pub const SCHEMA_VERSION: u32 = 1;
+pub const ABI_VERSION: u32 = 2;
+pub const SCHEMA_VERSION: u32 = sd_300::topics::SCHEMA_VERSION;
 
 pub const STATUS_OK: i32 = 0;
 pub const STATUS_UNCHANGED: i32 = 1;
@@ -37,71 +39,15 @@ const PROCESS_SORT_CPU: u8 = 0;
 const PROCESS_SORT_MEMORY: u8 = 1;
 const PROCESS_SORT_PID: u8 = 2;
 const PROCESS_SORT_NAME: u8 = 3;
+const PROCESS_SORT_DISK_IO: u8 = 4;
+const PROCESS_SORT_GPU_MEMORY: u8 = 5;
 const PROCESS_SUMMARY_ROWS: usize = 16;
 const PROCESS_NAME_BYTES: usize = 96;
 const PROCESS_STATUS_BYTES: usize = 32;
 const EXPORT_NONE: u8 = 0;
 const EXPORT_SNAPSHOT: u8 = 1;
 const EXPORT_CAPABILITIES: u8 = 2;
-
-#[repr(u32)]
-#[derive(Clone, Copy)]
-pub enum Topic {
-    Static = 0,
-    Fast = 1,
-    Medium = 2,
-    Slow = 3,
-    Diagnostics = 4,
-    Health = 5,
-    Drivers = 6,
-    Warnings = 7,
-    Capabilities = 8,
-}
-
-impl Topic {
-    fn from_raw(value: u32) -> Option<Self> {
-        match value {
-            0 => Some(Self::Static),
-            1 => Some(Self::Fast),
-            2 => Some(Self::Medium),
-            3 => Some(Self::Slow),
-            4 => Some(Self::Diagnostics),
-            5 => Some(Self::Health),
-            6 => Some(Self::Drivers),
-            7 => Some(Self::Warnings),
-            8 => Some(Self::Capabilities),
-            _ => None,
-        }
-    }
-
-    fn name(self) -> &'static str {
-        match self {
-            Self::Static => "static",
-            Self::Fast => "fast",
-            Self::Medium => "medium",
-            Self::Slow => "slow",
-            Self::Diagnostics => "diagnostics",
-            Self::Health => "health",
-            Self::Drivers => "drivers",
-            Self::Warnings => "warnings",
-            Self::Capabilities => "capabilities",
-        }
-    }
-
-    fn provenance(self) -> &'static str {
-        match self {
-            Self::Static => "SD-300 system, display, memory, and network collectors",
-            Self::Fast => "SD-300 sysinfo-backed live collectors",
-            Self::Medium => "SD-300 platform connection collector",
-            Self::Slow => "SD-300 disk, GPU, and thermal collectors",
-            Self::Diagnostics => "SD-300 gateway, DNS, and internet collectors",
-            Self::Health => "SD-300 platform disk-health collector",
-            Self::Drivers => "SD-300 platform device provider",
-            Self::Warnings => "SD-300 warning deduplication pipeline",
-            Self::Capabilities => "SD-300 capability and provenance matrix",
-        }
-    }
-}
+const EXPORT_HTML_REPORT: u8 = 3;
 
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default)]
@@ -135,10 +81,14 @@ pub struct ProcessRowSummary {
     pub cpu_percent: f32,
     pub memory_bytes: u64,
     pub memory_percent: f64,
+    pub disk_read_bytes_per_sec: u64,
+    pub disk_write_bytes_per_sec: u64,
+    /// Meaningful only when `gpu_memory_available` is nonzero.
+    pub gpu_memory_bytes: u64,
     pub name_len: u32,
     pub friendly_name_len: u32,
     pub status_len: u32,
-    pub reserved: u32,
+    pub gpu_memory_available: u32,
     pub name: [u8; PROCESS_NAME_BYTES],
     pub friendly_name: [u8; PROCESS_NAME_BYTES],
     pub status: [u8; PROCESS_STATUS_BYTES],
@@ -151,10 +101,13 @@ impl Default for ProcessRowSummary {
             cpu_percent: 0.0,
             memory_bytes: 0,
             memory_percent: 0.0,
+            disk_read_bytes_per_sec: 0,
+            disk_write_bytes_per_sec: 0,
+            gpu_memory_bytes: 0,
             name_len: 0,
             friendly_name_len: 0,
             status_len: 0,
-            reserved: 0,
+            gpu_memory_available: 0,
             name: [0; PROCESS_NAME_BYTES],
             friendly_name: [0; PROCESS_NAME_BYTES],
             status: [0; PROCESS_STATUS_BYTES],
@@ -291,63 +244,6 @@ impl Drop for Engine {
     }
 }
 
-#[derive(Serialize)]
-struct StaticProjection<'a> {
-    system: &'a collectors::system_info::SystemInfoData,
-    displays: &'a collectors::display::DisplayData,
-    memory_modules: &'a [collectors::memory::MemoryModule],
-    memory_module_status: &'a sd_300::observation::Observation,
-    network_adapters: &'a [collectors::network::NetworkAdapterInfo],
-    network_adapter_status: &'a sd_300::observation::Observation,
-}
-
-#[derive(Serialize)]
-struct FastProjection<'a> {
-    cpu: &'a collectors::cpu::CpuData,
-    memory: &'a collectors::memory::MemoryData,
-    network: &'a collectors::network::NetworkData,
-    processes: &'a collectors::processes::ProcessData,
-}
-
-#[derive(Serialize)]
-struct SlowProjection<'a> {
-    disk: &'a collectors::disk::DiskData,
-    gpu: &'a collectors::gpu::GpuData,
-    thermals: &'a collectors::thermals::ThermalData,
-}
-
-#[derive(Serialize)]
-struct MediumProjection<'a> {
-    active_connections: &'a [collectors::network_diag::ConnectionInfo],
-    listening_ports: &'a [collectors::network_diag::ConnectionInfo],
-}
-
-#[derive(Serialize)]
-struct TopicEnvelope<'a, T: ?Sized> {
-    schema_version: u32,
-    product_version: &'static str,
-    target: &'static str,
-    topic: &'static str,
-    sequence: u64,
-    captured_unix_ms: u64,
-    freshness_ms: u64,
-    availability: &'static str,
-    provenance: &'static str,
-    warnings: &'a [DiagnosticWarning],
-    data: &'a T,
-}
-
-fn target_label() -> &'static str {
-    match (std::env::consts::ARCH, std::env::consts::OS) {
-        ("x86_64", "windows") => "x86_64-windows",
-        ("x86_64", "macos") => "x86_64-macos",
-        ("aarch64", "macos") => "aarch64-macos",
-        ("x86_64", "linux") => "x86_64-linux",
-        ("aarch64", "linux") => "aarch64-linux",
-        _ => "unsupported-target",
-    }
-}
-
 fn unix_ms() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -365,20 +261,7 @@ fn publish<T: Serialize>(shared: &Shared, topic: Topic, data: &T, warnings: &[Di
     let state = &mut topics[topic as usize];
     state.sequence = state.sequence.saturating_add(1);
     let captured_unix_ms = unix_ms();
-    let envelope = TopicEnvelope {
-        schema_version: SCHEMA_VERSION,
-        product_version: env!("CARGO_PKG_VERSION"),
-        target: target_label(),
-        topic: topic.name(),
-        sequence: state.sequence,
-        captured_unix_ms,
-        freshness_ms: 0,
-        availability: "available",
-        provenance: topic.provenance(),
-        warnings,
-        data,
-    };
-    match serde_json::to_vec(&envelope) {
+    match sd_300::topics::envelope(topic, state.sequence, captured_unix_ms, data, warnings) {
         Ok(json) => state.json = json,
         Err(error) => set_error(
             shared,
@@ -459,6 +342,10 @@ fn update_process_summary(shared: &Shared, snapshot: &SystemSnapshot) {
         destination.cpu_percent = source.cpu_percent;
         destination.memory_bytes = source.memory_bytes;
         destination.memory_percent = source.memory_percent;
+        destination.disk_read_bytes_per_sec = source.disk_read_bytes_per_sec;
+        destination.disk_write_bytes_per_sec = source.disk_write_bytes_per_sec;
+        destination.gpu_memory_bytes = source.gpu_memory_bytes.unwrap_or(0);
+        destination.gpu_memory_available = u32::from(source.gpu_memory_bytes.is_some());
         destination.name_len = copy_summary_text(&mut destination.name, &source.name);
         destination.friendly_name_len =
             copy_summary_text(&mut destination.friendly_name, &source.friendly_name);
@@ -526,6 +413,8 @@ fn process_sort_from_raw(value: u32) -> Option<ProcessSortKey> {
         value if value == u32::from(PROCESS_SORT_MEMORY) => Some(ProcessSortKey::Memory),
         value if value == u32::from(PROCESS_SORT_PID) => Some(ProcessSortKey::Pid),
         value if value == u32::from(PROCESS_SORT_NAME) => Some(ProcessSortKey::Name),
+        value if value == u32::from(PROCESS_SORT_DISK_IO) => Some(ProcessSortKey::DiskIo),
+        value if value == u32::from(PROCESS_SORT_GPU_MEMORY) => Some(ProcessSortKey::GpuMemory),
         _ => None,
     }
 }
@@ -586,6 +475,7 @@ impl Drop for CollectorWorkers<'_> {
 
 fn collect_loop(shared: &Shared) {
     let mut snapshot = SystemSnapshot::default();
+    snapshot.apply_settings(&sd_300::settings::load_shared());
     let mut workers = CollectorWorkers::new(shared);
 
     // Static identity and display inventory are collected once on the engine
@@ -939,17 +829,22 @@ fn service_export_request(shared: &Shared, snapshot: &SystemSnapshot) {
         return;
     }
     let result = write_export(snapshot, kind);
+    let kind_name = match kind {
+        EXPORT_SNAPSHOT => "redacted_snapshot",
+        EXPORT_HTML_REPORT => "html_report",
+        _ => "capabilities",
+    };
     let status = match result {
         Ok(path) => json!({
             "state": "complete",
-            "kind": if kind == EXPORT_SNAPSHOT { "redacted_snapshot" } else { "capabilities" },
+            "kind": kind_name,
             "path": path.to_string_lossy(),
         }),
         Err(error) => {
             set_error(shared, &error);
             json!({
                 "state": "error",
-                "kind": if kind == EXPORT_SNAPSHOT { "redacted_snapshot" } else { "capabilities" },
+                "kind": kind_name,
                 "error": error,
             })
         }
@@ -965,16 +860,20 @@ fn write_export(snapshot: &SystemSnapshot, kind: u8) -> Result<PathBuf, String>
     let directory = sd_300::settings::reports_dir()?;
     ensure_export_directory(&directory)?;
     let captured = unix_ms();
-    let stem = if kind == EXPORT_SNAPSHOT {
-        "sd300-redacted-snapshot"
-    } else if kind == EXPORT_CAPABILITIES {
-        "sd300-capabilities"
-    } else {
-        return Err("unknown export kind".into());
+    let (stem, extension) = match kind {
+        EXPORT_SNAPSHOT => ("sd300-redacted-snapshot", "json"),
+        EXPORT_CAPABILITIES => ("sd300-capabilities", "json"),
+        EXPORT_HTML_REPORT => ("sd300-report", "html"),
+        _ => return Err("unknown export kind".into()),
     };
     let report = sd_300::report::DiagnosticReport::from_snapshot(snapshot, false);
     let bytes = if kind == EXPORT_SNAPSHOT {
         serde_json::to_vec_pretty(&report)
+    } else if kind == EXPORT_HTML_REPORT {
+        Ok(sd_300::html_report::render(&report, captured)
+            .trim_end()
+            .as_bytes()
+            .to_vec())
     } else {
         serde_json::to_vec_pretty(&json!({
             "schema_version": report.schema_version,
@@ -990,9 +889,9 @@ fn write_export(snapshot: &SystemSnapshot, kind: u8) -> Result<PathBuf, String>
 
     for suffix in 0..100u8 {
         let file_name = if suffix == 0 {
-            format!("{stem}-{captured}.json")
+            format!("{stem}-{captured}.{extension}")
         } else {
-            format!("{stem}-{captured}-{suffix}.json")
+            format!("{stem}-{captured}-{suffix}.{extension}")
         };
         let destination = directory.join(file_name);
         if destination.exists() {
@@ -1059,7 +958,9 @@ fn restrict_export_directory(_directory: &Path) -> Result<(), String> {
 }
 
 fn write_export_atomically(destination: &Path, bytes: &[u8]) -> Result<(), String> {
-    let temporary = destination.with_extension("json.tmp");
+    let mut temporary = destination.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let temporary = PathBuf::from(temporary);
     let mut options = OpenOptions::new();
     options.write(true).create_new(true);
     #[cfg(unix)]
@@ -1334,6 +1235,7 @@ pub extern "C" fn sd300_engine_request_export(handle: *mut c_void, kind: u32) ->
         let kind = match kind {
             1 => EXPORT_SNAPSHOT,
             2 => EXPORT_CAPABILITIES,
+            3 => EXPORT_HTML_REPORT,
             _ => return STATUS_INVALID_ARGUMENT,
         };
         if !engine.shared.running.load(Ordering::Acquire) {
@@ -1625,7 +1527,7 @@ mod tests {
             serde_json::from_slice(&state.json).expect("valid topic JSON");
         assert_eq!(envelope["schema_version"], SCHEMA_VERSION);
         assert_eq!(envelope["product_version"], "3.1.2");
-        assert_eq!(envelope["target"], target_label());
+        assert_eq!(envelope["target"], sd_300::topics::target_label());
         assert_eq!(envelope["topic"], "warnings");
         assert_eq!(envelope["sequence"], 1);
         assert_eq!(envelope["freshness_ms"], 0);
@@ -1640,9 +1542,9 @@ mod tests {
         assert_eq!(std::mem::align_of::<FastSummary>(), 8);
         assert_eq!(std::mem::size_of::<TraySummary>(), 32);
         assert_eq!(std::mem::align_of::<TraySummary>(), 8);
-        assert_eq!(std::mem::size_of::<ProcessRowSummary>(), 264);
+        assert_eq!(std::mem::size_of::<ProcessRowSummary>(), 288);
         assert_eq!(std::mem::align_of::<ProcessRowSummary>(), 8);
-        assert_eq!(std::mem::size_of::<ProcessSummary>(), 4256);
+        assert_eq!(std::mem::size_of::<ProcessSummary>(), 4640);
         assert_eq!(std::mem::align_of::<ProcessSummary>(), 8);
     }
 
@@ -1717,6 +1619,8 @@ mod tests {
             (1, ProcessSortKey::Memory),
             (2, ProcessSortKey::Pid),
             (3, ProcessSortKey::Name),
+            (4, ProcessSortKey::DiskIo),
+            (5, ProcessSortKey::GpuMemory),
         ];
 
         for (raw, expected) in cases {
@@ -1727,7 +1631,10 @@ mod tests {
             sd300_engine_set_process_sort(handle, u32::MAX),
             STATUS_INVALID_ARGUMENT
         );
-        assert_eq!(selected_process_sort(&engine.shared), ProcessSortKey::Name);
+        assert_eq!(
+            selected_process_sort(&engine.shared),
+            ProcessSortKey::GpuMemory
+        );
         assert_eq!(
             sd300_engine_set_process_sort(ptr::null_mut(), 0),
             STATUS_INVALID_ARGUMENT
@@ -3,7 +3,7 @@ const builtin = @import("builtin");
 
 const windows = std.os.windows;
 
-pub const expected_abi_version: u32 = 1;
+pub const expected_abi_version: u32 = 2;
 pub const expected_schema_version: u32 = 1;
 pub const expected_product_version = "3.1.2";
 
@@ -26,6 +26,7 @@ pub const Topic = enum(u32) {
 pub const ExportKind = enum(u32) {
     redacted_snapshot = 1,
     capabilities = 2,
+    html_report = 3,
 };
 
 pub const TopicPayload = struct {
@@ -67,10 +68,13 @@ pub const ProcessRowSummary = extern struct {
     cpu_percent: f32 = 0,
     memory_bytes: u64 = 0,
     memory_percent: f64 = 0,
+    disk_read_bytes_per_sec: u64 = 0,
+    disk_write_bytes_per_sec: u64 = 0,
+    gpu_memory_bytes: u64 = 0,
     name_len: u32 = 0,
     friendly_name_len: u32 = 0,
     status_len: u32 = 0,
-    reserved: u32 = 0,
+    gpu_memory_available: u32 = 0,
     name: [process_name_bytes]u8 = [_]u8{0} ** process_name_bytes,
     friendly_name: [process_name_bytes]u8 = [_]u8{0} ** process_name_bytes,
     status: [process_status_bytes]u8 = [_]u8{0} ** process_status_bytes,
@@ -93,10 +97,10 @@ comptime {
     if (@sizeOf(TraySummary) != 32 or @alignOf(TraySummary) != 8) {
         @compileError("TraySummary no longer matches the SD-300 Rust ABI");
     }
-    if (@sizeOf(ProcessRowSummary) != 264 or @alignOf(ProcessRowSummary) != 8) {
+    if (@sizeOf(ProcessRowSummary) != 288 or @alignOf(ProcessRowSummary) != 8) {
         @compileError("ProcessRowSummary no longer matches the SD-300 Rust ABI");
     }
-    if (@sizeOf(ProcessSummary) != 4256 or @alignOf(ProcessSummary) != 8) {
+    if (@sizeOf(ProcessSummary) != 4640 or @alignOf(ProcessSummary) != 8) {
         @compileError("ProcessSummary no longer matches the SD-300 Rust ABI");
     }
 }
@@ -503,8 +507,8 @@ extern "kernel32" fn FreeLibrary(module: windows.HMODULE) callconv(.winapi) wind
 test "fast summary ABI is stable" {
     try std.testing.expectEqual(@as(usize, 48), @sizeOf(FastSummary));
     try std.testing.expectEqual(@as(usize, 8), @alignOf(FastSummary));
-    try std.testing.expectEqual(@as(usize, 264), @sizeOf(ProcessRowSummary));
+    try std.testing.expectEqual(@as(usize, 288), @sizeOf(ProcessRowSummary));
     try std.testing.expectEqual(@as(usize, 8), @alignOf(ProcessRowSummary));
-    try std.testing.expectEqual(@as(usize, 4256), @sizeOf(ProcessSummary));
+    try std.testing.expectEqual(@as(usize, 4640), @sizeOf(ProcessSummary));
     try std.testing.expectEqual(@as(usize, 8), @alignOf(ProcessSummary));
 }
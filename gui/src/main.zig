@@ -82,6 +82,7 @@ pub const Msg = union(enum) {
     density_comfortable,
     export_redacted_snapshot,
     export_capabilities,
+    export_html_report,
     update_now,
     export_poll: native_sdk.EffectTimer,
     content_scrolled: canvas.ScrollState,
@@ -555,6 +556,7 @@ pub fn update(model: *Model, msg: Msg, fx: *Effects) void {
         },
         .export_redacted_snapshot => requestExport(model, fx, .redacted_snapshot),
         .export_capabilities => requestExport(model, fx, .capabilities),
+        .export_html_report => requestExport(model, fx, .html_report),
         .update_now => requestUpdateNow(model),
         .export_poll => |timer| {
             if (timer.outcome == .fired and model.export_pending) pollExport(model, fx);
@@ -1,26 +1,40 @@
 use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
 use futures::StreamExt;
 use ratatui::DefaultTerminal;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::time::interval;
 
+use crate::baseline::Baseline;
+use crate::cli::ExportTable;
 use crate::collectors::disk_health::DiskHealthData;
 use crate::collectors::drivers::{DriverData, DriverScanStatus};
 use crate::collectors::network_diag::NetworkDiagData;
 use crate::collectors::{DiagnosticWarning, SystemSnapshot, WarningSeverity};
-use crate::error::Result;
-use crate::history::HistoryBuffer;
-use crate::types::{DiagnosticMode, HealthStatus, ProcessSortKey, Section, TempUnit};
+use crate::error::{AppError, Result};
+use crate::history::{HistoryBuffer, MetricHistory, FULL_HISTORY_SAMPLES};
+use crate::metrics_history::MetricsRecorder;
+use crate::replay::Player;
+use crate::report::DiagnosticReport;
+use crate::session_stats::{SessionStats, Unit};
+use crate::settings::MetricsHistorySettings;
+use crate::types::{DiagnosticMode, HealthStatus, ProcessSortKey, Section, TempUnit, TimeWindow};
 use crate::ui;
 
 // -- Refresh Intervals --
-const REFRESH_FAST: Duration = Duration::from_secs(1);
-const REFRESH_SLOW: Duration = Duration::from_secs(5);
-const REFRESH_MEDIUM: Duration = Duration::from_secs(3);
-const REFRESH_DIAG: Duration = Duration::from_secs(15);
-const REFRESH_HEALTH: Duration = Duration::from_secs(60);
+pub(crate) const REFRESH_FAST: Duration = Duration::from_secs(1);
+pub(crate) const REFRESH_SLOW: Duration = Duration::from_secs(5);
+pub(crate) const REFRESH_MEDIUM: Duration = Duration::from_secs(3);
+pub(crate) const REFRESH_DIAG: Duration = Duration::from_secs(15);
+pub(crate) const REFRESH_HEALTH: Duration = Duration::from_secs(60);
 const HISTORY_SAMPLES: usize = 60;
 
+// -- Replay --
+const REPLAY_TICK: Duration = Duration::from_millis(100);
+const REPLAY_SEEK: Duration = Duration::from_secs(10);
+
+/// How long a status notice stays in the header.
+const NOTICE_DURATION: Duration = Duration::from_secs(5);
+
 /// Main application state
 pub struct App {
     /// Current diagnostic mode (None = show mode selection screen)
@@ -31,20 +45,22 @@ pub struct App {
     pub should_quit: bool,
     /// Whether to show the help overlay
     pub show_help: bool,
+    /// Whether to show the overall health breakdown (User Mode)
+    pub show_health: bool,
     /// Exceptional Cargo v2-to-v3 state: the second update still needs to
     /// install the managed CLI+GUI product. This never affects normal TUI
     /// startup or session behavior.
     pub cargo_gui_completion_notice: bool,
     /// System data snapshot
     pub snapshot: SystemSnapshot,
-    /// CPU usage history (60 samples)
-    pub cpu_history: HistoryBuffer,
+    /// CPU usage history
+    pub cpu_history: MetricHistory,
     /// Memory usage history
-    pub mem_history: HistoryBuffer,
+    pub mem_history: MetricHistory,
     /// Network download history
-    pub net_down_history: HistoryBuffer,
+    pub net_down_history: MetricHistory,
     /// Network upload history
-    pub net_up_history: HistoryBuffer,
+    pub net_up_history: MetricHistory,
     /// Process table scroll offset
     pub process_scroll: usize,
     /// Process sort key
@@ -54,13 +70,15 @@ pub struct App {
     /// Per-core CPU history
     pub per_core_history: Vec<HistoryBuffer>,
     /// Swap usage history
-    pub swap_history: HistoryBuffer,
+    pub swap_history: MetricHistory,
     /// GPU usage history
-    pub gpu_history: HistoryBuffer,
+    pub gpu_history: MetricHistory,
     /// Temperature history
-    pub temp_history: HistoryBuffer,
+    pub temp_history: MetricHistory,
     /// Temperature display unit (Celsius or Fahrenheit)
     pub temp_unit: TempUnit,
+    /// Span of history shown by sparklines
+    pub time_window: TimeWindow,
     /// Network connection table scroll offset
     pub connection_scroll: usize,
     /// Disk I/O read history
@@ -71,6 +89,18 @@ pub struct App {
     pub driver_scroll: usize,
     /// Disk section scroll offset (tech mode)
     pub disk_scroll: usize,
+    /// Event timeline scroll offset, counted from the newest event
+    pub events_scroll: usize,
+    /// Recording being replayed instead of live collection
+    pub replay: Option<Player>,
+    /// Active drift baseline, when one has been chosen
+    pub baseline: Option<Baseline>,
+    /// Whole-session statistics; live sessions only, never replays
+    pub session_stats: Option<SessionStats>,
+    /// Short-lived status message for the header, such as a saved export
+    pub notice: Option<(String, Instant)>,
+    /// Long-term metrics history, when enabled in shared settings
+    metrics_recorder: Option<MetricsRecorder>,
     /// Async driver scan handle
     driver_scan_handle: Option<tokio::task::JoinHandle<DriverData>>,
     /// Async connectivity check handle
@@ -87,25 +117,33 @@ impl App {
             current_section: Section::Overview,
             should_quit: false,
             show_help: false,
+            show_health: false,
             cargo_gui_completion_notice: false,
             snapshot: SystemSnapshot::default(),
-            cpu_history: HistoryBuffer::new(HISTORY_SAMPLES),
-            mem_history: HistoryBuffer::new(HISTORY_SAMPLES),
-            net_down_history: HistoryBuffer::new(HISTORY_SAMPLES),
-            net_up_history: HistoryBuffer::new(HISTORY_SAMPLES),
+            cpu_history: MetricHistory::new(HISTORY_SAMPLES),
+            mem_history: MetricHistory::new(HISTORY_SAMPLES),
+            net_down_history: MetricHistory::new(HISTORY_SAMPLES),
+            net_up_history: MetricHistory::new(HISTORY_SAMPLES),
             process_scroll: 0,
             process_sort: ProcessSortKey::Cpu,
             too_small: false,
             per_core_history: Vec::new(),
-            swap_history: HistoryBuffer::new(HISTORY_SAMPLES),
-            gpu_history: HistoryBuffer::new(HISTORY_SAMPLES),
-            temp_history: HistoryBuffer::new(HISTORY_SAMPLES),
+            swap_history: MetricHistory::new(HISTORY_SAMPLES),
+            gpu_history: MetricHistory::new(HISTORY_SAMPLES),
+            temp_history: MetricHistory::new(HISTORY_SAMPLES),
             temp_unit: TempUnit::Celsius,
+            time_window: TimeWindow::Minute,
             connection_scroll: 0,
             disk_read_history: HistoryBuffer::new(HISTORY_SAMPLES),
             disk_write_history: HistoryBuffer::new(HISTORY_SAMPLES),
             driver_scroll: 0,
             disk_scroll: 0,
+            events_scroll: 0,
+            replay: None,
+            baseline: None,
+            session_stats: None,
+            notice: None,
+            metrics_recorder: None,
             driver_scan_handle: None,
             connectivity_check_handle: None,
             disk_health_handle: None,
@@ -114,6 +152,25 @@ impl App {
 
     /// Run the main event loop
     pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        if self.replay.is_some() {
+            return self.run_replay(terminal).await;
+        }
+
+        self.session_stats = Some(SessionStats::new(crate::record::unix_ms()));
+        let shared = crate::settings::load_shared();
+        self.snapshot.apply_settings(&shared);
+        if let Some(history) = shared.metrics_history {
+            self.start_metrics_history(history);
+        }
+        match Baseline::load_active() {
+            Ok(baseline) => self.baseline = baseline,
+            Err(error) => self.snapshot.warnings.push(DiagnosticWarning {
+                source: crate::baseline::WARNING_SOURCE.into(),
+                message: format!("Drift baseline could not be loaded: {error}"),
+                severity: WarningSeverity::Warning,
+            }),
+        }
+
         // Initial data collection
         self.snapshot.refresh_static();
         self.snapshot.refresh_fast();
@@ -136,6 +193,7 @@ impl App {
 
         loop {
             self.poll_background_scans().await;
+            self.snapshot.record_events(crate::record::unix_ms());
 
             // Draw
             let size = terminal.size()?;
@@ -149,8 +207,10 @@ impl App {
             // Event handling with tokio select
             tokio::select! {
                 _ = fast_tick.tick() => {
+                    self.snapshot.process_rank = self.process_sort;
                     self.snapshot.refresh_fast();
                     self.update_fast_history();
+                    self.record_metrics_history();
                 }
                 _ = slow_tick.tick() => {
                     self.snapshot.refresh_slow();
@@ -162,6 +222,7 @@ impl App {
                     if self.connectivity_check_handle.is_none() {
                         self.start_connectivity_check();
                     }
+                    self.check_drift();
                 }
                 _ = health_tick.tick() => {
                     if self.disk_health_handle.is_none() {
@@ -177,6 +238,129 @@ impl App {
         }
     }
 
+    /// Event loop for `--replay`: no collectors or background scans, only
+    /// the playback clock.
+    async fn run_replay(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        self.rebuild_replay_history();
+
+        let mut playback_tick = interval(REPLAY_TICK);
+        let mut event_stream = crossterm::event::EventStream::new();
+
+        loop {
+            let size = terminal.size()?;
+            self.too_small = size.width < 80 || size.height < 24;
+            terminal.draw(|frame| ui::render(frame, self))?;
+
+            if self.should_quit {
+                return Ok(());
+            }
+
+            let shown = self.replay_position();
+            tokio::select! {
+                _ = playback_tick.tick() => {
+                    if let Some(player) = self.replay.as_mut() {
+                        player.advance(REPLAY_TICK);
+                    }
+                }
+                event = event_stream.next() => {
+                    if let Some(Ok(evt)) = event {
+                        self.handle_event(evt);
+                    }
+                }
+            }
+            self.sync_replay(shown);
+        }
+    }
+
+    fn replay_position(&self) -> usize {
+        self.replay.as_ref().map_or(0, Player::position)
+    }
+
+    /// Show the player's current frame after it moved from `shown`. Moving
+    /// forward extends the sparklines frame by frame; moving back, or further
+    /// than the history tiers reach, rebuilds them from the recorded frames
+    /// leading up to the new position.
+    fn sync_replay(&mut self, shown: usize) {
+        let Some(mut player) = self.replay.take() else {
+            return;
+        };
+        let position = player.position();
+        let rebuild = position < shown || position - shown >= FULL_HISTORY_SAMPLES;
+        if !rebuild && position != shown {
+            for index in shown + 1..=position {
+                player.apply_history(index, &mut self.snapshot);
+                self.update_fast_history();
+            }
+            player.apply(position, &mut self.snapshot);
+        }
+        self.replay = Some(player);
+        if rebuild {
+            self.rebuild_replay_history();
+        }
+    }
+
+    fn rebuild_replay_history(&mut self) {
+        let Some(mut player) = self.replay.take() else {
+            return;
+        };
+        self.cpu_history = MetricHistory::new(HISTORY_SAMPLES);
+        self.mem_history = MetricHistory::new(HISTORY_SAMPLES);
+        self.net_down_history = MetricHistory::new(HISTORY_SAMPLES);
+        self.net_up_history = MetricHistory::new(HISTORY_SAMPLES);
+        self.per_core_history.clear();
+        self.swap_history = MetricHistory::new(HISTORY_SAMPLES);
+        self.gpu_history = MetricHistory::new(HISTORY_SAMPLES);
+        self.temp_history = MetricHistory::new(HISTORY_SAMPLES);
+        self.disk_read_history = HistoryBuffer::new(HISTORY_SAMPLES);
+        self.disk_write_history = HistoryBuffer::new(HISTORY_SAMPLES);
+
+        let position = player.position();
+        for index in position.saturating_sub(FULL_HISTORY_SAMPLES - 1)..=position {
+            player.apply_history(index, &mut self.snapshot);
+            self.update_fast_history();
+        }
+        player.apply(position, &mut self.snapshot);
+        self.replay = Some(player);
+    }
+
+    /// Drift changes slowly, so it is checked on the connectivity cadence
+    /// rather than every fast refresh.
+    fn check_drift(&mut self) {
+        let Some(baseline) = &self.baseline else {
+            return;
+        };
+        let drift = baseline.drift(&self.snapshot);
+        self.snapshot
+            .warnings
+            .retain(|w| w.source != crate::baseline::WARNING_SOURCE);
+        self.snapshot.warnings.extend(drift);
+    }
+
+    fn start_metrics_history(&mut self, history: MetricsHistorySettings) {
+        match MetricsRecorder::open_default(history) {
+            Ok(recorder) => self.metrics_recorder = Some(recorder),
+            Err(error) => self.stop_metrics_history(error),
+        }
+    }
+
+    fn record_metrics_history(&mut self) {
+        let Some(recorder) = self.metrics_recorder.as_mut() else {
+            return;
+        };
+        if let Err(error) = recorder.observe(&self.snapshot, crate::metrics_history::unix_secs()) {
+            self.stop_metrics_history(error);
+        }
+    }
+
+    /// A failing store stops recording for the session instead of retrying
+    /// every tick; the Overview warnings say why.
+    fn stop_metrics_history(&mut self, error: AppError) {
+        self.metrics_recorder = None;
+        self.snapshot
+            .warnings
+            .push(crate::metrics_history::stopped_warning(&error));
+    }
+
     fn start_driver_scan(&mut self) {
         self.snapshot.drivers.scan_status = DriverScanStatus::Scanning;
         self.driver_scan_handle = Some(tokio::task::spawn_blocking(
@@ -249,8 +433,16 @@ impl App {
     }
 
     fn update_fast_history(&mut self) {
+        let mut stats = self.session_stats.as_mut();
+        let mut record = |name: &str, unit: Unit, value: f64| {
+            if let Some(stats) = stats.as_mut() {
+                stats.record(name, unit, value);
+            }
+        };
+
         // CPU total
         self.cpu_history.push(self.snapshot.cpu.total_usage as f64);
+        record("cpu", Unit::Percent, self.snapshot.cpu.total_usage as f64);
 
         // Per-core
         while self.per_core_history.len() < self.snapshot.cpu.per_core_usage.len() {
@@ -261,6 +453,7 @@ impl App {
             if let Some(buf) = self.per_core_history.get_mut(i) {
                 buf.push(*usage as f64);
             }
+            record(&format!("cpu_core_{i}"), Unit::Percent, *usage as f64);
         }
 
         // Memory
@@ -271,6 +464,7 @@ impl App {
             0.0
         };
         self.mem_history.push(mem_pct);
+        record("memory", Unit::Percent, mem_pct);
 
         // Swap
         let swap_pct = if self.snapshot.memory.swap_total_bytes > 0 {
@@ -281,17 +475,25 @@ impl App {
             0.0
         };
         self.swap_history.push(swap_pct);
+        record("swap", Unit::Percent, swap_pct);
 
         // Network
-        self.net_down_history
-            .push(self.snapshot.network.total_download_rate as f64);
-        self.net_up_history
-            .push(self.snapshot.network.total_upload_rate as f64);
+        let down = self.snapshot.network.total_download_rate as f64;
+        let up = self.snapshot.network.total_upload_rate as f64;
+        self.net_down_history.push(down);
+        self.net_up_history.push(up);
+        record("net_down", Unit::BytesPerSecond, down);
+        record("net_up", Unit::BytesPerSecond, up);
 
         // GPU
         if self.snapshot.gpu.telemetry_available {
             self.gpu_history
                 .push(self.snapshot.gpu.utilization_percent as f64);
+            record(
+                "gpu",
+                Unit::Percent,
+                self.snapshot.gpu.utilization_percent as f64,
+            );
         }
 
         // Temperature
@@ -302,6 +504,7 @@ impl App {
             .or(self.snapshot.thermals.gpu_temp)
         {
             self.temp_history.push(temperature);
+            record("temperature", Unit::Celsius, temperature);
         }
 
         // Disk I/O
@@ -309,6 +512,16 @@ impl App {
             if let Some(ref io) = drive.io_stats {
                 self.disk_read_history.push(io.read_bytes_per_sec as f64);
                 self.disk_write_history.push(io.write_bytes_per_sec as f64);
+                record(
+                    "disk_read",
+                    Unit::BytesPerSecond,
+                    io.read_bytes_per_sec as f64,
+                );
+                record(
+                    "disk_write",
+                    Unit::BytesPerSecond,
+                    io.write_bytes_per_sec as f64,
+                );
             }
         }
     }
@@ -334,6 +547,15 @@ impl App {
                 return;
             }
 
+            // So does the health breakdown
+            if self.show_health {
+                match key.code {
+                    KeyCode::Char('w') | KeyCode::Esc => self.show_health = false,
+                    _ => {}
+                }
+                return;
+            }
+
             // Mode selection screen
             if self.mode.is_none() {
                 match key.code {
@@ -345,18 +567,26 @@ impl App {
                 return;
             }
 
+            if self.replay.is_some() && self.handle_replay_key(key.code) {
+                return;
+            }
+
             // Main navigation
             match key.code {
                 KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
                 KeyCode::Char('m') => self.mode = None,
                 KeyCode::Char('?') => self.show_help = true,
-                KeyCode::Char(c @ '1'..='9') => {
+                KeyCode::Char('w') if self.mode == Some(DiagnosticMode::User) => {
+                    self.show_health = true;
+                }
+                KeyCode::Char(c @ '0'..='9') => {
                     if let Some(section) = Section::from_number(c as u8 - b'0') {
                         self.current_section = section;
                         self.process_scroll = 0;
                         self.connection_scroll = 0;
                         self.driver_scroll = 0;
                         self.disk_scroll = 0;
+                        self.events_scroll = 0;
                     }
                 }
                 // Scrollable table controls
@@ -382,6 +612,10 @@ impl App {
                         Section::Disk => {
                             self.disk_scroll = self.disk_scroll.saturating_add(1);
                         }
+                        Section::Events => {
+                            let max = self.snapshot.events.len().saturating_sub(1);
+                            self.events_scroll = (self.events_scroll + 1).min(max);
+                        }
                         _ => {}
                     }
                 }
@@ -398,6 +632,9 @@ impl App {
                     Section::Disk => {
                         self.disk_scroll = self.disk_scroll.saturating_sub(1);
                     }
+                    Section::Events => {
+                        self.events_scroll = self.events_scroll.saturating_sub(1);
+                    }
                     _ => {}
                 },
                 KeyCode::Char('c') if self.current_section == Section::Processes => {
@@ -412,14 +649,26 @@ impl App {
                 KeyCode::Char('p') if self.current_section == Section::Processes => {
                     self.process_sort = ProcessSortKey::Pid;
                 }
+                KeyCode::Char('d') if self.current_section == Section::Processes => {
+                    self.process_sort = ProcessSortKey::DiskIo;
+                }
+                KeyCode::Char('g') if self.current_section == Section::Processes => {
+                    self.process_sort = ProcessSortKey::GpuMemory;
+                }
                 // Temperature unit toggle
                 KeyCode::Char('f') => {
                     self.temp_unit = self.temp_unit.toggle();
                 }
+                // Sparkline time window
+                KeyCode::Char('t') => {
+                    self.time_window = self.time_window.next();
+                }
+                KeyCode::Char('e') => self.export_current_table(),
                 // Manual refresh for drivers section (non-blocking)
                 KeyCode::Char('r')
                     if self.current_section == Section::Drivers
-                        && self.driver_scan_handle.is_none() =>
+                        && self.driver_scan_handle.is_none()
+                        && self.replay.is_none() =>
                 {
                     self.start_driver_scan();
                 }
@@ -428,25 +677,71 @@ impl App {
         }
     }
 
-    /// Get overall system health status
-    pub fn overall_health(&self) -> HealthStatus {
-        let cpu_status = HealthStatus::from_percent(self.snapshot.cpu.total_usage as f64);
-        let mem_pct = if self.snapshot.memory.total_bytes > 0 {
-            (self.snapshot.memory.used_bytes as f64 / self.snapshot.memory.total_bytes as f64)
-                * 100.0
-        } else {
-            0.0
+    /// Save the current section's table as CSV to the reports directory,
+    /// redacted and in on-screen order.
+    fn export_current_table(&mut self) {
+        let message = match export_table_for(self.current_section) {
+            Some(which) => {
+                let mut report = DiagnosticReport::from_snapshot(&self.snapshot, false);
+                ui::sections::processes::sort_for_table(
+                    &mut report.processes.list,
+                    self.process_sort,
+                );
+                let table = crate::export::table(&report, which);
+                match crate::export::save_to_reports(&table, which, crate::record::unix_ms()) {
+                    Ok(path) => format!("Saved {} table to {}", which.name(), path.display()),
+                    Err(error) => format!("Export failed: {error}"),
+                }
+            }
+            None => "This section has no table to export".to_string(),
         };
-        let mem_status = HealthStatus::from_percent(mem_pct);
+        self.notice = Some((message, Instant::now()));
+    }
 
-        // Worst of all statuses
-        if cpu_status == HealthStatus::Critical || mem_status == HealthStatus::Critical {
-            HealthStatus::Critical
-        } else if cpu_status == HealthStatus::Warning || mem_status == HealthStatus::Warning {
-            HealthStatus::Warning
-        } else {
-            HealthStatus::Good
+    /// The status notice, while it is still fresh.
+    pub fn active_notice(&self) -> Option<&str> {
+        self.notice
+            .as_ref()
+            .filter(|(_, shown)| shown.elapsed() < NOTICE_DURATION)
+            .map(|(message, _)| message.as_str())
+    }
+
+    /// Playback controls, active only while replaying. Returns whether the
+    /// key was consumed.
+    fn handle_replay_key(&mut self, code: KeyCode) -> bool {
+        let Some(player) = self.replay.as_mut() else {
+            return false;
+        };
+        match code {
+            KeyCode::Char(' ') => player.toggle_play(),
+            KeyCode::Right => player.seek(REPLAY_SEEK, true),
+            KeyCode::Left => player.seek(REPLAY_SEEK, false),
+            KeyCode::Char('.') => player.step(true),
+            KeyCode::Char(',') => player.step(false),
+            KeyCode::Char('+') | KeyCode::Char('=') => player.faster(),
+            KeyCode::Char('-') => player.slower(),
+            KeyCode::Home => player.seek_start(),
+            KeyCode::End => player.seek_end(),
+            _ => return false,
         }
+        true
+    }
+
+    /// Get overall system health status
+    pub fn overall_health(&self) -> HealthStatus {
+        self.snapshot.overall_health()
+    }
+}
+
+/// The exportable table each section shows, if any.
+fn export_table_for(section: Section) -> Option<ExportTable> {
+    match section {
+        Section::Processes => Some(ExportTable::Processes),
+        Section::Network => Some(ExportTable::Connections),
+        Section::Drivers => Some(ExportTable::Drivers),
+        Section::Thermals => Some(ExportTable::Sensors),
+        Section::Disk => Some(ExportTable::Partitions),
+        _ => None,
     }
 }
 
@@ -475,6 +770,9 @@ mod compatibility_tests {
             memory_bytes: 0,
             memory_percent: 0.0,
             status: "Run".into(),
+            disk_read_bytes_per_sec: 0,
+            disk_write_bytes_per_sec: 0,
+            gpu_memory_bytes: None,
         }
     }
 
@@ -571,6 +869,22 @@ mod compatibility_tests {
         );
     }
 
+    #[test]
+    fn health_breakdown_opens_in_user_mode_and_consumes_keys() {
+        let mut technician = App::new(Some(DiagnosticMode::Technician));
+        press(&mut technician, KeyCode::Char('w'));
+        assert!(!technician.show_health);
+
+        let mut user = App::new(Some(DiagnosticMode::User));
+        press(&mut user, KeyCode::Char('w'));
+        assert!(user.show_health);
+        press(&mut user, KeyCode::Char('q'));
+        assert!(!user.should_quit, "the breakdown should consume other keys");
+        press(&mut user, KeyCode::Esc);
+        assert!(!user.show_health);
+        assert!(!user.should_quit);
+    }
+
     #[test]
     fn v2_section_unit_sort_and_scroll_keybindings_are_unchanged() {
         let mut app = App::new(Some(DiagnosticMode::Technician));
@@ -601,6 +915,8 @@ mod compatibility_tests {
             ('M', ProcessSortKey::Memory),
             ('p', ProcessSortKey::Pid),
             ('n', ProcessSortKey::Name),
+            ('d', ProcessSortKey::DiskIo),
+            ('g', ProcessSortKey::GpuMemory),
         ] {
             press(&mut app, KeyCode::Char(key));
             assert_eq!(app.process_sort, sort);
@@ -646,6 +962,103 @@ mod compatibility_tests {
         assert!(!app.should_quit);
     }
 
+    #[test]
+    fn time_window_key_cycles_every_sparkline_window() {
+        let mut app = App::new(Some(DiagnosticMode::User));
+        assert_eq!(app.time_window, TimeWindow::Minute);
+        let mut seen = Vec::new();
+        for _ in 0..5 {
+            press(&mut app, KeyCode::Char('t'));
+            seen.push(app.time_window);
+        }
+        assert_eq!(
+            seen,
+            [
+                TimeWindow::TenMinutes,
+                TimeWindow::Hour,
+                TimeWindow::SixHours,
+                TimeWindow::Day,
+                TimeWindow::Minute,
+            ]
+        );
+    }
+
+    #[test]
+    fn replay_keys_move_playback_and_rebuild_sparklines_from_the_recording() {
+        use crate::record::{RecordingHeader, RecordingWriter, StopReason};
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("capture.ndjson");
+        let mut writer = RecordingWriter::create(
+            path.clone(),
+            u64::MAX,
+            RecordingHeader {
+                kind: "header",
+                format: crate::record::FORMAT,
+                format_version: crate::record::FORMAT_VERSION,
+                schema_version: 1,
+                product_version: "test",
+                target_os: "test",
+                target_arch: "test",
+                privacy: crate::report::PrivacyMetadata {
+                    sensitive_values_included: false,
+                    redacted_fields: Vec::new(),
+                },
+                interval_ms: 1000,
+                sections: vec!["cpu"],
+                started_unix_ms: 0,
+                file_index: 0,
+            },
+        )
+        .expect("create recording");
+        for second in 0..80u64 {
+            let cpu = crate::collectors::cpu::CpuData {
+                total_usage: second as f32,
+                ..Default::default()
+            };
+            let mut sections = serde_json::Map::new();
+            sections.insert("cpu".into(), serde_json::to_value(cpu).unwrap());
+            writer.write_frame(second * 1000, sections).unwrap();
+        }
+        writer.finish(StopReason::Duration).unwrap();
+
+        let mut app = App::new(Some(DiagnosticMode::Technician));
+        app.replay = Some(Player::open(&path).expect("open recording"));
+        app.rebuild_replay_history();
+        assert_eq!(app.cpu_history.as_slice(), [0.0]);
+
+        let replay_press = |app: &mut App, code: KeyCode| {
+            let shown = app.replay_position();
+            press(app, code);
+            app.sync_replay(shown);
+        };
+        replay_press(&mut app, KeyCode::Char(' '));
+        assert!(!app.replay.as_ref().unwrap().playing());
+        replay_press(&mut app, KeyCode::Char('.'));
+        assert_eq!(app.snapshot.cpu.total_usage, 1.0);
+        assert_eq!(app.cpu_history.as_slice(), [0.0, 1.0]);
+
+        replay_press(&mut app, KeyCode::End);
+        assert_eq!(app.snapshot.cpu.total_usage, 79.0);
+        assert_eq!(app.cpu_history.len(), HISTORY_SAMPLES);
+        assert_eq!(app.cpu_history.as_slice().first(), Some(&20.0));
+
+        replay_press(&mut app, KeyCode::Left);
+        assert_eq!(app.snapshot.cpu.total_usage, 69.0);
+        assert_eq!(app.cpu_history.latest(), Some(69.0));
+        assert_eq!(app.cpu_history.as_slice().first(), Some(&10.0));
+
+        replay_press(&mut app, KeyCode::Char('+'));
+        assert_eq!(app.replay.as_ref().unwrap().speed(), 2.0);
+
+        app.current_section = Section::Drivers;
+        press(&mut app, KeyCode::Char('r'));
+        assert!(
+            app.driver_scan_handle.is_none(),
+            "replay never starts a live driver scan"
+        );
+    }
+
     #[tokio::test]
     async fn completed_driver_scan_is_polled_and_duplicate_refresh_is_not_spawned() {
         let mut app = App::new(Some(DiagnosticMode::Technician));
@@ -693,4 +1106,20 @@ mod compatibility_tests {
             handle.abort();
         }
     }
+
+    #[test]
+    fn export_key_reports_sections_without_a_table() {
+        let mut app = App::new(Some(DiagnosticMode::Technician));
+        assert_eq!(app.active_notice(), None);
+        press(&mut app, KeyCode::Char('e'));
+        assert_eq!(
+            app.active_notice(),
+            Some("This section has no table to export")
+        );
+        assert_eq!(
+            export_table_for(Section::Network),
+            Some(ExportTable::Connections)
+        );
+        assert_eq!(export_table_for(Section::Events), None);
+    }
 }
@@ -0,0 +1,290 @@
+//! Saved baselines and configuration-drift warnings.
+//!
+//! `sd300 baseline save NAME` stores the current redacted report in the
+//! settings directory's `baselines` folder and makes it the active baseline.
+//! While one is active the TUI compares the live snapshot with it and raises
+//! `Drift` warnings for the changes that matter on a shared machine: new
+//! listening ports, changed driver versions, devices that disappeared, a BIOS
+//! update, or a different amount of installed memory. The comparison is
+//! `sd300 diff`'s, narrowed to those changes.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use crate::cli::{parse_baseline_name, BaselineAction, BaselineArgs};
+use crate::collectors::drivers::DriverScanStatus;
+use crate::collectors::{DiagnosticWarning, SystemSnapshot, WarningSeverity};
+use crate::diff::{diff_reports, Category, Change, ChangeKind};
+use crate::error::{AppError, Result};
+use crate::observation::Observation;
+use crate::report::DiagnosticReport;
+use crate::settings;
+
+pub const WARNING_SOURCE: &str = "Drift";
+const ACTIVE_FILE: &str = "active";
+
+pub struct Baseline {
+    pub name: String,
+    report: Value,
+}
+
+impl Baseline {
+    pub fn new(name: impl Into<String>, report: Value) -> Self {
+        Self {
+            name: name.into(),
+            report,
+        }
+    }
+
+    /// The active baseline, if one has been chosen.
+    pub fn load_active() -> Result<Option<Self>> {
+        let dir = baselines_dir()?;
+        let name = match fs::read_to_string(dir.join(ACTIVE_FILE)) {
+            Ok(name) => parse_baseline_name(name.trim()).map_err(AppError::platform)?,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        let bytes = fs::read(baseline_path(&dir, &name)).map_err(|error| {
+            AppError::platform(format!("cannot read baseline '{name}': {error}"))
+        })?;
+        let report = serde_json::from_slice(&bytes).map_err(|error| {
+            AppError::platform(format!("baseline '{name}' is not valid JSON: {error}"))
+        })?;
+        Ok(Some(Self::new(name, report)))
+    }
+
+    /// Drift warnings for `snapshot`. Drivers and drive health are compared
+    /// only once their background scans have finished, so a scan in progress
+    /// never reads as every device disappearing.
+    pub fn drift(&self, snapshot: &SystemSnapshot) -> Vec<DiagnosticWarning> {
+        let Ok(mut current) =
+            serde_json::to_value(DiagnosticReport::from_snapshot(snapshot, false))
+        else {
+            return Vec::new();
+        };
+        if let Some(current) = current.as_object_mut() {
+            if snapshot.drivers.scan_status != DriverScanStatus::Success {
+                current.remove("drivers");
+            }
+            if snapshot.disk_health.health_status == Observation::default() {
+                current.remove("disk_health");
+            }
+        }
+        drift_changes(&self.report, &current)
+            .iter()
+            .map(|change| DiagnosticWarning {
+                source: WARNING_SOURCE.into(),
+                message: drift_message(change),
+                severity: WarningSeverity::Warning,
+            })
+            .collect()
+    }
+}
+
+/// The subset of report changes that count as drift.
+pub fn drift_changes(baseline: &Value, current: &Value) -> Vec<Change> {
+    let Ok(diff) = diff_reports(baseline, current) else {
+        return Vec::new();
+    };
+    diff.changes
+        .into_iter()
+        .filter(|change| match (change.category, change.kind) {
+            (Category::ListeningPorts, ChangeKind::Added) => true,
+            (Category::Drivers, ChangeKind::Changed) => change.item.ends_with(" driver"),
+            (Category::Hardware, ChangeKind::Removed) => true,
+            (Category::Hardware, ChangeKind::Changed) => {
+                matches!(change.item.as_str(), "BIOS version" | "Installed memory")
+            }
+            _ => false,
+        })
+        .collect()
+}
+
+fn drift_message(change: &Change) -> String {
+    let before = change.before.as_deref().unwrap_or("unknown");
+    let after = change.after.as_deref().unwrap_or("unknown");
+    match (change.category, change.kind) {
+        (Category::ListeningPorts, _) => match &change.after {
+            Some(process) => format!("New listening port {} ({process})", change.item),
+            None => format!("New listening port {}", change.item),
+        },
+        (Category::Drivers, _) => format!(
+            "Driver version changed: {} {before} -> {after}",
+            change.item.trim_end_matches(" driver")
+        ),
+        (_, ChangeKind::Removed) => format!("No longer present: {}", change.item),
+        _ => format!("{} changed: {before} -> {after}", change.item),
+    }
+}
+
+pub async fn run(args: &BaselineArgs) -> Result<()> {
+    let dir = baselines_dir()?;
+    match &args.action {
+        BaselineAction::Save { name } => {
+            let report = DiagnosticReport::collect(false).await;
+            let mut bytes = serde_json::to_vec_pretty(&report).map_err(|error| {
+                AppError::platform(format!("JSON serialization failed: {error}"))
+            })?;
+            bytes.push(b'\n');
+            fs::create_dir_all(&dir)?;
+            settings::restrict_directory(&dir).map_err(AppError::platform)?;
+            settings::write_private_atomic(&baseline_path(&dir, name), ".baseline", &bytes)
+                .map_err(AppError::platform)?;
+            set_active(&dir, name)?;
+            println!("Saved baseline '{name}'; the TUI now warns about drift from it.");
+        }
+        BaselineAction::Use { name } => {
+            if !baseline_path(&dir, name).is_file() {
+                return Err(AppError::platform(format!(
+                    "no baseline named '{name}'; `sd300 baseline list` shows saved baselines"
+                )));
+            }
+            set_active(&dir, name)?;
+            println!("Drift is now checked against baseline '{name}'.");
+        }
+        BaselineAction::Off => {
+            remove_if_present(&dir.join(ACTIVE_FILE))?;
+            println!("Drift checking is off; saved baselines were kept.");
+        }
+        BaselineAction::List => {
+            let active = fs::read_to_string(dir.join(ACTIVE_FILE)).unwrap_or_default();
+            let mut saved = Vec::new();
+            if let Ok(entries) = fs::read_dir(&dir) {
+                for entry in entries.flatten() {
+                    let file_name = entry.file_name().to_string_lossy().into_owned();
+                    let Some(name) = file_name.strip_suffix(".json") else {
+                        continue;
+                    };
+                    if parse_baseline_name(name).is_err() {
+                        continue;
+                    }
+                    let saved_unix_ms = entry
+                        .metadata()
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+                        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+                    saved.push((name.to_string(), saved_unix_ms));
+                }
+            }
+            if saved.is_empty() {
+                println!("No saved baselines. Save one with `sd300 baseline save NAME`.");
+            }
+            saved.sort();
+            for (name, saved_unix_ms) in saved {
+                println!(
+                    "{} {name:<24} saved {} UTC",
+                    if name == active.trim() { '*' } else { ' ' },
+                    crate::replay::format_utc(saved_unix_ms)
+                );
+            }
+        }
+        BaselineAction::Remove { name } => {
+            let path = baseline_path(&dir, name);
+            if !path.is_file() {
+                return Err(AppError::platform(format!("no baseline named '{name}'")));
+            }
+            fs::remove_file(path)?;
+            if fs::read_to_string(dir.join(ACTIVE_FILE)).is_ok_and(|active| active.trim() == name) {
+                remove_if_present(&dir.join(ACTIVE_FILE))?;
+            }
+            println!("Removed baseline '{name}'.");
+        }
+    }
+    Ok(())
+}
+
+fn baselines_dir() -> Result<PathBuf> {
+    settings::baselines_dir().map_err(AppError::platform)
+}
+
+fn baseline_path(dir: &std::path::Path, name: &str) -> PathBuf {
+    dir.join(format!("{name}.json"))
+}
+
+fn set_active(dir: &std::path::Path, name: &str) -> Result<()> {
+    settings::write_private_atomic(&dir.join(ACTIVE_FILE), ".active", name.as_bytes())
+        .map_err(AppError::platform)
+}
+
+fn remove_if_present(path: &std::path::Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(error.into()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn report() -> Value {
+        json!({
+            "schema_version": 1,
+            "system": { "bios_version": "1.0.4", "total_memory_bytes": 17_179_869_184u64 },
+            "drivers": {
+                "network": [{ "name": "Wi-Fi", "driver_version": "22.1", "status": "OK" }],
+                "usb": [{ "name": "Webcam", "driver_version": "1.0", "status": "OK" }],
+                "services": [{ "name": "cups", "is_running": true }]
+            },
+            "network_diagnostics": { "listening_ports": [
+                { "protocol": "TCP", "local_addr": "0.0.0.0", "local_port": 22, "process_name": "sshd" }
+            ] },
+            "warnings": []
+        })
+    }
+
+    #[test]
+    fn only_lab_relevant_changes_count_as_drift() {
+        let baseline = report();
+        let mut current = report();
+        current["system"]["bios_version"] = json!("1.0.7");
+        current["system"]["total_memory_bytes"] = json!(8_589_934_592u64);
+        current["drivers"]["network"][0]["driver_version"] = json!("22.2");
+        current["drivers"]["network"][0]["status"] = json!("Error");
+        current["drivers"]["usb"] = json!([]);
+        current["drivers"]["services"][0]["is_running"] = json!(false);
+        current["network_diagnostics"]["listening_ports"] = json!([
+            { "protocol": "TCP", "local_addr": "0.0.0.0", "local_port": 8080, "process_name": "python3" }
+        ]);
+        current["warnings"] =
+            json!([{ "source": "Network", "message": "DNS slow", "severity": "warning" }]);
+
+        let messages: Vec<String> = drift_changes(&baseline, &current)
+            .iter()
+            .map(drift_message)
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "Installed memory changed: 16.0 GB -> 8.0 GB",
+                "BIOS version changed: 1.0.4 -> 1.0.7",
+                "No longer present: Webcam (usb)",
+                "Driver version changed: Wi-Fi (network) 22.1 -> 22.2",
+                "New listening port TCP 0.0.0.0:8080 (python3)",
+            ]
+        );
+    }
+
+    #[test]
+    fn unfinished_driver_scans_are_not_read_as_missing_devices() {
+        let baseline = Baseline::new("lab", report());
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.drivers.scan_status = DriverScanStatus::Scanning;
+        let drift = baseline.drift(&snapshot);
+        assert!(drift
+            .iter()
+            .all(|warning| !warning.message.contains("Webcam")));
+        assert!(drift.iter().all(|warning| warning.source == WARNING_SOURCE));
+
+        snapshot.drivers.scan_status = DriverScanStatus::Success;
+        assert!(baseline
+            .drift(&snapshot)
+            .iter()
+            .any(|warning| warning.message == "No longer present: Webcam (usb)"));
+    }
+}
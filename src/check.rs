@@ -0,0 +1,892 @@
+//! `sd300 check`: a Nagios/Icinga-compatible plugin.
+//!
+//! Each selected probe is rated OK, WARNING, CRITICAL, or UNKNOWN, and the
+//! check prints one status line with performance data and exits with the
+//! worst rating's plugin code. A collector that could not observe its
+//! subject (unavailable, permission denied, unsupported) makes its probe
+//! UNKNOWN rather than OK: a check that cannot see a failing drive must not
+//! report it healthy. Usage and temperature limits default to the configured
+//! health thresholds.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::cli::{CheckArgs, CheckProbe};
+use crate::collectors::disk::DiskData;
+use crate::collectors::disk_health::{DiskHealthData, DiskHealthStatus};
+use crate::collectors::drivers::{DriverData, DriverScanStatus};
+use crate::collectors::network_diag::NetworkDiagData;
+use crate::collectors::thermals::ThermalData;
+use crate::collectors::SystemSnapshot;
+use crate::observation::Observation;
+use crate::report::probe_checked;
+use crate::settings::{self, Limits, ThresholdSettings};
+use crate::types::HealthStatus;
+
+/// Plugin states, ordered as monitoring-plugins ranks them when combining
+/// results: UNKNOWN outranks OK but not a real problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum State {
+    Ok,
+    Unknown,
+    Warning,
+    Critical,
+}
+
+impl State {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            Self::Ok => 0,
+            Self::Warning => 1,
+            Self::Critical => 2,
+            Self::Unknown => 3,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Warning => "WARNING",
+            Self::Critical => "CRITICAL",
+            Self::Unknown => "UNKNOWN",
+        }
+    }
+
+    fn from_health(status: HealthStatus) -> Self {
+        match status {
+            HealthStatus::Good => Self::Ok,
+            HealthStatus::Warning => Self::Warning,
+            HealthStatus::Critical => Self::Critical,
+            HealthStatus::Unknown => Self::Unknown,
+        }
+    }
+
+    /// WARNING at `warning` or more, CRITICAL at `critical` or more.
+    fn from_count(count: u64, warning: u64, critical: Option<u64>) -> Self {
+        if critical.is_some_and(|critical| count >= critical) {
+            Self::Critical
+        } else if count >= warning {
+            Self::Warning
+        } else {
+            Self::Ok
+        }
+    }
+}
+
+/// One performance-data value in the plugin format
+/// `'label'=value[unit];warn;crit;min;max`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Perf {
+    pub label: String,
+    pub value: f64,
+    pub unit: &'static str,
+    pub warning: Option<f64>,
+    pub critical: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl Perf {
+    fn new(label: impl Into<String>, value: f64, unit: &'static str) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            unit,
+            warning: None,
+            critical: None,
+            min: None,
+            max: None,
+        }
+    }
+
+    fn limits(mut self, limits: Limits) -> Self {
+        self.warning = Some(limits.warning);
+        self.critical = Some(limits.critical);
+        self
+    }
+
+    fn range(mut self, min: f64, max: Option<f64>) -> Self {
+        self.min = Some(min);
+        self.max = max;
+        self
+    }
+}
+
+impl fmt::Display for Perf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // `=` ends a label and a lone `'` ends a quoted one.
+        let label = self.label.replace('=', "_").replace('\'', "''");
+        write!(f, "'{label}'={}{}", number(self.value), self.unit)?;
+        let fields = [self.warning, self.critical, self.min, self.max];
+        let used = fields
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |i| i + 1);
+        for field in &fields[..used] {
+            f.write_str(";")?;
+            if let Some(value) = field {
+                f.write_str(&number(*value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+/// One probe's rating.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeResult {
+    pub state: State,
+    pub summary: String,
+    pub perfdata: Vec<Perf>,
+}
+
+impl ProbeResult {
+    fn new(state: State, summary: impl Into<String>) -> Self {
+        Self {
+            state,
+            summary: summary.into(),
+            perfdata: Vec::new(),
+        }
+    }
+
+    /// UNKNOWN unless the collector observed its subject.
+    fn unobserved(observation: &Observation, subject: &str) -> Option<Self> {
+        if observation.is_available() {
+            return None;
+        }
+        let status = serde_json::to_value(&observation.status)
+            .ok()
+            .and_then(|value| value.as_str().map(|status| status.replace('_', " ")))
+            .unwrap_or_default();
+        let detail = observation.detail.as_deref().unwrap_or("no detail");
+        Some(Self::new(
+            State::Unknown,
+            format!("{subject} {status}: {detail}"),
+        ))
+    }
+}
+
+/// The single status line: problems first, then the rest in probe order,
+/// with every probe's performance data after the `|`.
+pub fn status_line(results: &[ProbeResult]) -> (State, String) {
+    let state = results
+        .iter()
+        .map(|result| result.state)
+        .max()
+        .unwrap_or(State::Unknown);
+    let mut ordered: Vec<&ProbeResult> = results.iter().collect();
+    ordered.sort_by_key(|result| std::cmp::Reverse(result.state));
+    let summary = ordered
+        .iter()
+        .map(|result| one_line(&result.summary))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let mut line = format!("SD300 {} - {summary}", state.label());
+    let perfdata: Vec<String> = results
+        .iter()
+        .flat_map(|result| &result.perfdata)
+        .map(ToString::to_string)
+        .collect();
+    if !perfdata.is_empty() {
+        line.push_str(" | ");
+        line.push_str(&perfdata.join(" "));
+    }
+    (state, line)
+}
+
+/// Collector messages may span lines or contain the perfdata separator.
+fn one_line(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "/")
+}
+
+pub fn disk_health(data: &DiskHealthData) -> ProbeResult {
+    if let Some(unknown) = ProbeResult::unobserved(&data.health_status, "disk health") {
+        return unknown;
+    }
+    if data.drives.is_empty() {
+        return ProbeResult::new(State::Unknown, "no drives reported");
+    }
+    let mut result = ProbeResult::new(State::Ok, "");
+    let mut problems = Vec::new();
+    for drive in &data.drives {
+        let state = match drive.health_status {
+            DiskHealthStatus::Critical => State::Critical,
+            DiskHealthStatus::Warning => State::Warning,
+            DiskHealthStatus::Healthy | DiskHealthStatus::Unknown => State::Ok,
+        };
+        if state != State::Ok {
+            problems.push(format!(
+                "drive {} {}",
+                drive.model,
+                drive.health_status.user_label()
+            ));
+            result.state = result.state.max(state);
+        }
+        if let Some(temperature) = drive.temperature_celsius {
+            result.perfdata.push(Perf::new(
+                format!("{} temperature", drive.model),
+                temperature,
+                "",
+            ));
+        }
+        if let Some(wear) = drive.wear_percent {
+            result.perfdata.push(
+                Perf::new(format!("{} wear", drive.model), wear as f64, "%")
+                    .range(0.0, Some(100.0)),
+            );
+        }
+    }
+    result.summary = if problems.is_empty() {
+        format!("{} drives healthy", data.drives.len())
+    } else {
+        problems.join(", ")
+    };
+    result
+}
+
+pub fn partitions(
+    disk: &DiskData,
+    thresholds: &ThresholdSettings,
+    args: &CheckArgs,
+) -> ProbeResult {
+    let mut result = ProbeResult::new(State::Ok, "");
+    let mut problems = Vec::new();
+    let mut rated = 0;
+    for partition in disk.partitions.iter().filter(|p| p.total_bytes > 0) {
+        let configured = thresholds.disk_for(partition);
+        let limits = override_limits(configured, args.disk_warning, args.disk_critical);
+        let used = partition.usage_percent();
+        let state = State::from_health(HealthStatus::from_limits(used, limits));
+        let name = if partition.mount_point.is_empty() {
+            &partition.name
+        } else {
+            &partition.mount_point
+        };
+        if state != State::Ok {
+            problems.push(format!("{name} {used:.0}% used"));
+            result.state = result.state.max(state);
+        }
+        result.perfdata.push(
+            Perf::new(name.clone(), used, "%")
+                .limits(limits)
+                .range(0.0, Some(100.0)),
+        );
+        rated += 1;
+    }
+    result.summary = match (rated, problems.is_empty()) {
+        (0, _) => return ProbeResult::new(State::Unknown, "no partitions reported"),
+        (count, true) => format!("{count} partitions below limits"),
+        (_, false) => problems.join(", "),
+    };
+    result
+}
+
+pub fn thermals(
+    thermals: &ThermalData,
+    thresholds: &ThresholdSettings,
+    args: &CheckArgs,
+) -> ProbeResult {
+    let readings = [
+        ("processor", thermals.cpu_temp, thresholds.cpu_temperature),
+        ("graphics", thermals.gpu_temp, thresholds.gpu_temperature),
+    ];
+    let mut result = ProbeResult::new(State::Ok, "");
+    let mut parts = Vec::new();
+    for (name, temperature, configured) in readings {
+        let Some(temperature) = temperature else {
+            continue;
+        };
+        let limits = override_limits(
+            configured,
+            args.temperature_warning,
+            args.temperature_critical,
+        );
+        let state = State::from_health(HealthStatus::from_limits(temperature, limits));
+        result.state = result.state.max(state);
+        parts.push(format!("{name} {temperature:.0}°C"));
+        result
+            .perfdata
+            .push(Perf::new(format!("{name}_temperature"), temperature, "").limits(limits));
+    }
+    if parts.is_empty() {
+        return ProbeResult::unobserved(&thermals.cpu_temperature_status, "temperature")
+            .unwrap_or_else(|| ProbeResult::new(State::Unknown, "no temperature reported"));
+    }
+    result.summary = parts.join(", ");
+    result
+}
+
+pub fn connectivity(diag: &NetworkDiagData, args: &CheckArgs) -> ProbeResult {
+    let internet = &diag.internet;
+    if !probe_checked(internet) {
+        return ProbeResult::new(State::Unknown, "connectivity not checked");
+    }
+    let mut result = ProbeResult::new(State::Ok, "internet reachable");
+    if !internet.reachable {
+        result.state = State::Critical;
+        result.summary = match &internet.error {
+            Some(error) => format!("internet unreachable: {error}"),
+            None => "internet unreachable".into(),
+        };
+    }
+    if probe_checked(&diag.gateway) && !diag.gateway.reachable {
+        result.state = result.state.max(State::Warning);
+        result.summary.push_str(", gateway unreachable");
+    }
+    if !diag.dns.domain.is_empty() && !diag.dns.resolved {
+        result.state = result.state.max(State::Warning);
+        result.summary.push_str(", DNS lookup failed");
+    }
+    if let Some(latency) = internet.latency_ms {
+        let warning = args.latency_warning.map(|ms| ms as f64);
+        let critical = args.latency_critical.map(|ms| ms as f64);
+        let state = if critical.is_some_and(|limit| latency >= limit) {
+            State::Critical
+        } else if warning.is_some_and(|limit| latency >= limit) {
+            State::Warning
+        } else {
+            State::Ok
+        };
+        if state != State::Ok {
+            result
+                .summary
+                .push_str(&format!(", round trip {latency:.0} ms"));
+        }
+        result.state = result.state.max(state);
+        let mut perf = Perf::new("internet_latency", latency, "ms").range(0.0, None);
+        perf.warning = warning;
+        perf.critical = critical;
+        result.perfdata.push(perf);
+    }
+    if let Some(latency) = diag.gateway.latency_ms {
+        result
+            .perfdata
+            .push(Perf::new("gateway_latency", latency, "ms").range(0.0, None));
+    }
+    if let Some(latency) = diag.dns.resolution_ms {
+        result
+            .perfdata
+            .push(Perf::new("dns_resolution", latency, "ms").range(0.0, None));
+    }
+    result
+}
+
+/// UNKNOWN when the driver scan failed; it also lists the services. The
+/// check runs the scan to completion itself, and only the Windows scan
+/// reports success explicitly.
+fn driver_scan(drivers: &DriverData) -> Option<ProbeResult> {
+    match &drivers.scan_status {
+        DriverScanStatus::ScanFailed(message) => Some(ProbeResult::new(
+            State::Unknown,
+            format!("driver scan failed: {message}"),
+        )),
+        _ => None,
+    }
+}
+
+pub fn drivers(drivers: &DriverData, args: &CheckArgs) -> ProbeResult {
+    if let Some(unknown) = driver_scan(drivers) {
+        return unknown;
+    }
+    let names: Vec<&str> = drivers
+        .attention_devices()
+        .map(|device| device.name.as_str())
+        .collect();
+    let count = names.len() as u64;
+    let state = State::from_count(count, args.drivers_warning, args.drivers_critical);
+    let summary = match names.as_slice() {
+        [] => "no devices need driver attention".to_string(),
+        [name] => format!("1 device needs driver attention: {name}"),
+        names => format!(
+            "{count} devices need driver attention: {}",
+            names.join(", ")
+        ),
+    };
+    let mut perf = Perf::new("drivers_attention", count as f64, "").range(0.0, None);
+    perf.warning = Some(args.drivers_warning as f64);
+    perf.critical = args.drivers_critical.map(|count| count as f64);
+    ProbeResult {
+        state,
+        summary,
+        perfdata: vec![perf],
+    }
+}
+
+pub fn services(drivers: &DriverData, args: &CheckArgs) -> ProbeResult {
+    if let Some(unknown) = driver_scan(drivers) {
+        return unknown;
+    }
+    let missing: Vec<&str> = args
+        .services
+        .iter()
+        .filter(|name| {
+            !drivers
+                .services
+                .iter()
+                .any(|service| &service.name == *name)
+        })
+        .map(String::as_str)
+        .collect();
+    // Without `--service`, only services set to run count: a stopped service
+    // that is absent or disabled on this machine is not a failure.
+    let selected: Vec<_> = drivers
+        .services
+        .iter()
+        .filter(|service| {
+            if args.services.is_empty() {
+                service.expected_running || service.is_running
+            } else {
+                args.services.contains(&service.name)
+            }
+        })
+        .collect();
+    let stopped: Vec<&str> = selected
+        .iter()
+        .filter(|service| !service.is_running)
+        .map(|service| service.name.as_str())
+        .collect();
+    let count = stopped.len() as u64;
+    let mut state = State::from_count(count, args.services_warning, args.services_critical);
+    let mut summary = if stopped.is_empty() {
+        format!("{} services running", selected.len())
+    } else {
+        format!("stopped services: {}", stopped.join(", "))
+    };
+    if !missing.is_empty() {
+        state = state.max(State::Unknown);
+        summary.push_str(&format!(", not monitored here: {}", missing.join(", ")));
+    }
+    let mut perf =
+        Perf::new("services_stopped", count as f64, "").range(0.0, Some(selected.len() as f64));
+    perf.warning = Some(args.services_warning as f64);
+    perf.critical = args.services_critical.map(|count| count as f64);
+    ProbeResult {
+        state,
+        summary,
+        perfdata: vec![perf],
+    }
+}
+
+/// Argument limits replace the configured ones field by field.
+fn override_limits(configured: Limits, warning: Option<u64>, critical: Option<u64>) -> Limits {
+    Limits::new(
+        warning.map_or(configured.warning, |value| value as f64),
+        critical.map_or(configured.critical, |value| value as f64),
+    )
+}
+
+/// Refuse a single limit flag that would put the warning limit above a
+/// configured critical limit, or the critical limit below a configured
+/// warning limit, for any partition or temperature it applies to.
+fn check_overrides(thresholds: &ThresholdSettings, args: &CheckArgs) -> Result<(), String> {
+    let disks = std::iter::once((None, thresholds.disk)).chain(
+        thresholds
+            .disks
+            .iter()
+            .map(|(name, limits)| (Some(name.as_str()), *limits)),
+    );
+    let temperatures = [
+        (Some("processor"), thresholds.cpu_temperature),
+        (Some("graphics"), thresholds.gpu_temperature),
+    ];
+    let checks = disks
+        .map(|configured| ("disk", args.disk_warning, args.disk_critical, configured))
+        .chain(temperatures.into_iter().map(|configured| {
+            (
+                "temperature",
+                args.temperature_warning,
+                args.temperature_critical,
+                configured,
+            )
+        }));
+    for (name, warning, critical, (subject, configured)) in checks {
+        let limits = override_limits(configured, warning, critical);
+        if limits.warning <= limits.critical {
+            continue;
+        }
+        let subject = subject
+            .map(|subject| format!(" for {subject}"))
+            .unwrap_or_default();
+        return Err(if warning.is_some() {
+            format!(
+                "--{name}-warning {} is above the configured critical limit{subject} ({})",
+                limits.warning, limits.critical
+            )
+        } else {
+            format!(
+                "--{name}-critical {} is below the configured warning limit{subject} ({})",
+                limits.critical, limits.warning
+            )
+        });
+    }
+    Ok(())
+}
+
+/// What the selected probes need, collected concurrently. `None` means
+/// no selected probe needed it.
+struct Collected {
+    snapshot: Option<Result<SystemSnapshot, String>>,
+    disk_health: Option<Result<DiskHealthData, String>>,
+    drivers: Option<Result<DriverData, String>>,
+    connectivity: Option<Result<NetworkDiagData, String>>,
+}
+
+async fn blocking<T: Send + 'static>(needed: bool, work: fn() -> T) -> Option<Result<T, String>> {
+    if !needed {
+        return None;
+    }
+    Some(
+        tokio::task::spawn_blocking(work)
+            .await
+            .map_err(|error| error.to_string()),
+    )
+}
+
+async fn collect(probes: &[CheckProbe]) -> Collected {
+    let wants = |probe| probes.contains(&probe);
+    let (snapshot, disk_health, drivers, connectivity) = tokio::join!(
+        blocking(
+            wants(CheckProbe::Partitions) || wants(CheckProbe::Thermals),
+            || {
+                let mut snapshot = SystemSnapshot::default();
+                snapshot.refresh_slow();
+                snapshot
+            }
+        ),
+        blocking(wants(CheckProbe::DiskHealth), || {
+            crate::collectors::disk_health::collect().0
+        }),
+        blocking(
+            wants(CheckProbe::Drivers) || wants(CheckProbe::Services),
+            crate::collectors::drivers::collect,
+        ),
+        blocking(wants(CheckProbe::Connectivity), || {
+            crate::collectors::network_diag::collect_connectivity().0
+        }),
+    );
+    Collected {
+        snapshot,
+        disk_health,
+        drivers,
+        connectivity,
+    }
+}
+
+/// Whether `args` (program name first) run `sd300 check`, whose argument
+/// errors must be reported as UNKNOWN rather than with clap's exit code 2,
+/// which monitoring systems read as CRITICAL.
+pub fn is_invocation(args: &[std::ffi::OsString]) -> bool {
+    args.iter()
+        .skip(1)
+        .find(|arg| !arg.to_string_lossy().starts_with('-'))
+        .is_some_and(|arg| arg == "check")
+}
+
+/// Print an argument error as an UNKNOWN status line and return the exit
+/// code. Help and version requests are not errors and exit as usual.
+pub fn usage_error(error: clap::Error) -> i32 {
+    if !error.use_stderr() {
+        error.exit();
+    }
+    let rendered = error.render().to_string();
+    let message = rendered
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("error: ");
+    println!("SD300 UNKNOWN - {}", one_line(message));
+    State::Unknown.exit_code()
+}
+
+/// Run the selected probes, print the status line, and return the exit
+/// code.
+pub async fn run(args: &CheckArgs) -> i32 {
+    let (state, line) = match check(args).await {
+        Ok(results) => status_line(&results),
+        Err(message) => (State::Unknown, format!("SD300 UNKNOWN - {message}")),
+    };
+    println!("{line}");
+    state.exit_code()
+}
+
+async fn check(args: &CheckArgs) -> Result<Vec<ProbeResult>, String> {
+    for (name, warning, critical) in [
+        ("disk", args.disk_warning, args.disk_critical),
+        (
+            "temperature",
+            args.temperature_warning,
+            args.temperature_critical,
+        ),
+        ("latency", args.latency_warning, args.latency_critical),
+        ("drivers", Some(args.drivers_warning), args.drivers_critical),
+        (
+            "services",
+            Some(args.services_warning),
+            args.services_critical,
+        ),
+    ] {
+        if let (Some(warning), Some(critical)) = (warning, critical) {
+            if warning > critical {
+                return Err(format!(
+                    "--{name}-warning must not be above --{name}-critical"
+                ));
+            }
+        }
+    }
+    let probes: Vec<CheckProbe> = if args.probes.is_empty() {
+        CheckProbe::ALL.to_vec()
+    } else {
+        args.probes.clone()
+    };
+    let shared = settings::load_shared();
+    shared.print_problems();
+    let thresholds = shared.thresholds.unwrap_or_default();
+    check_overrides(&thresholds, args)?;
+    let collected = tokio::time::timeout(Duration::from_secs(args.timeout), collect(&probes))
+        .await
+        .map_err(|_| format!("probes did not finish within {} seconds", args.timeout))?;
+
+    let mut results = Vec::with_capacity(probes.len());
+    for probe in probes {
+        let (subject, outcome) = match probe {
+            CheckProbe::DiskHealth => (
+                "disk health",
+                collected
+                    .disk_health
+                    .as_ref()
+                    .map(|data| data.as_ref().map(disk_health)),
+            ),
+            CheckProbe::Partitions => (
+                "disk",
+                collected.snapshot.as_ref().map(|snapshot| {
+                    snapshot
+                        .as_ref()
+                        .map(|snapshot| partitions(&snapshot.disk, &thresholds, args))
+                }),
+            ),
+            CheckProbe::Thermals => (
+                "thermal",
+                collected.snapshot.as_ref().map(|snapshot| {
+                    snapshot
+                        .as_ref()
+                        .map(|snapshot| thermals(&snapshot.thermals, &thresholds, args))
+                }),
+            ),
+            CheckProbe::Connectivity => (
+                "connectivity",
+                collected
+                    .connectivity
+                    .as_ref()
+                    .map(|data| data.as_ref().map(|data| connectivity(data, args))),
+            ),
+            CheckProbe::Drivers => (
+                "driver",
+                collected
+                    .drivers
+                    .as_ref()
+                    .map(|data| data.as_ref().map(|data| drivers(data, args))),
+            ),
+            CheckProbe::Services => (
+                "driver",
+                collected
+                    .drivers
+                    .as_ref()
+                    .map(|data| data.as_ref().map(|data| services(data, args))),
+            ),
+        };
+        results.push(match outcome {
+            Some(Ok(result)) => result,
+            Some(Err(error)) => ProbeResult::new(
+                State::Unknown,
+                format!("{subject} collector failed: {error}"),
+            ),
+            None => unreachable!("every selected probe's data is collected"),
+        });
+    }
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Command};
+    use crate::collectors::disk::{DiskType, PartitionInfo};
+    use crate::collectors::drivers::ServiceInfo;
+    use clap::Parser;
+
+    fn args(extra: &[&str]) -> CheckArgs {
+        let mut argv = vec!["sd300", "check"];
+        argv.extend_from_slice(extra);
+        match Cli::try_parse_from(argv).expect("parse").command {
+            Some(Command::Check(args)) => args,
+            other => panic!("expected check, got {other:?}"),
+        }
+    }
+
+    fn partition(mount_point: &str, used: u64) -> PartitionInfo {
+        PartitionInfo {
+            name: "sda1".into(),
+            mount_point: mount_point.into(),
+            filesystem: "ext4".into(),
+            total_bytes: 100,
+            used_bytes: used,
+            available_bytes: 100 - used,
+            is_removable: false,
+            disk_type: DiskType::Ssd,
+        }
+    }
+
+    #[test]
+    fn status_line_leads_with_the_worst_state_and_formats_perfdata() {
+        let disk = DiskData {
+            partitions: vec![partition("/", 50), partition("/var log", 93)],
+        };
+        let partitions = partitions(&disk, &ThresholdSettings::default(), &args(&[]));
+        assert_eq!(partitions.state, State::Critical);
+
+        let drivers = drivers(
+            &DriverData {
+                scan_status: DriverScanStatus::ScanFailed("access denied\nretry".into()),
+                ..DriverData::default()
+            },
+            &args(&[]),
+        );
+        let (state, line) = status_line(&[drivers, partitions]);
+        assert_eq!(state.exit_code(), 2);
+        assert_eq!(
+            line,
+            "SD300 CRITICAL - /var log 93% used; driver scan failed: access denied retry \
+             | '/'=50%;75;90;0;100 '/var log'=93%;75;90;0;100"
+        );
+    }
+
+    #[test]
+    fn argument_thresholds_override_the_configured_limits() {
+        let disk = DiskData {
+            partitions: vec![partition("/", 93)],
+        };
+        let result = partitions(
+            &disk,
+            &ThresholdSettings::default(),
+            &args(&["--disk-warning", "92", "--disk-critical", "98"]),
+        );
+        assert_eq!(result.state, State::Warning);
+        assert_eq!(result.perfdata[0].to_string(), "'/'=93%;92;98;0;100");
+    }
+
+    #[test]
+    fn single_limit_flags_must_agree_with_the_configured_limits() {
+        let defaults = ThresholdSettings::default();
+        assert!(check_overrides(&defaults, &args(&["--disk-warning", "85"])).is_ok());
+        assert_eq!(
+            check_overrides(&defaults, &args(&["--disk-warning", "95"])).unwrap_err(),
+            "--disk-warning 95 is above the configured critical limit (90)"
+        );
+        assert_eq!(
+            check_overrides(&defaults, &args(&["--temperature-critical", "72"])).unwrap_err(),
+            "--temperature-critical 72 is below the configured warning limit for graphics (75)"
+        );
+
+        let mut thresholds = ThresholdSettings::default();
+        thresholds
+            .disks
+            .insert("/data".into(), Limits::new(60.0, 70.0));
+        assert_eq!(
+            check_overrides(&thresholds, &args(&["--disk-warning", "80"])).unwrap_err(),
+            "--disk-warning 80 is above the configured critical limit for /data (70)"
+        );
+    }
+
+    #[test]
+    fn unobserved_collectors_are_unknown() {
+        let data = DiskHealthData {
+            health_status: Observation::permission_denied("smartctl", "run as root"),
+            ..DiskHealthData::default()
+        };
+        let result = disk_health(&data);
+        assert_eq!(result.state, State::Unknown);
+        assert_eq!(result.summary, "disk health permission denied: run as root");
+
+        let thermals = thermals(
+            &ThermalData::default(),
+            &ThresholdSettings::default(),
+            &args(&[]),
+        );
+        assert_eq!(thermals.state, State::Unknown);
+        assert_eq!(State::Unknown.exit_code(), 3);
+    }
+
+    #[test]
+    fn services_count_stopped_ones_and_flag_unmonitored_names() {
+        let data = DriverData {
+            services: vec![
+                ServiceInfo {
+                    name: "bluetooth".into(),
+                    display_name: "Bluetooth".into(),
+                    is_running: false,
+                    expected_running: true,
+                },
+                ServiceInfo {
+                    name: "NetworkManager".into(),
+                    display_name: "Network Manager".into(),
+                    is_running: true,
+                    expected_running: true,
+                },
+            ],
+            ..DriverData::default()
+        };
+        let result = services(&data, &args(&["--service", "NetworkManager"]));
+        assert_eq!(result.state, State::Ok);
+        assert_eq!(
+            result.perfdata[0].to_string(),
+            "'services_stopped'=0;1;;0;1"
+        );
+
+        let result = services(&data, &args(&["--services-critical", "1"]));
+        assert_eq!(result.state, State::Critical);
+        assert_eq!(result.summary, "stopped services: bluetooth");
+
+        let result = services(&data, &args(&["--service", "sshd"]));
+        assert_eq!(result.state, State::Unknown);
+    }
+
+    #[test]
+    fn absent_or_disabled_services_are_not_counted_unless_named() {
+        let service = |name: &str, is_running: bool, expected_running: bool| ServiceInfo {
+            name: name.into(),
+            display_name: name.into(),
+            is_running,
+            expected_running,
+        };
+        let data = DriverData {
+            services: vec![
+                service("NetworkManager", true, true),
+                service("pulseaudio", false, false),
+                service("wpa_supplicant", false, false),
+            ],
+            ..DriverData::default()
+        };
+        let result = services(&data, &args(&[]));
+        assert_eq!(result.state, State::Ok);
+        assert_eq!(result.summary, "1 services running");
+        assert_eq!(
+            result.perfdata[0].to_string(),
+            "'services_stopped'=0;1;;0;1"
+        );
+
+        let result = services(&data, &args(&["--service", "pulseaudio"]));
+        assert_eq!(result.state, State::Warning);
+        assert_eq!(result.summary, "stopped services: pulseaudio");
+    }
+}
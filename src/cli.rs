@@ -9,9 +9,31 @@ pub enum Command {
     /// Remove SD-300 through its proven installation owner.
     Uninstall(ActionArgs),
     /// Collect one redacted noninteractive diagnostic snapshot.
-    Snapshot(ReportArgs),
+    Snapshot(SnapshotArgs),
     /// Show which diagnostic capabilities are available on this machine.
     Capabilities(ReportArgs),
+    /// Record redacted diagnostics to NDJSON until a duration or Ctrl+C.
+    Record(RecordArgs),
+    /// Stream changed report sections to stdout as NDJSON until Ctrl+C.
+    Watch(WatchArgs),
+    /// Query the long-term metrics history kept while the TUI runs.
+    History(HistoryArgs),
+    /// List semantic changes between two `snapshot --json` reports.
+    Diff(DiffArgs),
+    /// Save or choose the baseline the TUI checks for configuration drift.
+    Baseline(BaselineArgs),
+    /// Serve live metrics for Prometheus at /metrics until Ctrl+C.
+    Serve(ServeArgs),
+    /// Push live metrics to InfluxDB or StatsD until Ctrl+C.
+    Push(PushArgs),
+    /// Serve live topic envelopes to local clients over a private socket.
+    Daemon(DaemonArgs),
+    /// Run monitoring-plugin probes and exit 0-3 for Nagios or Icinga.
+    Check(CheckArgs),
+    /// Write one redacted diagnostic table as CSV, TSV, or JSON.
+    Export(ExportArgs),
+    /// Print the JSON Schema that `snapshot --json` reports follow.
+    Schema,
     /// Open or focus the installed SD-300 desktop monitor.
     Gui,
     /// Installer-only cleanup used to make a fresh native install authoritative.
@@ -68,6 +90,642 @@ pub struct ReportArgs {
     pub include_sensitive: bool,
 }
 
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotArgs {
+    #[command(flatten)]
+    pub report: ReportArgs,
+
+    /// Write the report in another format instead of JSON; html is a single
+    /// offline page with User and Technician views.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        conflicts_with = "json",
+        hide_short_help = true
+    )]
+    pub format: Option<SnapshotFormat>,
+
+    /// Cut a markdown or text summary to at most this many characters,
+    /// dropping the least important lines first.
+    #[arg(
+        long,
+        value_name = "CHARS",
+        requires = "format",
+        hide_short_help = true,
+        value_parser = clap::value_parser!(u64).range(200..)
+    )]
+    pub max_chars: Option<u64>,
+}
+
+/// Alternative `snapshot` output formats.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotFormat {
+    /// Self-contained HTML with inline CSS and SVG charts
+    Html,
+    /// Ticket-sized Markdown summary with suggested next steps
+    Markdown,
+    /// Ticket-sized plain-text summary with suggested next steps
+    Text,
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct ExportArgs {
+    /// Table to export.
+    #[arg(long, value_enum, value_name = "TABLE")]
+    pub table: ExportTable,
+
+    /// Output format; every format uses the same column names.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+    pub format: ExportFormat,
+
+    /// Write to this file instead of standard output.
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<std::path::PathBuf>,
+
+    /// Include the connection addresses that are redacted by default.
+    #[arg(long, hide_short_help = true)]
+    pub include_sensitive: bool,
+}
+
+/// Tables `export` can write.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportTable {
+    Processes,
+    Connections,
+    Listening,
+    Drivers,
+    Services,
+    Sensors,
+    Partitions,
+}
+
+impl ExportTable {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Processes => "processes",
+            Self::Connections => "connections",
+            Self::Listening => "listening",
+            Self::Drivers => "drivers",
+            Self::Services => "services",
+            Self::Sensors => "sensors",
+            Self::Partitions => "partitions",
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Tsv,
+    Json,
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct RecordArgs {
+    /// Recording file; rotated files are numbered beside it (name.1.ndjson).
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        default_value = "sd300-recording.ndjson"
+    )]
+    pub output: std::path::PathBuf,
+
+    /// Seconds between recorded frames. Collectors keep the TUI's cadence.
+    #[arg(
+        short,
+        long,
+        value_name = "SECONDS",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u64).range(1..=3600)
+    )]
+    pub interval: u64,
+
+    /// Stop after this long (for example 90s, 15m, or 2h) instead of Ctrl+C.
+    #[arg(short, long, value_name = "DURATION", value_parser = parse_duration)]
+    pub duration: Option<std::time::Duration>,
+
+    /// Comma-separated report sections to record (default: all).
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_delimiter = ',',
+        value_name = "SECTIONS"
+    )]
+    pub sections: Vec<ReportSection>,
+
+    /// Start a new file once the current one reaches this many MiB.
+    #[arg(
+        long = "max-file-mib",
+        value_name = "MIB",
+        default_value_t = 64,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_file_mib: u64,
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct WatchArgs {
+    /// Emit one JSON object per line (currently the only output format).
+    #[arg(long, required = true)]
+    pub json: bool,
+
+    /// Comma-separated report sections to watch (default: all).
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_delimiter = ',',
+        value_name = "SECTIONS"
+    )]
+    pub sections: Vec<ReportSection>,
+
+    /// Seconds between lines. Collectors keep the TUI's cadence.
+    #[arg(
+        short,
+        long,
+        value_name = "SECONDS",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u64).range(1..=3600)
+    )]
+    pub interval: u64,
+
+    /// Include host, network-address, MAC-address, and drive-serial values.
+    #[arg(long, hide_short_help = true)]
+    pub include_sensitive: bool,
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct ServeArgs {
+    /// Address to listen on. The endpoint has no authentication, so keep it
+    /// on loopback unless a firewall limits who can reach it.
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:9731")]
+    pub listen: std::net::SocketAddr,
+
+    /// Instead of listening, keep a node_exporter textfile-collector file
+    /// (ending in .prom) up to date with the metrics node_exporter lacks.
+    #[arg(long, value_name = "PATH", conflicts_with = "listen")]
+    pub textfile: Option<std::path::PathBuf>,
+
+    /// Seconds between textfile writes.
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 60,
+        requires = "textfile",
+        value_parser = clap::value_parser!(u64).range(5..=3600)
+    )]
+    pub interval: u64,
+
+    #[command(flatten)]
+    pub warning_log: WarningLogArgs,
+}
+
+#[derive(Args, Debug, Clone, Default, PartialEq, Eq)]
+pub struct WarningLogArgs {
+    /// Also send warnings to the system log as they are raised and cleared.
+    #[arg(long, value_enum, value_name = "LOG", hide_short_help = true)]
+    pub log_warnings: Option<WarningLog>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningLog {
+    /// The local syslog socket
+    Syslog,
+    /// The systemd journal, with source and severity as journal fields
+    Journald,
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct DaemonArgs {
+    /// Unix socket path, or named pipe name on Windows (default: a
+    /// per-user socket in the runtime directory).
+    #[arg(long, value_name = "PATH")]
+    pub socket: Option<std::path::PathBuf>,
+
+    /// Include host, network-address, MAC-address, and drive-serial values.
+    #[arg(long, hide_short_help = true)]
+    pub include_sensitive: bool,
+
+    #[command(flatten)]
+    pub warning_log: WarningLogArgs,
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct PushArgs {
+    /// Wire protocol the endpoint speaks.
+    #[arg(long, value_enum, value_name = "PROTOCOL")]
+    pub protocol: PushProtocol,
+
+    /// `http://host:port/path?query` for influx-http (for example
+    /// `/api/v2/write?org=lab&bucket=sd300`); `host:port` for UDP.
+    #[arg(long, value_name = "ENDPOINT")]
+    pub endpoint: String,
+
+    /// Tag added to every metric; repeat for more tags.
+    #[arg(long = "tag", value_name = "KEY=VALUE", value_parser = parse_tag)]
+    pub tags: Vec<(String, String)>,
+
+    /// Most lines sent in one HTTP request or UDP datagram.
+    #[arg(
+        long,
+        value_name = "LINES",
+        default_value_t = 500,
+        value_parser = clap::value_parser!(u64).range(1..=100_000)
+    )]
+    pub batch_size: u64,
+
+    /// Lines kept while the endpoint is down; the oldest go first.
+    #[arg(
+        long,
+        value_name = "LINES",
+        default_value_t = 100_000,
+        hide_short_help = true,
+        value_parser = clap::value_parser!(u64).range(1..=10_000_000)
+    )]
+    pub buffer_lines: u64,
+
+    #[command(flatten)]
+    pub warning_log: WarningLogArgs,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushProtocol {
+    /// InfluxDB line protocol over HTTP
+    InfluxHttp,
+    /// InfluxDB line protocol over UDP
+    InfluxUdp,
+    /// StatsD gauges over UDP, with DogStatsD-style tags
+    Statsd,
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct CheckArgs {
+    /// Comma-separated probes to run (default: all).
+    #[arg(short, long, value_enum, value_delimiter = ',', value_name = "PROBES")]
+    pub probes: Vec<CheckProbe>,
+
+    /// Partition usage that is WARNING (default: the configured disk
+    /// threshold).
+    #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u64).range(0..=100))]
+    pub disk_warning: Option<u64>,
+
+    /// Partition usage that is CRITICAL.
+    #[arg(long, value_name = "PERCENT", value_parser = clap::value_parser!(u64).range(0..=100))]
+    pub disk_critical: Option<u64>,
+
+    /// Processor or graphics temperature that is WARNING (default: the
+    /// configured temperature thresholds).
+    #[arg(long, value_name = "CELSIUS", value_parser = clap::value_parser!(u64).range(0..=150))]
+    pub temperature_warning: Option<u64>,
+
+    /// Processor or graphics temperature that is CRITICAL.
+    #[arg(long, value_name = "CELSIUS", value_parser = clap::value_parser!(u64).range(0..=150))]
+    pub temperature_critical: Option<u64>,
+
+    /// Internet round trip that is WARNING.
+    #[arg(long, value_name = "MS")]
+    pub latency_warning: Option<u64>,
+
+    /// Internet round trip that is CRITICAL.
+    #[arg(long, value_name = "MS")]
+    pub latency_critical: Option<u64>,
+
+    /// Devices needing driver attention that make the check WARNING.
+    #[arg(long, value_name = "COUNT", default_value_t = 1)]
+    pub drivers_warning: u64,
+
+    /// Devices needing driver attention that make the check CRITICAL.
+    #[arg(long, value_name = "COUNT")]
+    pub drivers_critical: Option<u64>,
+
+    /// Service that must be running; repeat for more (default: every
+    /// monitored service that is enabled or has failed).
+    #[arg(long = "service", value_name = "NAME")]
+    pub services: Vec<String>,
+
+    /// Stopped services that make the check WARNING.
+    #[arg(long, value_name = "COUNT", default_value_t = 1)]
+    pub services_warning: u64,
+
+    /// Stopped services that make the check CRITICAL.
+    #[arg(long, value_name = "COUNT")]
+    pub services_critical: Option<u64>,
+
+    /// Seconds to wait for the probes before reporting UNKNOWN.
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 30,
+        value_parser = clap::value_parser!(u64).range(1..=300)
+    )]
+    pub timeout: u64,
+}
+
+/// Probes `check` can run.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckProbe {
+    /// SMART or storage-reliability drive health
+    DiskHealth,
+    /// Used space on every partition
+    Partitions,
+    /// Processor and graphics temperatures
+    Thermals,
+    /// Gateway, DNS, and internet reachability
+    Connectivity,
+    /// Devices whose drivers need attention
+    Drivers,
+    /// Monitored services that are not running
+    Services,
+}
+
+impl CheckProbe {
+    pub const ALL: [Self; 6] = [
+        Self::DiskHealth,
+        Self::Partitions,
+        Self::Thermals,
+        Self::Connectivity,
+        Self::Drivers,
+        Self::Services,
+    ];
+}
+
+/// Parse a `key=value` tag; neither side may be empty.
+pub fn parse_tag(text: &str) -> Result<(String, String), String> {
+    match text.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() && !value.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("tags look like key=value, got '{text}'")),
+    }
+}
+
+/// Top-level sections of the diagnostic report, by their JSON key.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ReportSection {
+    System,
+    Cpu,
+    Memory,
+    Disk,
+    DiskHealth,
+    Displays,
+    Gpu,
+    Network,
+    NetworkDiagnostics,
+    Processes,
+    Thermals,
+    Drivers,
+    Capabilities,
+    Warnings,
+    Events,
+}
+
+impl ReportSection {
+    pub fn report_key(&self) -> &'static str {
+        match self {
+            Self::System => "system",
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::Disk => "disk",
+            Self::DiskHealth => "disk_health",
+            Self::Displays => "displays",
+            Self::Gpu => "gpu",
+            Self::Network => "network",
+            Self::NetworkDiagnostics => "network_diagnostics",
+            Self::Processes => "processes",
+            Self::Thermals => "thermals",
+            Self::Drivers => "drivers",
+            Self::Capabilities => "capabilities",
+            Self::Warnings => "warnings",
+            Self::Events => "events",
+        }
+    }
+}
+
+/// Parse `90`, `90s`, `15m`, `2h`, or `1d` into a nonzero duration.
+pub fn parse_duration(text: &str) -> Result<std::time::Duration, String> {
+    let text = text.trim();
+    let (digits, scale) = match text.char_indices().last() {
+        Some((index, 's')) => (&text[..index], 1),
+        Some((index, 'm')) => (&text[..index], 60),
+        Some((index, 'h')) => (&text[..index], 60 * 60),
+        Some((index, 'd')) => (&text[..index], 24 * 60 * 60),
+        _ => (text, 1),
+    };
+    let amount = digits
+        .parse::<u64>()
+        .map_err(|_| format!("expected a duration such as 90s, 15m, or 2h, got '{text}'"))?;
+    match amount.checked_mul(scale) {
+        Some(0) => Err("duration must be greater than zero".into()),
+        Some(seconds) => Ok(std::time::Duration::from_secs(seconds)),
+        None => Err(format!("duration '{text}' is too large")),
+    }
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct DiffArgs {
+    /// Earlier report.
+    #[arg(value_name = "BEFORE")]
+    pub before: std::path::PathBuf,
+
+    /// Later report.
+    #[arg(value_name = "AFTER")]
+    pub after: std::path::PathBuf,
+
+    /// Emit machine-readable JSON instead of text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct BaselineArgs {
+    #[command(subcommand)]
+    pub action: BaselineAction,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum BaselineAction {
+    /// Save the current redacted report as NAME and check drift against it.
+    Save {
+        #[arg(value_parser = parse_baseline_name)]
+        name: String,
+    },
+    /// Check drift against an already saved baseline.
+    Use {
+        #[arg(value_parser = parse_baseline_name)]
+        name: String,
+    },
+    /// Stop checking for drift; saved baselines are kept.
+    Off,
+    /// List saved baselines and mark the active one.
+    List,
+    /// Delete a saved baseline.
+    Remove {
+        #[arg(value_parser = parse_baseline_name)]
+        name: String,
+    },
+}
+
+/// Baseline names become file names: 1-64 letters, digits, `.`, `_`, or
+/// `-`, not starting with `.`.
+pub fn parse_baseline_name(text: &str) -> Result<String, String> {
+    let valid = (1..=64).contains(&text.len())
+        && !text.starts_with('.')
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    if valid {
+        Ok(text.to_string())
+    } else {
+        Err(format!(
+            "baseline names use 1-64 letters, digits, '.', '_', or '-' and cannot start with '.', got '{text}'"
+        ))
+    }
+}
+
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct HistoryArgs {
+    /// Metric to query.
+    #[arg(value_enum)]
+    pub metric: HistoryMetric,
+
+    /// Start of the range: a duration ago (6h, 7d) or a UTC time (2026-10-17T14:00).
+    #[arg(long, value_name = "TIME", default_value = "24h", value_parser = parse_time)]
+    pub from: TimeSpec,
+
+    /// End of the range, in the same forms as --from (default: now).
+    #[arg(long, value_name = "TIME", value_parser = parse_time)]
+    pub to: Option<TimeSpec>,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = HistoryFormat::Table)]
+    pub format: HistoryFormat,
+
+    /// Rows (table, JSON) or columns (sparkline) the range is divided into.
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u16).range(1..=1000)
+    )]
+    pub points: Option<u16>,
+}
+
+/// Metrics kept by the long-term history store.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryMetric {
+    Cpu,
+    Memory,
+    Swap,
+    Gpu,
+    CpuTemp,
+    GpuTemp,
+    NetDown,
+    NetUp,
+    DiskRead,
+    DiskWrite,
+}
+
+impl HistoryMetric {
+    pub fn key(&self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::Swap => "swap",
+            Self::Gpu => "gpu",
+            Self::CpuTemp => "cpu_temp",
+            Self::GpuTemp => "gpu_temp",
+            Self::NetDown => "net_down",
+            Self::NetUp => "net_up",
+            Self::DiskRead => "disk_read",
+            Self::DiskWrite => "disk_write",
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HistoryFormat {
+    #[default]
+    Table,
+    Json,
+    Sparkline,
+}
+
+/// A point in time given relative to now or as an absolute UTC time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeSpec {
+    Ago(std::time::Duration),
+    UnixSeconds(u64),
+}
+
+impl TimeSpec {
+    pub fn resolve(&self, now_unix_secs: u64) -> u64 {
+        match self {
+            Self::Ago(duration) => now_unix_secs.saturating_sub(duration.as_secs()),
+            Self::UnixSeconds(secs) => *secs,
+        }
+    }
+}
+
+/// Parse a duration ago (see [`parse_duration`]) or a UTC time written as
+/// `YYYY-MM-DD`, `YYYY-MM-DDTHH:MM`, or `YYYY-MM-DDTHH:MM:SS` (a space may
+/// replace the `T`).
+pub fn parse_time(text: &str) -> Result<TimeSpec, String> {
+    let text = text.trim();
+    if !text.contains('-') {
+        return parse_duration(text).map(TimeSpec::Ago);
+    }
+    let invalid = || {
+        format!(
+            "expected a duration such as 24h or a UTC time such as 2026-10-17T14:00, got '{text}'"
+        )
+    };
+    let (date, time) = match text.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (text, None),
+    };
+    let fields = |part: &str, separator: char| -> Option<Vec<u64>> {
+        part.split(separator)
+            .map(|field| field.parse::<u64>().ok())
+            .collect()
+    };
+    let date = fields(date, '-').ok_or_else(invalid)?;
+    let [year, month, day] = date[..] else {
+        return Err(invalid());
+    };
+    let (hour, minute, second) = match time.map(|time| fields(time, ':')) {
+        None => (0, 0, 0),
+        Some(Some(time)) => match time[..] {
+            [hour, minute] => (hour, minute, 0),
+            [hour, minute, second] => (hour, minute, second),
+            _ => return Err(invalid()),
+        },
+        Some(None) => return Err(invalid()),
+    };
+    let days_in_month = match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => 0,
+    };
+    if year < 1970 || day == 0 || day > days_in_month || hour > 23 || minute > 59 || second > 59 {
+        return Err(invalid());
+    }
+    // Howard Hinnant's `days_from_civil`, restricted to dates after the epoch.
+    let year_of_march = if month <= 2 { year - 1 } else { year };
+    let era = year_of_march / 400;
+    let year_of_era = year_of_march % 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    Ok(TimeSpec::UnixSeconds(
+        days * 86_400 + hour * 3600 + minute * 60 + second,
+    ))
+}
+
 #[derive(Args, Debug, Clone, Default, PartialEq, Eq)]
 pub struct MigrateArgs {
     /// Remove an allowlisted SD-300 copy from the invoking user's Cargo home.
@@ -213,18 +871,41 @@ EXAMPLES:
   sd300 gui      Open or focus the installed desktop monitor
   sd300 snapshot --json       Redacted diagnostic snapshot
   sd300 capabilities --json   Capability and availability states
+  sd300 snapshot --format html > report.html  Offline report for a ticket
+  sd300 snapshot --format markdown --max-chars 4000  Paste into a vendor ticket
+  sd300 record --duration 15m Record redacted metrics to NDJSON
+  sd300 watch --json -s cpu,memory | jq .  Stream changed sections as NDJSON
+  sd300 history cpu --from 7d Query the long-term metrics history
+  sd300 diff OLD NEW  Compare two snapshot --json reports
+  sd300 baseline save lab Check future runs for drift from now
+  sd300 serve --listen 127.0.0.1:9731  Prometheus metrics at /metrics
+  sd300 push --protocol statsd --endpoint 127.0.0.1:8125  Push metrics to StatsD
+  sd300 daemon   Serve live topics on a private local socket
+  sd300 check -p partitions,thermals  Nagios/Icinga plugin status line
+  sd300 export --table processes -o procs.csv  One table for a spreadsheet
+  sd300 schema > sd300-report.schema.json  JSON Schema for snapshot --json
+  sd300 --replay FILE Browse a recording in the normal sections
+  sd300 --tech --summary-on-exit s.json  Save session statistics on exit
   sd300 --update Same as 'sd300 update' (legacy flag form)
 
 KEYBINDINGS:
   1-9          Switch to section
+  0            Event timeline
   q / Esc      Quit
   Ctrl+C       Quit to shell
   m            Return to mode selection
   ?            Help overlay
+  w            Why overall health is not green (User Mode)
   f            Toggle temperature unit (C/F)
+  t            Cycle sparkline window (60s / 10m / 1h / 6h / 24h)
   j / k        Scroll (Processes, Connections, Drivers, Disk)
   c / M / p / n  Sort processes by CPU / Memory / PID / Name
+  d / g        Sort processes by disk I/O / GPU memory
   r            Refresh drivers (Drivers section)
+  e            Save the current section's table as CSV to the reports folder
+  Space        Replay: play / pause
+  , . / Left Right  Replay: step one frame / seek 10s
+  - + / Home End    Replay: speed / first or last frame
 
 SECTIONS:
   1 Overview    System health dashboard / identity and gauges
@@ -235,7 +916,8 @@ SECTIONS:
   6 Network     Connectivity, interfaces, active connections
   7 Processes   Running apps / sortable process table
   8 Thermals    Temperature, fans, battery, power
-  9 Drivers     Device health, driver versions, services")]
+  9 Drivers     Device health, driver versions, services
+  0 Events      Warnings, device and connectivity changes over time")]
 pub struct Cli {
     /// Launch directly into User Mode (plain language diagnostics)
     #[arg(long, conflicts_with_all = ["tech", "update"])]
@@ -249,6 +931,24 @@ pub struct Cli {
     #[arg(long, conflicts_with_all = ["user", "tech"])]
     pub update: bool,
 
+    /// Replay an `sd300 record` recording in the TUI instead of live data
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with = "update",
+        hide_short_help = true
+    )]
+    pub replay: Option<std::path::PathBuf>,
+
+    /// Write whole-session statistics as JSON to PATH on exit instead of asking
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["update", "replay"],
+        hide_short_help = true
+    )]
+    pub summary_on_exit: Option<std::path::PathBuf>,
+
     /// Action subcommand. If present, takes precedence over legacy action flags.
     #[command(subcommand)]
     pub command: Option<Command>,
@@ -315,9 +1015,13 @@ mod tests {
             .expect("snapshot action should parse");
         assert_eq!(
             snapshot.command,
-            Some(Command::Snapshot(ReportArgs {
-                json: true,
-                include_sensitive: false,
+            Some(Command::Snapshot(SnapshotArgs {
+                report: ReportArgs {
+                    json: true,
+                    include_sensitive: false,
+                },
+                format: None,
+                max_chars: None,
             }))
         );
 
@@ -329,6 +1033,344 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn html_snapshots_replace_json_and_may_include_sensitive_values() {
+        let html = Cli::try_parse_from(["sd300", "snapshot", "--format", "html"])
+            .expect("HTML snapshot should parse");
+        assert!(matches!(
+            html.command,
+            Some(Command::Snapshot(SnapshotArgs {
+                format: Some(SnapshotFormat::Html),
+                ..
+            }))
+        ));
+        let both = Cli::try_parse_from(["sd300", "snapshot", "--json", "--format", "html"])
+            .expect_err("one output format at a time");
+        assert_eq!(both.kind(), clap::error::ErrorKind::ArgumentConflict);
+        let sensitive = Cli::try_parse_from([
+            "sd300",
+            "snapshot",
+            "--format",
+            "html",
+            "--include-sensitive",
+        ])
+        .expect("an explicit format satisfies the structured-output requirement");
+        assert!(matches!(
+            sensitive.command,
+            Some(Command::Snapshot(SnapshotArgs {
+                report: ReportArgs {
+                    include_sensitive: true,
+                    ..
+                },
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn ticket_summaries_take_an_optional_length_limit() {
+        let cli = Cli::try_parse_from([
+            "sd300",
+            "snapshot",
+            "--format",
+            "markdown",
+            "--max-chars",
+            "4000",
+        ])
+        .expect("limited Markdown summary should parse");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Snapshot(SnapshotArgs {
+                format: Some(SnapshotFormat::Markdown),
+                max_chars: Some(4000),
+                ..
+            }))
+        ));
+        for args in [
+            &["sd300", "snapshot", "--max-chars", "4000"][..],
+            &["sd300", "snapshot", "--format", "text", "--max-chars", "50"][..],
+        ] {
+            assert!(Cli::try_parse_from(args).is_err(), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn export_requires_a_table_and_defaults_to_csv() {
+        let cli = Cli::try_parse_from(["sd300", "export", "--table", "listening"])
+            .expect("export with a table should parse");
+        assert_eq!(
+            cli.command,
+            Some(Command::Export(ExportArgs {
+                table: ExportTable::Listening,
+                format: ExportFormat::Csv,
+                output: None,
+                include_sensitive: false,
+            }))
+        );
+        let json = Cli::try_parse_from([
+            "sd300", "export", "--table", "sensors", "--format", "json", "-o", "t.json",
+        ])
+        .expect("export with a format and output should parse");
+        assert!(matches!(
+            json.command,
+            Some(Command::Export(ExportArgs {
+                table: ExportTable::Sensors,
+                format: ExportFormat::Json,
+                output: Some(_),
+                ..
+            }))
+        ));
+        assert!(Cli::try_parse_from(["sd300", "export"]).is_err());
+        assert!(Cli::try_parse_from(["sd300", "export", "--table", "events"]).is_err());
+    }
+
+    #[test]
+    fn watch_requires_json_and_takes_record_style_sections() {
+        let cli = Cli::try_parse_from([
+            "sd300",
+            "watch",
+            "--json",
+            "--sections",
+            "cpu,processes",
+            "--interval",
+            "5",
+        ])
+        .expect("watch should parse");
+        assert_eq!(
+            cli.command,
+            Some(Command::Watch(WatchArgs {
+                json: true,
+                sections: vec![ReportSection::Cpu, ReportSection::Processes],
+                interval: 5,
+                include_sensitive: false,
+            }))
+        );
+        let text = Cli::try_parse_from(["sd300", "watch"]).expect_err("only NDJSON is offered");
+        assert_eq!(text.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn parses_record_action_with_sections_and_duration() {
+        let cli = Cli::try_parse_from([
+            "sd300",
+            "record",
+            "--interval",
+            "5",
+            "--duration",
+            "15m",
+            "--sections",
+            "cpu,disk-health,processes",
+            "--output",
+            "capture.ndjson",
+        ])
+        .expect("record action should parse");
+        let Some(Command::Record(args)) = cli.command else {
+            panic!("expected the record action");
+        };
+        assert_eq!(args.interval, 5);
+        assert_eq!(args.duration, Some(std::time::Duration::from_secs(900)));
+        assert_eq!(
+            args.sections,
+            [
+                ReportSection::Cpu,
+                ReportSection::DiskHealth,
+                ReportSection::Processes
+            ]
+        );
+        assert_eq!(args.output, std::path::PathBuf::from("capture.ndjson"));
+        assert_eq!(args.max_file_mib, 64);
+
+        let defaults = Cli::try_parse_from(["sd300", "record"]).expect("defaults should parse");
+        assert!(matches!(
+            defaults.command,
+            Some(Command::Record(RecordArgs {
+                interval: 1,
+                duration: None,
+                ..
+            }))
+        ));
+
+        for invalid in [
+            ["sd300", "record", "--interval", "0"],
+            ["sd300", "record", "--duration", "0s"],
+            ["sd300", "record", "--duration", "soon"],
+            ["sd300", "record", "--sections", "everything"],
+        ] {
+            assert!(Cli::try_parse_from(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn parses_history_query_ranges() {
+        let cli = Cli::try_parse_from([
+            "sd300",
+            "history",
+            "cpu-temp",
+            "--from",
+            "2026-10-17T14:00",
+            "--to",
+            "2026-10-17 18:30:15",
+            "--format",
+            "sparkline",
+        ])
+        .expect("history query should parse");
+        let Some(Command::History(args)) = cli.command else {
+            panic!("expected the history action");
+        };
+        assert_eq!(args.metric, HistoryMetric::CpuTemp);
+        assert_eq!(args.from, TimeSpec::UnixSeconds(1_792_245_600));
+        assert_eq!(args.to, Some(TimeSpec::UnixSeconds(1_792_261_815)));
+        assert_eq!(args.format, HistoryFormat::Sparkline);
+
+        let defaults =
+            Cli::try_parse_from(["sd300", "history", "memory"]).expect("defaults should parse");
+        let Some(Command::History(args)) = defaults.command else {
+            panic!("expected the history action");
+        };
+        assert_eq!(
+            args.from,
+            TimeSpec::Ago(std::time::Duration::from_secs(86_400))
+        );
+        assert_eq!(args.from.resolve(100_000), 13_600);
+        assert_eq!(args.to, None);
+        assert_eq!(args.format, HistoryFormat::Table);
+
+        assert_eq!(parse_time("1970-01-01"), Ok(TimeSpec::UnixSeconds(0)));
+        assert_eq!(
+            parse_time("2000-02-29T00:00"),
+            Ok(TimeSpec::UnixSeconds(951_782_400))
+        );
+        for invalid in [
+            "2026-02-29",
+            "2026-13-01",
+            "2026-10-17T24:00",
+            "2026-10",
+            "later",
+        ] {
+            assert!(parse_time(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn session_summaries_are_for_live_sessions_only() {
+        let cli = Cli::try_parse_from(["sd300", "--tech", "--summary-on-exit", "s.json"]).unwrap();
+        assert_eq!(
+            cli.summary_on_exit.as_deref(),
+            Some(std::path::Path::new("s.json"))
+        );
+        assert!(Cli::try_parse_from([
+            "sd300",
+            "--replay",
+            "r.ndjson",
+            "--summary-on-exit",
+            "s.json"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn serve_writes_a_textfile_or_listens_but_not_both() {
+        let cli = Cli::try_parse_from(["sd300", "serve", "--textfile", "sd300.prom"]).unwrap();
+        let Some(Command::Serve(args)) = cli.command else {
+            panic!("expected serve");
+        };
+        assert_eq!(args.interval, 60);
+        assert_eq!(args.listen.to_string(), "127.0.0.1:9731");
+        for rejected in [
+            &[
+                "sd300",
+                "serve",
+                "--textfile",
+                "a.prom",
+                "--listen",
+                "0.0.0.0:9731",
+            ][..],
+            &["sd300", "serve", "--interval", "30"],
+            &["sd300", "serve", "--textfile", "a.prom", "--interval", "1"],
+        ] {
+            assert!(Cli::try_parse_from(rejected).is_err(), "{rejected:?}");
+        }
+    }
+
+    #[test]
+    fn push_takes_repeatable_tags_and_bounded_batches() {
+        let cli = Cli::try_parse_from([
+            "sd300",
+            "push",
+            "--protocol",
+            "influx-http",
+            "--endpoint",
+            "http://127.0.0.1:8086/write?db=sd300",
+            "--tag",
+            "site=lab",
+            "--tag",
+            "rack = 7",
+        ])
+        .unwrap();
+        let Some(Command::Push(args)) = cli.command else {
+            panic!("expected push");
+        };
+        assert_eq!(args.protocol, PushProtocol::InfluxHttp);
+        assert_eq!(
+            args.tags,
+            vec![("site".into(), "lab".into()), ("rack".into(), "7".into())]
+        );
+        assert_eq!(args.batch_size, 500);
+        for rejected in [
+            &["sd300", "push", "--protocol", "statsd"][..],
+            &[
+                "sd300",
+                "push",
+                "--protocol",
+                "statsd",
+                "--endpoint",
+                "h:1",
+                "--tag",
+                "site",
+            ],
+            &[
+                "sd300",
+                "push",
+                "--protocol",
+                "statsd",
+                "--endpoint",
+                "h:1",
+                "--batch-size",
+                "0",
+            ],
+        ] {
+            assert!(Cli::try_parse_from(rejected).is_err(), "{rejected:?}");
+        }
+    }
+
+    #[test]
+    fn parses_baseline_actions_with_file_safe_names() {
+        let save = Cli::try_parse_from(["sd300", "baseline", "save", "lab-01.pre_patch"])
+            .expect("baseline save should parse");
+        assert_eq!(
+            save.command,
+            Some(Command::Baseline(BaselineArgs {
+                action: BaselineAction::Save {
+                    name: "lab-01.pre_patch".into()
+                }
+            }))
+        );
+        let off = Cli::try_parse_from(["sd300", "baseline", "off"]).expect("off should parse");
+        assert!(matches!(
+            off.command,
+            Some(Command::Baseline(BaselineArgs {
+                action: BaselineAction::Off
+            }))
+        ));
+
+        for invalid in ["", ".hidden", "../escape", "a/b", "name with space"] {
+            assert!(
+                Cli::try_parse_from(["sd300", "baseline", "use", invalid]).is_err(),
+                "{invalid:?}"
+            );
+        }
+    }
+
     #[test]
     fn parses_lifecycle_json_actions() {
         let update = Cli::try_parse_from(["sd300", "update", "--json"])
@@ -1,7 +1,8 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use sysinfo::System;
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct CpuData {
     pub total_usage: f32,
     pub per_core_usage: Vec<f32>,
@@ -1,15 +1,16 @@
 use super::DiagnosticWarning;
 use crate::observation::Observation;
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct DiskHealthData {
     pub drives: Vec<DriveHealth>,
     pub health_status: Observation,
     pub reliability_status: Observation,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DriveHealth {
     pub device_id: String,
     pub model: String,
@@ -26,7 +27,7 @@ pub struct DriveHealth {
     pub health_source: String,
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct DiskIoStats {
     pub read_bytes_per_sec: u64,
     pub write_bytes_per_sec: u64,
@@ -35,7 +36,7 @@ pub struct DiskIoStats {
     pub avg_write_latency_ms: f64,
 }
 
-#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum MediaType {
     Ssd,
@@ -57,7 +58,7 @@ impl std::fmt::Display for MediaType {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Default, Serialize)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DiskHealthStatus {
     Healthy,
@@ -99,9 +100,6 @@ pub fn collect() -> (DiskHealthData, Vec<DiagnosticWarning>) {
 
 // --- Windows implementation ---
 
-#[cfg(windows)]
-use serde::Deserialize;
-
 #[cfg(windows)]
 #[derive(Deserialize, Debug)]
 #[serde(rename = "Win32_DiskDrive")]
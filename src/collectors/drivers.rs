@@ -1,9 +1,10 @@
 pub mod platform;
 
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 /// Driver/device health data
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct DriverData {
     pub network: Vec<DeviceInfo>,
     pub bluetooth: Vec<DeviceInfo>,
@@ -18,7 +19,7 @@ pub struct DriverData {
     pub scan_status: DriverScanStatus,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DeviceInfo {
     pub name: String,
     pub driver_version: String,
@@ -28,7 +29,7 @@ pub struct DeviceInfo {
     pub extra: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DeviceStatus {
     Ok,
@@ -103,7 +104,7 @@ impl DriverData {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DeviceCategory {
     Network,
@@ -133,7 +134,7 @@ impl DeviceCategory {
     }
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DriverScanStatus {
     #[default]
@@ -143,11 +144,15 @@ pub enum DriverScanStatus {
     ScanFailed(String),
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ServiceInfo {
     pub name: String,
     pub display_name: String,
     pub is_running: bool,
+    /// Installed and set to start on its own, or failed after starting; a
+    /// stopped service without this is not a fault
+    #[serde(default)]
+    pub expected_running: bool,
 }
 
 pub fn collect() -> DriverData {
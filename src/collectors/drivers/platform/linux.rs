@@ -1,4 +1,4 @@
-use crate::collectors::command::{run_status, CommandTimeout};
+use crate::collectors::command::{run_stdout, CommandTimeout};
 use crate::collectors::drivers::{
     DeviceCategory, DeviceInfo, DeviceStatus, DriverData, ServiceInfo,
 };
@@ -178,17 +178,74 @@ fn collect_services(data: &mut DriverData) {
     ];
 
     for (name, display) in &services {
-        let is_running = run_status(
+        let (is_running, expected_running) = run_stdout(
             "systemctl",
-            ["is-active", "--quiet", name],
+            [
+                "show",
+                "--property=LoadState,UnitFileState,ActiveState",
+                name,
+            ],
             CommandTimeout::Quick,
         )
-        .unwrap_or(false);
+        .map(|text| service_state(&text))
+        .unwrap_or_default();
 
         data.services.push(ServiceInfo {
             name: name.to_string(),
             display_name: display.to_string(),
             is_running,
+            expected_running,
         });
     }
 }
+
+/// `(is_running, expected_running)` from `systemctl show`. A unit is
+/// expected to run when it is installed and enabled, or has failed; absent
+/// and disabled units are not.
+fn service_state(show: &str) -> (bool, bool) {
+    let property = |key: &str| {
+        show.lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .unwrap_or_default()
+    };
+    let active = property("ActiveState");
+    let enabled =
+        property("LoadState") == "loaded" && property("UnitFileState").starts_with("enabled");
+    (active == "active", enabled || active == "failed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::service_state;
+
+    #[test]
+    fn only_enabled_or_failed_units_are_expected_to_run() {
+        let show = |load: &str, file: &str, active: &str| {
+            format!("LoadState={load}\nActiveState={active}\nUnitFileState={file}\n")
+        };
+        assert_eq!(
+            service_state(&show("loaded", "enabled", "active")),
+            (true, true)
+        );
+        assert_eq!(
+            service_state(&show("loaded", "enabled", "inactive")),
+            (false, true)
+        );
+        assert_eq!(
+            service_state(&show("loaded", "disabled", "failed")),
+            (false, true)
+        );
+        assert_eq!(
+            service_state(&show("loaded", "disabled", "inactive")),
+            (false, false)
+        );
+        assert_eq!(
+            service_state(&show("loaded", "static", "inactive")),
+            (false, false)
+        );
+        assert_eq!(
+            service_state(&show("not-found", "", "inactive")),
+            (false, false)
+        );
+    }
+}
@@ -200,10 +200,13 @@ fn collect_services(data: &mut DriverData) {
         let is_running =
             run_status("launchctl", ["list", name], CommandTimeout::Quick).unwrap_or(false);
 
+        // `launchctl list` only succeeds for loaded jobs; an unloaded one is
+        // not set to run.
         data.services.push(ServiceInfo {
             name: name.to_string(),
             display_name: display.to_string(),
             is_running,
+            expected_running: is_running,
         });
     }
 }
@@ -453,7 +453,13 @@ fn query_services(data: &mut DriverData) {
     for svc_name in &service_names {
         let wide_name: Vec<u16> = svc_name.encode_utf16().chain(std::iter::once(0)).collect();
 
-        let svc = unsafe { OpenServiceW(scm, PCWSTR(wide_name.as_ptr()), SERVICE_QUERY_STATUS) };
+        let svc = unsafe {
+            OpenServiceW(
+                scm,
+                PCWSTR(wide_name.as_ptr()),
+                SERVICE_QUERY_STATUS | SERVICE_QUERY_CONFIG,
+            )
+        };
         let svc = match svc {
             Ok(h) => h,
             Err(_) => continue,
@@ -468,6 +474,7 @@ fn query_services(data: &mut DriverData) {
                 name: svc_name.to_string(),
                 display_name,
                 is_running: status.dwCurrentState == SERVICE_RUNNING,
+                expected_running: starts_automatically(svc),
             });
         }
 
@@ -481,6 +488,25 @@ fn query_services(data: &mut DriverData) {
     }
 }
 
+/// Whether the service is set to start with Windows. Manual (trigger-start)
+/// and disabled services are often stopped by design.
+fn starts_automatically(svc: SC_HANDLE) -> bool {
+    // The configuration is followed by its strings; 8 KiB is the documented
+    // maximum. `u64` elements keep the structure aligned.
+    let mut buffer = vec![0u64; 1024];
+    let mut needed = 0u32;
+    let config = buffer.as_mut_ptr().cast::<QUERY_SERVICE_CONFIGW>();
+    let queried = unsafe {
+        QueryServiceConfigW(
+            svc,
+            Some(config),
+            (buffer.len() * std::mem::size_of::<u64>()) as u32,
+            &mut needed,
+        )
+    };
+    queried.is_ok() && unsafe { (*config).dwStartType } == SERVICE_AUTO_START
+}
+
 /// Get the display name of a service from SCM.
 fn get_service_display_name(scm: SC_HANDLE, wide_name: &[u16]) -> String {
     let mut buf: Vec<u16> = vec![0u16; 256];
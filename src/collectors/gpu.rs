@@ -1,10 +1,11 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::observation::Observation;
 
 use super::command::{run_output, CommandTimeout};
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct GpuData {
     pub available: bool,
     pub telemetry_available: bool,
@@ -19,7 +20,7 @@ pub struct GpuData {
     pub telemetry_status: Observation,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GpuAdapter {
     pub name: String,
     pub driver_version: Option<String>,
@@ -1,9 +1,10 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use sysinfo::System;
 
 use crate::observation::Observation;
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct MemoryData {
     pub used_bytes: u64,
     pub total_bytes: u64,
@@ -14,7 +15,7 @@ pub struct MemoryData {
     pub module_status: Observation,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MemoryModule {
     pub capacity_bytes: u64,
     pub configured_speed_mt_s: Option<u32>,
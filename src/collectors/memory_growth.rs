@@ -0,0 +1,347 @@
+//! Per-process resident-memory growth tracking.
+//!
+//! A slow leak is invisible in a single refresh, so every process's RSS is
+//! sampled across fast refreshes and a least-squares growth rate is fitted
+//! over a bounded window. Histories are keyed by PID plus process start time:
+//! when the operating system reuses a PID for a new process, the old history
+//! is discarded instead of being blended into the newcomer's trend.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use sysinfo::System;
+
+use super::{DiagnosticWarning, WarningSeverity};
+use crate::settings::MemoryGrowthSettings;
+
+/// Warning source used for leak reports so collectors can replace them
+/// wholesale on every pass, like every other warning source.
+pub const WARNING_SOURCE: &str = "Process Memory";
+
+/// Minimum spacing between retained samples. The TUI refreshes processes every
+/// second; keeping every sample would cost memory without improving a fit that
+/// spans minutes to hours.
+const SAMPLE_SPACING_SECS: f64 = 30.0;
+/// Two hours of history at the retained spacing.
+const MAX_SAMPLES: usize = 240;
+/// Fewer points than this cannot distinguish a trend from noise.
+const MIN_TREND_SAMPLES: usize = 3;
+/// Below this rate a process is drawn as stable, whatever its fit quality.
+const TREND_MIN_BYTES_PER_HOUR: f64 = 1024.0 * 1024.0;
+/// Fit quality required before a trend arrow is drawn.
+const TREND_MIN_R_SQUARED: f64 = 0.5;
+/// Fit quality required before growth is called "steady" and warned about.
+const LEAK_MIN_R_SQUARED: f64 = 0.8;
+const MIB: f64 = 1024.0 * 1024.0;
+
+/// Direction of a process's fitted memory trend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryTrend {
+    Growing,
+    Stable,
+    Shrinking,
+}
+
+impl MemoryTrend {
+    pub fn arrow(&self) -> &'static str {
+        match self {
+            Self::Growing => "\u{2191}",   // ↑
+            Self::Stable => "\u{2192}",    // →
+            Self::Shrinking => "\u{2193}", // ↓
+        }
+    }
+}
+
+/// Least-squares fit over one process's retained samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrowthEstimate {
+    pub bytes_per_hour: f64,
+    pub r_squared: f64,
+    pub observed_secs: f64,
+    pub total_growth_bytes: i64,
+}
+
+impl GrowthEstimate {
+    pub fn trend(&self) -> MemoryTrend {
+        if self.r_squared < TREND_MIN_R_SQUARED
+            || self.bytes_per_hour.abs() < TREND_MIN_BYTES_PER_HOUR
+        {
+            MemoryTrend::Stable
+        } else if self.bytes_per_hour > 0.0 {
+            MemoryTrend::Growing
+        } else {
+            MemoryTrend::Shrinking
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ProcessHistory {
+    start_time: u64,
+    name: String,
+    /// (seconds since tracker creation, resident bytes)
+    samples: Vec<(f64, u64)>,
+    estimate: Option<GrowthEstimate>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MemoryGrowthTracker {
+    origin: Instant,
+    thresholds: MemoryGrowthSettings,
+    histories: HashMap<u32, ProcessHistory>,
+}
+
+impl Default for MemoryGrowthTracker {
+    fn default() -> Self {
+        Self {
+            origin: Instant::now(),
+            thresholds: MemoryGrowthSettings::default(),
+            histories: HashMap::new(),
+        }
+    }
+}
+
+impl MemoryGrowthTracker {
+    pub fn set_thresholds(&mut self, thresholds: MemoryGrowthSettings) {
+        self.thresholds = thresholds;
+    }
+
+    /// Record the current RSS of every process sysinfo knows about. Processes
+    /// that are no longer present lose their history.
+    pub fn observe(&mut self, sys: &System) {
+        let at = self.origin.elapsed().as_secs_f64();
+        let samples = sys.processes().values().map(|process| {
+            (
+                process.pid().as_u32(),
+                process.start_time(),
+                process.name().to_string_lossy(),
+                process.memory(),
+            )
+        });
+        self.observe_samples(at, samples);
+    }
+
+    fn observe_samples<'a, I, N>(&mut self, at: f64, samples: I)
+    where
+        I: IntoIterator<Item = (u32, u64, N, u64)>,
+        N: Into<std::borrow::Cow<'a, str>>,
+    {
+        let mut seen = Vec::new();
+        for (pid, start_time, name, rss) in samples {
+            seen.push(pid);
+            let history = self.histories.entry(pid).or_insert_with(|| ProcessHistory {
+                start_time,
+                name: String::new(),
+                samples: Vec::new(),
+                estimate: None,
+            });
+            if history.start_time != start_time {
+                // Reused PID: the previous process exited between refreshes.
+                *history = ProcessHistory {
+                    start_time,
+                    name: String::new(),
+                    samples: Vec::new(),
+                    estimate: None,
+                };
+            }
+            if history.name.is_empty() {
+                history.name = name.into().into_owned();
+            }
+            let due = history
+                .samples
+                .last()
+                .is_none_or(|(last, _)| at - last >= SAMPLE_SPACING_SECS);
+            if due {
+                if history.samples.len() >= MAX_SAMPLES {
+                    history.samples.remove(0);
+                }
+                history.samples.push((at, rss));
+                history.estimate = fit(&history.samples);
+            }
+        }
+        seen.sort_unstable();
+        self.histories
+            .retain(|pid, _| seen.binary_search(pid).is_ok());
+    }
+
+    /// Fitted growth for a live process, once enough samples exist.
+    pub fn estimate(&self, pid: u32) -> Option<GrowthEstimate> {
+        self.histories
+            .get(&pid)
+            .and_then(|history| history.estimate)
+    }
+
+    pub fn trend(&self, pid: u32) -> Option<MemoryTrend> {
+        self.estimate(pid).map(|estimate| estimate.trend())
+    }
+
+    /// Processes whose growth is steady and exceeds every configured threshold.
+    pub fn warnings(&self) -> Vec<DiagnosticWarning> {
+        let mut leaking = self
+            .histories
+            .iter()
+            .filter_map(|(pid, history)| {
+                let estimate = history.estimate?;
+                self.is_leak(&estimate).then_some((*pid, history, estimate))
+            })
+            .collect::<Vec<_>>();
+        leaking.sort_by(|a, b| b.2.bytes_per_hour.total_cmp(&a.2.bytes_per_hour));
+        leaking
+            .into_iter()
+            .map(|(pid, history, estimate)| DiagnosticWarning {
+                source: WARNING_SOURCE.into(),
+                message: format!(
+                    "{} (PID {pid}) grew {:.0} MiB over {:.0} minutes (~{:.0} MiB/hour); possible memory leak",
+                    history.name,
+                    estimate.total_growth_bytes as f64 / MIB,
+                    estimate.observed_secs / 60.0,
+                    estimate.bytes_per_hour / MIB,
+                ),
+                severity: WarningSeverity::Warning,
+            })
+            .collect()
+    }
+
+    fn is_leak(&self, estimate: &GrowthEstimate) -> bool {
+        estimate.r_squared >= LEAK_MIN_R_SQUARED
+            && estimate.observed_secs >= self.thresholds.min_observed_minutes as f64 * 60.0
+            && estimate.bytes_per_hour >= self.thresholds.min_rate_mib_per_hour as f64 * MIB
+            && estimate.total_growth_bytes as f64
+                >= self.thresholds.min_total_growth_mib as f64 * MIB
+    }
+}
+
+fn fit(samples: &[(f64, u64)]) -> Option<GrowthEstimate> {
+    if samples.len() < MIN_TREND_SAMPLES {
+        return None;
+    }
+    let count = samples.len() as f64;
+    let mean_x = samples.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = samples.iter().map(|(_, y)| *y as f64).sum::<f64>() / count;
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (x, y) in samples {
+        let dx = x - mean_x;
+        let dy = *y as f64 - mean_y;
+        sxy += dx * dy;
+        sxx += dx * dx;
+        syy += dy * dy;
+    }
+    if sxx == 0.0 {
+        return None;
+    }
+    let slope = sxy / sxx;
+    let r_squared = if syy == 0.0 {
+        0.0
+    } else {
+        (sxy * sxy) / (sxx * syy)
+    };
+    let (first_at, first_rss) = samples[0];
+    let (last_at, last_rss) = samples[samples.len() - 1];
+    Some(GrowthEstimate {
+        bytes_per_hour: slope * 3600.0,
+        r_squared,
+        observed_secs: last_at - first_at,
+        total_growth_bytes: last_rss as i64 - first_rss as i64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    fn feed(tracker: &mut MemoryGrowthTracker, minutes: u64, rss: impl Fn(u64) -> u64) {
+        for minute in 0..=minutes {
+            tracker.observe_samples(
+                minute as f64 * 60.0,
+                [
+                    (42, 1_000, "leaky", rss(minute)),
+                    (7, 5, "steady", 200 * MB),
+                ],
+            );
+        }
+    }
+
+    #[test]
+    fn steady_growth_past_every_threshold_raises_one_warning() {
+        let mut tracker = MemoryGrowthTracker::default();
+        // 2 MiB per minute for 60 minutes: 120 MiB/hour and 120 MiB in total.
+        feed(&mut tracker, 60, |minute| 300 * MB + minute * 2 * MB);
+
+        let estimate = tracker.estimate(42).expect("fitted growth");
+        assert!((estimate.bytes_per_hour / MIB - 120.0).abs() < 0.5);
+        assert!(estimate.r_squared > 0.99);
+        assert_eq!(tracker.trend(42), Some(MemoryTrend::Growing));
+        assert_eq!(tracker.trend(7), Some(MemoryTrend::Stable));
+
+        let warnings = tracker.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].source, WARNING_SOURCE);
+        assert!(warnings[0].message.contains("leaky (PID 42)"));
+    }
+
+    #[test]
+    fn short_or_noisy_growth_is_not_reported() {
+        let mut short = MemoryGrowthTracker::default();
+        feed(&mut short, 10, |minute| 300 * MB + minute * 20 * MB);
+        assert_eq!(short.trend(42), Some(MemoryTrend::Growing));
+        assert!(short.warnings().is_empty(), "below the observation window");
+
+        let mut noisy = MemoryGrowthTracker::default();
+        feed(&mut noisy, 60, |minute| {
+            if minute % 2 == 0 {
+                300 * MB
+            } else {
+                900 * MB
+            }
+        });
+        assert!(noisy.warnings().is_empty(), "sawtooth usage is not a leak");
+    }
+
+    #[test]
+    fn configured_thresholds_gate_the_warning() {
+        let mut tracker = MemoryGrowthTracker::default();
+        tracker.set_thresholds(MemoryGrowthSettings {
+            min_rate_mib_per_hour: 500,
+            ..MemoryGrowthSettings::default()
+        });
+        feed(&mut tracker, 60, |minute| 300 * MB + minute * 2 * MB);
+        assert!(tracker.warnings().is_empty());
+    }
+
+    #[test]
+    fn reused_pid_starts_a_fresh_history_and_exited_processes_are_dropped() {
+        let mut tracker = MemoryGrowthTracker::default();
+        feed(&mut tracker, 60, |minute| 300 * MB + minute * 2 * MB);
+        assert!(tracker.estimate(42).is_some());
+
+        tracker.observe_samples(3_700.0, [(42, 2_000, "new-process", 10 * MB)]);
+        assert!(tracker.estimate(42).is_none());
+        assert!(tracker.warnings().is_empty());
+        assert!(tracker.estimate(7).is_none(), "PID 7 exited");
+
+        for minute in 0..=60 {
+            tracker.observe_samples(
+                3_700.0 + minute as f64 * 60.0,
+                [(42, 2_000, "new-process", 10 * MB + minute * 2 * MB)],
+            );
+        }
+        let warnings = tracker.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].message.starts_with("new-process (PID 42)"),
+            "{}",
+            warnings[0].message
+        );
+    }
+
+    #[test]
+    fn samples_are_thinned_to_the_retained_spacing() {
+        let mut tracker = MemoryGrowthTracker::default();
+        for second in 0..=60 {
+            tracker.observe_samples(second as f64, [(1, 1, "fast", 10 * MB)]);
+        }
+        assert_eq!(tracker.histories[&1].samples.len(), 3);
+    }
+}
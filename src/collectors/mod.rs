@@ -6,19 +6,28 @@ pub mod display;
 pub mod drivers;
 pub mod gpu;
 pub mod memory;
+pub mod memory_growth;
 pub mod network;
 pub mod network_diag;
 pub mod platform;
 pub mod processes;
+pub mod rules;
 pub mod system_info;
 pub mod thermals;
+pub mod thresholds;
 
-use serde::Serialize;
+use std::time::Instant;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 #[cfg(not(target_os = "windows"))]
 use sysinfo::ProcessRefreshKind;
 use sysinfo::{Components, Disks, Networks, ProcessesToUpdate, System};
 
-#[derive(Debug, Clone, Serialize)]
+use crate::settings::ThresholdSettings;
+use crate::types::HealthStatus;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DiagnosticWarning {
     pub source: String,
     pub message: String,
@@ -133,7 +142,7 @@ mod command_tests {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum WarningSeverity {
     Info,
@@ -156,6 +165,21 @@ pub struct SystemSnapshot {
     pub thermals: thermals::ThermalData,
     pub drivers: drivers::DriverData,
     pub warnings: Vec<DiagnosticWarning>,
+    /// Timestamped transitions between refreshes
+    pub events: crate::events::EventLog,
+    /// Per-process RSS history sampled by `refresh_fast`
+    pub memory_growth: memory_growth::MemoryGrowthTracker,
+    /// Warning and critical levels for every rated metric
+    pub thresholds: ThresholdSettings,
+    /// User-defined alert rules and how long each has held
+    pub rules: rules::RuleEngine,
+    /// Shared-settings entries skipped while loading, kept among the
+    /// warnings of the source each belongs to
+    settings_problems: Vec<crate::settings::SettingsProblem>,
+    /// Key that picks which 100 processes `refresh_fast` keeps
+    pub process_rank: crate::types::ProcessSortKey,
+    /// When sysinfo last sampled per-process disk counters
+    process_io_sampled_at: Instant,
     /// Internal sysinfo handle
     sys: System,
     networks: Networks,
@@ -181,6 +205,14 @@ impl Default for SystemSnapshot {
             thermals: thermals::ThermalData::default(),
             drivers: drivers::DriverData::default(),
             warnings: Vec::new(),
+            events: crate::events::EventLog::default(),
+            memory_growth: memory_growth::MemoryGrowthTracker::default(),
+            thresholds: ThresholdSettings::default(),
+            rules: rules::RuleEngine::default(),
+            settings_problems: Vec::new(),
+            process_rank: crate::types::ProcessSortKey::Cpu,
+            // `new_all` samples per-process disk counters as it is built
+            process_io_sampled_at: Instant::now(),
             sys: System::new_all(),
             networks: Networks::new_with_refreshed_list(),
             disks: Disks::new_with_refreshed_list(),
@@ -192,6 +224,65 @@ impl Default for SystemSnapshot {
 }
 
 impl SystemSnapshot {
+    /// Worst status among the subsystems rated by [`Self::health`]
+    pub fn overall_health(&self) -> HealthStatus {
+        self.health().status
+    }
+
+    /// Every subsystem's rating and the reasons behind it
+    pub fn health(&self) -> crate::health::HealthExplanation {
+        crate::health::assess(self)
+    }
+
+    /// Use the shared settings' memory-growth override, thresholds, and
+    /// rules, and show each entry that was skipped while loading them.
+    pub fn apply_settings(&mut self, shared: &crate::settings::SharedSettings) {
+        if let Some(growth) = shared.memory_growth {
+            self.memory_growth.set_thresholds(growth);
+        }
+        self.thresholds = shared.thresholds.clone().unwrap_or_default();
+        self.rules = rules::RuleEngine::new(&shared.rules);
+        self.settings_problems = shared.problems.clone();
+        self.warnings.extend(
+            self.settings_problems
+                .iter()
+                .map(|problem| problem.warning()),
+        );
+    }
+
+    /// Settings problems reported under `source`.
+    fn settings_warnings(&self, source: &str) -> Vec<DiagnosticWarning> {
+        self.settings_problems
+            .iter()
+            .filter(|problem| problem.source == source)
+            .map(|problem| problem.warning())
+            .collect()
+    }
+
+    /// Replace the threshold warnings after a refresh that changed a level.
+    fn refresh_threshold_warnings(&mut self) {
+        self.warnings
+            .retain(|w| w.source != thresholds::WARNING_SOURCE);
+        let warnings = thresholds::warnings(self);
+        self.warnings.extend(warnings);
+        let problems = self.settings_warnings(thresholds::WARNING_SOURCE);
+        self.warnings.extend(problems);
+    }
+
+    /// Evaluate the alert rules and replace their warnings.
+    fn refresh_rule_warnings(&mut self) {
+        if self.rules.is_empty() {
+            return;
+        }
+        let mut engine = std::mem::take(&mut self.rules);
+        engine.observe(self, Instant::now());
+        self.rules = engine;
+        self.warnings.retain(|w| w.source != rules::WARNING_SOURCE);
+        self.warnings.extend(self.rules.warnings());
+        let problems = self.settings_warnings(rules::WARNING_SOURCE);
+        self.warnings.extend(problems);
+    }
+
     /// Refresh static info (once at startup)
     pub fn refresh_static(&mut self) {
         self.system = system_info::collect(&self.sys);
@@ -217,15 +308,32 @@ impl SystemSnapshot {
         self.network = network::collect(&mut self.networks);
         self.network.adapters = adapters;
         self.network.adapter_status = adapter_status;
-        self.processes = processes::collect(&self.sys);
+        let io_secs = self.process_io_interval();
+        self.processes = processes::collect(&self.sys, io_secs, self.process_rank);
+
+        self.memory_growth.observe(&self.sys);
+        self.warnings
+            .retain(|w| w.source != memory_growth::WARNING_SOURCE);
+        self.warnings.extend(self.memory_growth.warnings());
+        self.refresh_threshold_warnings();
+        self.refresh_rule_warnings();
+    }
+
+    /// Seconds covered by sysinfo's since-last-refresh disk counters. Call
+    /// once per process refresh that includes disk usage.
+    fn process_io_interval(&mut self) -> f64 {
+        let now = Instant::now();
+        let secs = now.duration_since(self.process_io_sampled_at).as_secs_f64();
+        self.process_io_sampled_at = now;
+        secs
     }
 
     /// Refresh the same fast values consumed by the native GUI without asking
     /// sysinfo to poll process fields that are absent from the GUI projection.
     ///
     /// The established TUI keeps calling `refresh_fast` above. This additive
-    /// path preserves its output and cadence while avoiding per-process disk
-    /// and executable refresh work in the separate GUI engine process.
+    /// path preserves its output and cadence while avoiding per-process
+    /// executable refresh work in the separate GUI engine process.
     pub fn refresh_fast_gui(&mut self) {
         self.refresh_fast_gui_summary();
         self.refresh_processes_gui(crate::types::ProcessSortKey::Cpu);
@@ -275,9 +383,13 @@ impl SystemSnapshot {
             self.sys.refresh_processes_specifics(
                 ProcessesToUpdate::All,
                 true,
-                ProcessRefreshKind::nothing().with_cpu().with_memory(),
+                ProcessRefreshKind::nothing()
+                    .with_cpu()
+                    .with_memory()
+                    .with_disk_usage(),
             );
-            self.processes = processes::collect_limited(&self.sys, 16, sort);
+            let io_secs = self.process_io_interval();
+            self.processes = processes::collect_limited(&self.sys, 16, sort, io_secs);
         }
     }
 
@@ -310,6 +422,8 @@ impl SystemSnapshot {
         self.thermals = thermal_data;
         self.warnings.retain(|w| w.source != "Thermals");
         self.warnings.extend(thermal_warnings);
+        self.refresh_threshold_warnings();
+        self.refresh_rule_warnings();
     }
 
     /// Refresh drivers (manual or every 30s)
@@ -347,4 +461,27 @@ impl SystemSnapshot {
         self.warnings.retain(|w| w.source != "Disk Health");
         self.warnings.extend(health_warnings);
     }
+
+    /// Add the transitions since the previous call to the event log. Processes
+    /// are read from sysinfo rather than the process table, which keeps only
+    /// the busiest hundred.
+    pub fn record_events(&mut self, unix_ms: u64) {
+        let total_memory = self.sys.total_memory();
+        let processes = self.sys.processes().values().map(|process| {
+            let memory_percent = if total_memory > 0 {
+                process.memory() as f64 / total_memory as f64 * 100.0
+            } else {
+                0.0
+            };
+            crate::events::ProcessSample {
+                pid: process.pid().as_u32(),
+                name: process.name().to_string_lossy().into_owned(),
+                cpu_percent: process.cpu_usage(),
+                memory_percent,
+            }
+        });
+        let mut events = std::mem::take(&mut self.events);
+        events.observe(self, processes, unix_ms);
+        self.events = events;
+    }
 }
@@ -1,9 +1,10 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use sysinfo::Networks;
 
 use crate::observation::Observation;
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct NetworkData {
     pub interfaces: Vec<InterfaceInfo>,
     pub total_download_rate: u64,
@@ -12,7 +13,7 @@ pub struct NetworkData {
     pub adapter_status: Observation,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct InterfaceInfo {
     pub name: String,
     pub ip_addresses: Vec<String>,
@@ -25,7 +26,7 @@ pub struct InterfaceInfo {
     pub operational_state: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NetworkAdapterInfo {
     pub name: String,
     pub description: Option<String>,
@@ -1,12 +1,13 @@
 use std::net::ToSocketAddrs;
 use std::time::Instant;
 
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use super::command::{run_output, run_stdout, CommandTimeout};
 use super::DiagnosticWarning;
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct NetworkDiagData {
     pub gateway: ConnectivityResult,
     pub dns: DnsResult,
@@ -15,7 +16,7 @@ pub struct NetworkDiagData {
     pub listening_ports: Vec<ConnectionInfo>,
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ConnectivityResult {
     pub reachable: bool,
     pub latency_ms: Option<f64>,
@@ -23,7 +24,7 @@ pub struct ConnectivityResult {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct DnsResult {
     pub resolved: bool,
     pub resolution_ms: Option<f64>,
@@ -32,7 +33,7 @@ pub struct DnsResult {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConnectionInfo {
     pub protocol: Protocol,
     pub local_addr: String,
@@ -44,7 +45,7 @@ pub struct ConnectionInfo {
     pub process_name: Option<String>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Protocol {
     Tcp,
@@ -60,7 +61,7 @@ impl std::fmt::Display for Protocol {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ConnectionState {
     Established,
@@ -1,7 +1,8 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::LazyLock;
-use sysinfo::System;
+use sysinfo::{Process, System};
 
 use crate::types::ProcessSortKey;
 
@@ -11,14 +12,14 @@ mod windows_gui;
 #[cfg(target_os = "windows")]
 pub use windows_gui::GuiProcessSampler;
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ProcessData {
     pub list: Vec<ProcessInfo>,
     pub total_count: usize,
     pub total_threads: usize,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProcessInfo {
     pub pid: u32,
     pub name: String,
@@ -27,6 +28,19 @@ pub struct ProcessInfo {
     pub memory_bytes: u64,
     pub memory_percent: f64,
     pub status: String,
+    /// Bytes read from storage per second since the previous process refresh
+    pub disk_read_bytes_per_sec: u64,
+    /// Bytes written to storage per second since the previous process refresh
+    pub disk_write_bytes_per_sec: u64,
+    /// GPU memory held through DRM clients; `None` where unsupported or unreadable
+    pub gpu_memory_bytes: Option<u64>,
+}
+
+impl ProcessInfo {
+    pub fn disk_bytes_per_sec(&self) -> u64 {
+        self.disk_read_bytes_per_sec
+            .saturating_add(self.disk_write_bytes_per_sec)
+    }
 }
 
 /// Map binary names to user-friendly application names (allocated once)
@@ -99,7 +113,22 @@ fn get_friendly_name(process_name: &str) -> String {
         })
 }
 
-pub fn collect(sys: &System) -> ProcessData {
+/// Per-second disk rates from sysinfo's since-last-refresh counters.
+/// `io_secs` is the time since the previous refresh that sampled disk usage.
+fn disk_rates(process: &Process, io_secs: f64) -> (u64, u64) {
+    if io_secs <= 0.0 {
+        return (0, 0);
+    }
+    let usage = process.disk_usage();
+    (
+        (usage.read_bytes as f64 / io_secs) as u64,
+        (usage.written_bytes as f64 / io_secs) as u64,
+    )
+}
+
+/// Collect the 100 heaviest processes by `rank`, so disk and GPU sorts find
+/// quiet-CPU processes too. Name and PID orderings keep the CPU top 100.
+pub fn collect(sys: &System, io_secs: f64, rank: ProcessSortKey) -> ProcessData {
     let total_memory = sys.total_memory();
     let mut processes: Vec<ProcessInfo> = sys
         .processes()
@@ -113,6 +142,7 @@ pub fn collect(sys: &System) -> ProcessData {
             } else {
                 0.0
             };
+            let (disk_read, disk_write) = disk_rates(p, io_secs);
 
             ProcessInfo {
                 pid: p.pid().as_u32(),
@@ -122,21 +152,35 @@ pub fn collect(sys: &System) -> ProcessData {
                 memory_bytes: mem,
                 memory_percent: mem_pct,
                 status: format!("{:?}", p.status()),
+                disk_read_bytes_per_sec: disk_read,
+                disk_write_bytes_per_sec: disk_write,
+                gpu_memory_bytes: None,
             }
         })
         .collect();
 
     let total_count = processes.len();
 
-    // Sort by CPU usage descending by default
-    processes.sort_by(|a, b| {
-        b.cpu_percent
-            .partial_cmp(&a.cpu_percent)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+    let rank = match rank {
+        ProcessSortKey::Name | ProcessSortKey::Pid => ProcessSortKey::Cpu,
+        rank => rank,
+    };
+    // DRM fdinfo is walked per open file, so only a GPU ranking reads it for
+    // every process; otherwise just the displayed rows pay for it
+    if rank == ProcessSortKey::GpuMemory {
+        for process in &mut processes {
+            process.gpu_memory_bytes = gpu_memory_bytes(process.pid);
+        }
+    }
+    sort_process_info_rows(&mut processes, rank);
 
     // Keep top 100 for display
     processes.truncate(100);
+    if rank != ProcessSortKey::GpuMemory {
+        for process in &mut processes {
+            process.gpu_memory_bytes = gpu_memory_bytes(process.pid);
+        }
+    }
 
     ProcessData {
         list: processes,
@@ -149,7 +193,6 @@ fn is_ranked_consumer(pid: u32) -> bool {
     pid != 0
 }
 
-#[cfg(any(target_os = "windows", test))]
 pub(super) fn sort_process_info_rows(rows: &mut [ProcessInfo], sort: ProcessSortKey) {
     match sort {
         ProcessSortKey::Cpu => rows.sort_by(|a, b| {
@@ -170,13 +213,28 @@ pub(super) fn sort_process_info_rows(rows: &mut [ProcessInfo], sort: ProcessSort
                 process.pid,
             )
         }),
+        ProcessSortKey::DiskIo => rows.sort_by(|a, b| {
+            b.disk_bytes_per_sec()
+                .cmp(&a.disk_bytes_per_sec())
+                .then_with(|| a.pid.cmp(&b.pid))
+        }),
+        ProcessSortKey::GpuMemory => rows.sort_by(|a, b| {
+            b.gpu_memory_bytes
+                .cmp(&a.gpu_memory_bytes)
+                .then_with(|| a.pid.cmp(&b.pid))
+        }),
     }
 }
 
 /// Build the GUI's bounded process projection without allocating names and
 /// status strings for every process on the machine. The TUI keeps using
 /// `collect` above and retains its top-100 contract.
-pub fn collect_limited(sys: &System, limit: usize, sort: ProcessSortKey) -> ProcessData {
+pub fn collect_limited(
+    sys: &System,
+    limit: usize,
+    sort: ProcessSortKey,
+    io_secs: f64,
+) -> ProcessData {
     let total_memory = sys.total_memory();
     let total_count = sys.processes().len();
     // PID 0 is an operating-system idle accounting row on Windows, not a
@@ -207,6 +265,21 @@ pub fn collect_limited(sys: &System, limit: usize, sort: ProcessSortKey) -> Proc
                 process.pid().as_u32(),
             )
         }),
+        ProcessSortKey::DiskIo => ranked.sort_by_cached_key(|process| {
+            let (read, write) = disk_rates(process, io_secs);
+            (
+                std::cmp::Reverse(read.saturating_add(write)),
+                process.pid().as_u32(),
+            )
+        }),
+        // Ranking by GPU memory has to read every process's DRM fdinfo; the
+        // other keys only read it for the bounded result below.
+        ProcessSortKey::GpuMemory => ranked.sort_by_cached_key(|process| {
+            (
+                std::cmp::Reverse(gpu_memory_bytes(process.pid().as_u32())),
+                process.pid().as_u32(),
+            )
+        }),
     }
     ranked.truncate(limit);
 
@@ -215,6 +288,7 @@ pub fn collect_limited(sys: &System, limit: usize, sort: ProcessSortKey) -> Proc
         .map(|process| {
             let name = process.name().to_string_lossy().to_string();
             let memory_bytes = process.memory();
+            let (disk_read, disk_write) = disk_rates(process, io_secs);
             ProcessInfo {
                 pid: process.pid().as_u32(),
                 friendly_name: get_friendly_name(&name),
@@ -227,6 +301,9 @@ pub fn collect_limited(sys: &System, limit: usize, sort: ProcessSortKey) -> Proc
                     0.0
                 },
                 status: format!("{:?}", process.status()),
+                disk_read_bytes_per_sec: disk_read,
+                disk_write_bytes_per_sec: disk_write,
+                gpu_memory_bytes: gpu_memory_bytes(process.pid().as_u32()),
             }
         })
         .collect();
@@ -238,6 +315,107 @@ pub fn collect_limited(sys: &System, limit: usize, sort: ProcessSortKey) -> Proc
     }
 }
 
+/// GPU memory held by a process, summed across its DRM clients.
+///
+/// Linux exposes per-client usage in `/proc/<pid>/fdinfo` for every open
+/// `/dev/dri` node. Several descriptors can share one client, so clients are
+/// counted once by device and client id. Returns `None` when the process's
+/// descriptors cannot be read (other users' processes without privilege).
+#[cfg(target_os = "linux")]
+fn gpu_memory_bytes(pid: u32) -> Option<u64> {
+    let descriptors = std::fs::read_dir(format!("/proc/{pid}/fd")).ok()?;
+    let mut clients = HashMap::new();
+    for descriptor in descriptors.flatten() {
+        let is_drm = std::fs::read_link(descriptor.path())
+            .is_ok_and(|target| target.starts_with("/dev/dri/"));
+        if !is_drm {
+            continue;
+        }
+        let fdinfo = format!(
+            "/proc/{pid}/fdinfo/{}",
+            descriptor.file_name().to_string_lossy()
+        );
+        if let Some(client) = std::fs::read_to_string(fdinfo)
+            .ok()
+            .and_then(|text| parse_drm_fdinfo(&text))
+        {
+            clients.insert(client.key, client.memory_bytes);
+        }
+    }
+    Some(clients.values().sum())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn gpu_memory_bytes(_pid: u32) -> Option<u64> {
+    None
+}
+
+#[cfg(any(target_os = "linux", test))]
+#[derive(Debug, PartialEq, Eq)]
+struct DrmClientMemory {
+    /// (`drm-pdev`, `drm-client-id`)
+    key: (String, String),
+    memory_bytes: u64,
+}
+
+/// Parse one DRM fdinfo file (kernel `drm-usage-stats` format).
+///
+/// Newer drivers report `drm-total-<region>`; older amdgpu kernels only report
+/// `drm-memory-<region>`. Device-local regions (`vram*`, `local*`) are summed
+/// when present; integrated GPUs without them report their shared regions.
+#[cfg(any(target_os = "linux", test))]
+fn parse_drm_fdinfo(text: &str) -> Option<DrmClientMemory> {
+    let mut driver = None;
+    let mut pdev = String::new();
+    let mut client_id = None;
+    let mut total = Vec::new();
+    let mut legacy = Vec::new();
+    for line in text.lines() {
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if field == "drm-driver" {
+            driver = Some(value);
+        } else if field == "drm-pdev" {
+            pdev = value.to_string();
+        } else if field == "drm-client-id" {
+            client_id = Some(value.to_string());
+        } else if let Some(region) = field.strip_prefix("drm-total-") {
+            total.extend(parse_drm_bytes(value).map(|bytes| (region, bytes)));
+        } else if let Some(region) = field.strip_prefix("drm-memory-") {
+            legacy.extend(parse_drm_bytes(value).map(|bytes| (region, bytes)));
+        }
+    }
+    driver?;
+    let regions = if total.is_empty() { legacy } else { total };
+    let is_local = |region: &str| region.starts_with("vram") || region.starts_with("local");
+    let has_local = regions.iter().any(|(region, _)| is_local(region));
+    let memory_bytes = regions
+        .iter()
+        .filter(|(region, _)| !has_local || is_local(region))
+        .map(|(_, bytes)| bytes)
+        .sum();
+    Some(DrmClientMemory {
+        key: (pdev, client_id?),
+        memory_bytes,
+    })
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn parse_drm_bytes(value: &str) -> Option<u64> {
+    let mut parts = value.split_whitespace();
+    let amount = parts.next()?.parse::<u64>().ok()?;
+    let scale = match parts.next() {
+        None => 1,
+        Some("KiB") => 1024,
+        Some("MiB") => 1024 * 1024,
+        Some("GiB") => 1024 * 1024 * 1024,
+        Some(_) => return None,
+    };
+    amount.checked_mul(scale)
+}
+
 #[cfg(test)]
 mod gui_projection_tests {
     use super::*;
@@ -251,6 +429,9 @@ mod gui_projection_tests {
             memory_bytes: memory,
             memory_percent: 0.0,
             status: "Run".into(),
+            disk_read_bytes_per_sec: 0,
+            disk_write_bytes_per_sec: 0,
+            gpu_memory_bytes: None,
         }
     }
 
@@ -293,6 +474,82 @@ mod gui_projection_tests {
         );
     }
 
+    #[test]
+    fn disk_and_gpu_sorts_rank_heaviest_first_and_unreadable_gpu_last() {
+        let mut rows = fixture();
+        rows[0].disk_write_bytes_per_sec = 4096;
+        rows[1].disk_read_bytes_per_sec = 1024;
+        rows[1].disk_write_bytes_per_sec = 1024;
+        rows[0].gpu_memory_bytes = Some(0);
+        rows[2].gpu_memory_bytes = Some(512 * 1024 * 1024);
+
+        let mut by_disk = rows.clone();
+        sort_process_info_rows(&mut by_disk, ProcessSortKey::DiskIo);
+        assert_eq!(
+            by_disk.iter().map(|row| row.pid).collect::<Vec<_>>(),
+            [40, 20, 10]
+        );
+
+        sort_process_info_rows(&mut rows, ProcessSortKey::GpuMemory);
+        assert_eq!(
+            rows.iter().map(|row| row.pid).collect::<Vec<_>>(),
+            [10, 40, 20]
+        );
+    }
+
+    #[test]
+    fn collection_keeps_the_heaviest_processes_by_the_ranking_key() {
+        let sys = System::new_all();
+        let heaviest = sys
+            .processes()
+            .values()
+            .map(|process| process.memory())
+            .max()
+            .unwrap_or(0);
+        let data = collect(&sys, 1.0, ProcessSortKey::Memory);
+        assert!(data.list.len() <= 100);
+        assert_eq!(
+            data.list.first().map_or(0, |row| row.memory_bytes),
+            heaviest
+        );
+        assert!(data
+            .list
+            .windows(2)
+            .all(|pair| pair[0].memory_bytes >= pair[1].memory_bytes));
+    }
+
+    #[test]
+    fn drm_fdinfo_prefers_device_local_totals_and_falls_back_to_legacy_keys() {
+        let amdgpu = "pos:\t0\nflags:\t02100002\ndrm-driver:\tamdgpu\n\
+            drm-pdev:\t0000:03:00.0\ndrm-client-id:\t17\n\
+            drm-memory-vram:\t2048 KiB\ndrm-memory-gtt:\t512 KiB\n\
+            drm-total-vram:\t4096 KiB\ndrm-total-gtt:\t1024 KiB\n";
+        assert_eq!(
+            parse_drm_fdinfo(amdgpu),
+            Some(DrmClientMemory {
+                key: ("0000:03:00.0".into(), "17".into()),
+                memory_bytes: 4096 * 1024,
+            })
+        );
+
+        let legacy = "drm-driver:\tamdgpu\ndrm-client-id:\t3\n\
+            drm-memory-vram:\t2 MiB\ndrm-memory-gtt:\t1 MiB\n";
+        assert_eq!(
+            parse_drm_fdinfo(legacy).unwrap().memory_bytes,
+            2 * 1024 * 1024
+        );
+
+        let integrated = "drm-driver:\ti915\ndrm-client-id:\t9\n\
+            drm-total-system0:\t300 KiB\ndrm-total-stolen-system0:\t0\n";
+        assert_eq!(
+            parse_drm_fdinfo(integrated).unwrap().memory_bytes,
+            300 * 1024
+        );
+
+        assert_eq!(parse_drm_fdinfo("pos:\t0\nflags:\t0100002\n"), None);
+        assert_eq!(parse_drm_fdinfo("drm-driver:\ti915\n"), None);
+    }
+
     #[test]
     fn pid_zero_is_inventory_only_and_never_an_actionable_consumer() {
         assert!(!is_ranked_consumer(0));
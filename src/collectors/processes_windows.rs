@@ -3,6 +3,7 @@ use std::{
     ffi::c_void,
     mem::{size_of, zeroed},
     ptr,
+    time::Instant,
 };
 
 use winapi::{
@@ -33,6 +34,14 @@ struct ProcessTimes {
     total: u64,
 }
 
+/// Cumulative I/O transfer counters from the previous batched sample.
+#[derive(Debug, Clone, Copy)]
+struct ProcessIo {
+    creation: u64,
+    read: u64,
+    write: u64,
+}
+
 #[derive(Debug, Clone, Copy)]
 struct SystemCpuTimes {
     total: u64,
@@ -81,6 +90,8 @@ impl Drop for ProcessHandle {
 pub struct GuiProcessSampler {
     handles: HashMap<u32, ProcessHandle>,
     previous_process_times: HashMap<u32, ProcessTimes>,
+    previous_process_io: HashMap<u32, ProcessIo>,
+    previous_io_at: Option<Instant>,
     previous_system_times: Option<SystemCpuTimes>,
     total_cpu_percent: f32,
     process_buffer: Vec<u8>,
@@ -127,6 +138,12 @@ impl GuiProcessSampler {
         // near-zero denominator and could manufacture a one-frame CPU spike.
         self.commit_system_cpu_sample(pending_cpu);
         let global_delta = pending_cpu.global_delta;
+        let sampled_at = Instant::now();
+        let io_secs = self
+            .previous_io_at
+            .replace(sampled_at)
+            .map(|previous| sampled_at.duration_since(previous).as_secs_f64())
+            .filter(|secs| *secs > 0.0);
         let process_buffer = &self.process_buffer[..valid_len];
         let total_count = self.batched_rows.len();
         let total_threads = self
@@ -156,10 +173,29 @@ impl GuiProcessSampler {
                 })
                 .unwrap_or(0.0);
             self.previous_process_times.insert(row.pid, current);
+            let io = ProcessIo {
+                creation: row.creation,
+                read: row.read_transfer_bytes,
+                write: row.write_transfer_bytes,
+            };
+            let (disk_read_bytes_per_sec, disk_write_bytes_per_sec) = self
+                .previous_process_io
+                .insert(row.pid, io)
+                .filter(|previous| previous.creation == io.creation)
+                .zip(io_secs)
+                .map(|(previous, secs)| {
+                    (
+                        (io.read.saturating_sub(previous.read) as f64 / secs) as u64,
+                        (io.write.saturating_sub(previous.write) as f64 / secs) as u64,
+                    )
+                })
+                .unwrap_or((0, 0));
             if is_ranked_consumer(row.pid) {
                 self.ranked_rows.push(RankedBatchedRow {
                     row,
                     cpu_percent,
+                    disk_read_bytes_per_sec,
+                    disk_write_bytes_per_sec,
                     name: None,
                     friendly_name: None,
                 });
@@ -167,6 +203,8 @@ impl GuiProcessSampler {
         }
         self.previous_process_times
             .retain(|pid, _| self.seen_pids.contains(pid));
+        self.previous_process_io
+            .retain(|pid, _| self.seen_pids.contains(pid));
         self.handles.retain(|pid, _| self.seen_pids.contains(pid));
         if sort == ProcessSortKey::Name {
             for candidate in &mut self.ranked_rows {
@@ -198,6 +236,11 @@ impl GuiProcessSampler {
                         0.0
                     },
                     status: "Run".into(),
+                    disk_read_bytes_per_sec: candidate.disk_read_bytes_per_sec,
+                    disk_write_bytes_per_sec: candidate.disk_write_bytes_per_sec,
+                    // DRM fdinfo is Linux-only; Windows has no per-process
+                    // dedicated-memory counter in this snapshot.
+                    gpu_memory_bytes: None,
                 }
             })
             .collect();
@@ -273,6 +316,10 @@ impl GuiProcessSampler {
                     memory_bytes: 0,
                     memory_percent: 0.0,
                     status: "Run".into(),
+                    // The Toolhelp fallback has no transfer counters.
+                    disk_read_bytes_per_sec: 0,
+                    disk_write_bytes_per_sec: 0,
+                    gpu_memory_bytes: None,
                 });
             }
 
@@ -375,11 +422,26 @@ struct NativeProcessInformation {
     page_fault_count: u32,
     peak_working_set_size: usize,
     working_set_size: usize,
+    quota_peak_paged_pool_usage: usize,
+    quota_paged_pool_usage: usize,
+    quota_peak_non_paged_pool_usage: usize,
+    quota_non_paged_pool_usage: usize,
+    pagefile_usage: usize,
+    peak_pagefile_usage: usize,
+    private_page_count: usize,
+    read_operation_count: i64,
+    write_operation_count: i64,
+    other_operation_count: i64,
+    read_transfer_count: i64,
+    write_transfer_count: i64,
+    other_transfer_count: i64,
 }
 
 const _: () = assert!(size_of::<usize>() == 8);
 const _: () = assert!(std::mem::offset_of!(NativeProcessInformation, image_name) == 56);
 const _: () = assert!(std::mem::offset_of!(NativeProcessInformation, working_set_size) == 144);
+const _: () = assert!(std::mem::offset_of!(NativeProcessInformation, read_transfer_count) == 232);
+const _: () = assert!(size_of::<NativeProcessInformation>() == 256);
 
 #[derive(Debug, Clone, Copy)]
 struct BatchedProcessRow {
@@ -390,12 +452,16 @@ struct BatchedProcessRow {
     creation: u64,
     total_time: u64,
     working_set_bytes: u64,
+    read_transfer_bytes: u64,
+    write_transfer_bytes: u64,
 }
 
 #[derive(Debug)]
 struct RankedBatchedRow {
     row: BatchedProcessRow,
     cpu_percent: f32,
+    disk_read_bytes_per_sec: u64,
+    disk_write_bytes_per_sec: u64,
     // Names stay lazy for CPU, memory, and PID ranking so only the bounded
     // result pays for UTF-16 decoding. Name ranking intentionally decodes the
     // complete inventory before truncation.
@@ -423,6 +489,17 @@ fn compare_batched_rows(
             .cmp(&left.row.working_set_bytes)
             .then_with(|| left.row.pid.cmp(&right.row.pid)),
         ProcessSortKey::Pid => left.row.pid.cmp(&right.row.pid),
+        ProcessSortKey::DiskIo => right
+            .disk_read_bytes_per_sec
+            .saturating_add(right.disk_write_bytes_per_sec)
+            .cmp(
+                &left
+                    .disk_read_bytes_per_sec
+                    .saturating_add(left.disk_write_bytes_per_sec),
+            )
+            .then_with(|| left.row.pid.cmp(&right.row.pid)),
+        // No Windows row carries GPU memory, so every row ties on `None`.
+        ProcessSortKey::GpuMemory => left.row.pid.cmp(&right.row.pid),
         ProcessSortKey::Name => {
             let left_friendly = left
                 .friendly_name
@@ -554,6 +631,8 @@ fn parse_system_processes(buffer: &[u8], rows: &mut Vec<BatchedProcessRow>) -> O
                 .max(0)
                 .saturating_add(record.kernel_time.max(0)) as u64,
             working_set_bytes: record.working_set_size as u64,
+            read_transfer_bytes: record.read_transfer_count.max(0) as u64,
+            write_transfer_bytes: record.write_transfer_count.max(0) as u64,
         });
         if record.next_entry_offset == 0 {
             break;
@@ -743,8 +822,12 @@ mod tests {
                 creation: 1,
                 total_time: 1,
                 working_set_bytes,
+                read_transfer_bytes: 0,
+                write_transfer_bytes: 0,
             },
             cpu_percent,
+            disk_read_bytes_per_sec: 0,
+            disk_write_bytes_per_sec: 0,
             name: Some(name.into()),
             friendly_name: Some(friendly_name.into()),
         }
@@ -794,6 +877,12 @@ mod tests {
             retain_best_batched_rows(&mut rows, sort, 2);
             assert_eq!(pids(&rows), expected);
         }
+
+        let mut rows = fixture();
+        rows[0].disk_write_bytes_per_sec = 4096;
+        rows[1].disk_read_bytes_per_sec = 8192;
+        retain_best_batched_rows(&mut rows, ProcessSortKey::DiskIo, 2);
+        assert_eq!(pids(&rows), [20, 40]);
     }
 
     #[test]
@@ -0,0 +1,618 @@
+//! User-defined alert rules over snapshot fields.
+//!
+//! A rule such as `cpu.total_usage > 90 for 5m` names a field by its path in
+//! the snapshot, compares it with a literal, and can require the comparison
+//! to hold for a while before it raises a warning. A raised rule clears only
+//! once its value is back past the limit by the rule's hysteresis and has
+//! stayed there for `clear_after`, so a reading that hovers at the limit does
+//! not raise and clear the warning on alternate refreshes.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+use super::{DiagnosticWarning, SystemSnapshot, WarningSeverity};
+use crate::settings::RuleSettings;
+
+/// Warning source for rule reports, replaced wholesale on every pass.
+pub const WARNING_SOURCE: &str = "Rules";
+
+/// Snapshot sections a rule path may start with.
+const SECTIONS: &[&str] = &[
+    "system",
+    "cpu",
+    "memory",
+    "disk",
+    "disk_health",
+    "displays",
+    "gpu",
+    "network",
+    "network_diag",
+    "processes",
+    "thermals",
+    "drivers",
+];
+/// Fields an array element is matched on by a `[name]` selector.
+const SELECTOR_KEYS: &[&str] = &["mount_point", "name", "label", "model", "id"];
+const MAX_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+const MAX_MESSAGE_CHARS: usize = 512;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Field(String),
+    /// An array element by index or by [`SELECTOR_KEYS`], or an object key.
+    Select(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Operator {
+    /// Longer spellings first, so `>=` is not read as `>`.
+    const ALL: [(&'static str, Self); 6] = [
+        (">=", Self::GreaterOrEqual),
+        ("<=", Self::LessOrEqual),
+        ("==", Self::Equal),
+        ("!=", Self::NotEqual),
+        (">", Self::Greater),
+        ("<", Self::Less),
+    ];
+
+    fn is_ordering(self) -> bool {
+        !matches!(self, Self::Equal | Self::NotEqual)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    Number(f64),
+    Bool(bool),
+    Text(String),
+}
+
+/// A parsed `when` expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    section: String,
+    steps: Vec<Step>,
+    operator: Operator,
+    literal: Literal,
+    /// How long the comparison must hold before the rule is raised.
+    hold: Duration,
+}
+
+impl Condition {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let (start, spelling, operator) = find_operator(text)?;
+        let (section, steps) = parse_path(text[..start].trim())?;
+        let rest = text[start + spelling.len()..].trim();
+        let (literal, rest) = parse_literal(rest)?;
+        if operator.is_ordering() && !matches!(literal, Literal::Number(_)) {
+            return Err(format!("`{spelling}` needs a number to compare with"));
+        }
+        let hold = match rest.trim() {
+            "" => Duration::ZERO,
+            rest => match rest.strip_prefix("for ") {
+                Some(duration) => parse_duration(duration.trim())?,
+                None => return Err(format!("unexpected `{rest}` after the value")),
+            },
+        };
+        Ok(Self {
+            section,
+            steps,
+            operator,
+            literal,
+            hold,
+        })
+    }
+
+    /// Whether `value` satisfies the comparison with the limit moved `margin`
+    /// toward the clear side, or `None` when the two cannot be compared.
+    fn holds(&self, value: &Value, margin: f64) -> Option<bool> {
+        match (&self.literal, value) {
+            (Literal::Number(limit), Value::Number(value)) => {
+                let value = value.as_f64()?;
+                Some(match self.operator {
+                    Operator::Greater => value > limit - margin,
+                    Operator::GreaterOrEqual => value >= limit - margin,
+                    Operator::Less => value < limit + margin,
+                    Operator::LessOrEqual => value <= limit + margin,
+                    Operator::Equal => value == *limit,
+                    Operator::NotEqual => value != *limit,
+                })
+            }
+            (Literal::Bool(expected), Value::Bool(value)) => self.equality(value == expected),
+            (Literal::Text(expected), Value::String(value)) => self.equality(value == expected),
+            _ => None,
+        }
+    }
+
+    fn equality(&self, equal: bool) -> Option<bool> {
+        match self.operator {
+            Operator::Equal => Some(equal),
+            Operator::NotEqual => Some(!equal),
+            _ => None,
+        }
+    }
+
+    fn resolve<'a>(&self, section: &'a Value) -> Option<&'a Value> {
+        self.steps
+            .iter()
+            .try_fold(section, |value, step| match step {
+                Step::Field(name) => value.get(name),
+                Step::Select(selector) => select(value, selector),
+            })
+            .filter(|value| !value.is_null())
+    }
+}
+
+fn find_operator(text: &str) -> Result<(usize, &'static str, Operator), String> {
+    let mut depth = 0usize;
+    for (index, character) in text.char_indices() {
+        match character {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => {
+                if let Some((spelling, operator)) = Operator::ALL
+                    .iter()
+                    .find(|(spelling, _)| text[index..].starts_with(spelling))
+                {
+                    return Ok((index, spelling, *operator));
+                }
+            }
+            _ => {}
+        }
+    }
+    Err("expected a comparison: >, >=, <, <=, ==, or !=".into())
+}
+
+fn parse_path(text: &str) -> Result<(String, Vec<Step>), String> {
+    let is_name = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut steps = Vec::new();
+    let mut rest = text;
+    let mut expect_name = true;
+    while !rest.is_empty() {
+        if expect_name {
+            let end = rest.find(|c| !is_name(c)).unwrap_or(rest.len());
+            if end == 0 {
+                return Err(format!("expected a field name in `{text}`"));
+            }
+            steps.push(Step::Field(rest[..end].to_string()));
+            rest = &rest[end..];
+            expect_name = false;
+        } else if let Some(after) = rest.strip_prefix('.') {
+            rest = after;
+            expect_name = true;
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after
+                .find(']')
+                .ok_or_else(|| format!("unclosed `[` in `{text}`"))?;
+            let selector = after[..end].trim();
+            if selector.is_empty() {
+                return Err(format!("empty `[]` in `{text}`"));
+            }
+            steps.push(Step::Select(selector.to_string()));
+            rest = &after[end + 1..];
+        } else {
+            return Err(format!("unexpected `{rest}` in `{text}`"));
+        }
+    }
+    if expect_name {
+        return Err(format!("expected a field name in `{text}`"));
+    }
+    let section = match steps.remove(0) {
+        Step::Field(name) if SECTIONS.contains(&name.as_str()) => name,
+        Step::Field(name) | Step::Select(name) => {
+            return Err(format!(
+                "unknown section `{name}`; expected one of {}",
+                SECTIONS.join(", ")
+            ))
+        }
+    };
+    Ok((section, steps))
+}
+
+fn parse_literal(text: &str) -> Result<(Literal, &str), String> {
+    if let Some(quoted) = text.strip_prefix('"') {
+        let end = quoted
+            .find('"')
+            .ok_or_else(|| "unclosed string value".to_string())?;
+        return Ok((Literal::Text(quoted[..end].to_string()), &quoted[end + 1..]));
+    }
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    let literal = match &text[..end] {
+        "" => return Err("expected a value after the comparison".into()),
+        "true" => Literal::Bool(true),
+        "false" => Literal::Bool(false),
+        token => match token.parse::<f64>() {
+            Ok(number) if number.is_finite() => Literal::Number(number),
+            _ => {
+                return Err(format!(
+                    "`{token}` is not a number, true, false, or a quoted string"
+                ))
+            }
+        },
+    };
+    Ok((literal, &text[end..]))
+}
+
+/// A duration as the CLI writes them, such as `90s`, `5m`, or `1h`, up to
+/// a day.
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let duration = crate::cli::parse_duration(text)?;
+    if duration > MAX_DURATION {
+        return Err(format!("`{text}` is longer than a day"));
+    }
+    Ok(duration)
+}
+
+fn select<'a>(value: &'a Value, selector: &str) -> Option<&'a Value> {
+    match value {
+        Value::Array(items) => match selector.parse::<usize>() {
+            Ok(index) => items.get(index),
+            Err(_) => items.iter().find(|item| {
+                SELECTOR_KEYS
+                    .iter()
+                    .any(|key| item.get(key).and_then(Value::as_str) == Some(selector))
+            }),
+        },
+        Value::Object(map) => map.get(selector),
+        _ => None,
+    }
+}
+
+/// One snapshot section as a rule sees it: its JSON form plus the derived
+/// percentages the UI shows.
+fn section_value(snapshot: &SystemSnapshot, name: &str) -> Option<Value> {
+    let mut value = match name {
+        "system" => serde_json::to_value(&snapshot.system),
+        "cpu" => serde_json::to_value(&snapshot.cpu),
+        "memory" => serde_json::to_value(&snapshot.memory),
+        "disk" => serde_json::to_value(&snapshot.disk),
+        "disk_health" => serde_json::to_value(&snapshot.disk_health),
+        "displays" => serde_json::to_value(&snapshot.displays),
+        "gpu" => serde_json::to_value(&snapshot.gpu),
+        "network" => serde_json::to_value(&snapshot.network),
+        "network_diag" => serde_json::to_value(&snapshot.network_diag),
+        "processes" => serde_json::to_value(&snapshot.processes),
+        "thermals" => serde_json::to_value(&snapshot.thermals),
+        "drivers" => serde_json::to_value(&snapshot.drivers),
+        _ => return None,
+    }
+    .ok()?;
+    match name {
+        "memory" => {
+            value["usage_percent"] = snapshot.memory.usage_percent().into();
+            value["swap_percent"] = snapshot.memory.swap_percent().into();
+        }
+        "disk" => {
+            if let Some(partitions) = value["partitions"].as_array_mut() {
+                for (value, partition) in partitions.iter_mut().zip(&snapshot.disk.partitions) {
+                    value["usage_percent"] = partition.usage_percent().into();
+                }
+            }
+        }
+        _ => {}
+    }
+    Some(value)
+}
+
+/// A validated rule from the `shared.rules` settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    condition: Condition,
+    message: String,
+    severity: WarningSeverity,
+    hysteresis: f64,
+    clear_after: Duration,
+}
+
+impl Rule {
+    pub fn from_settings(settings: &RuleSettings) -> Result<Self, String> {
+        let condition = Condition::parse(&settings.when)?;
+        let message = settings.message.trim();
+        if message.is_empty() {
+            return Err("message must not be empty".into());
+        }
+        if message.chars().count() > MAX_MESSAGE_CHARS {
+            return Err(format!(
+                "message must be at most {MAX_MESSAGE_CHARS} characters"
+            ));
+        }
+        if !settings.hysteresis.is_finite() || settings.hysteresis < 0.0 {
+            return Err("hysteresis must be a non-negative number".into());
+        }
+        if settings.hysteresis > 0.0 && !condition.operator.is_ordering() {
+            return Err("hysteresis needs a >, >=, <, or <= comparison".into());
+        }
+        let clear_after = match &settings.clear_after {
+            Some(text) => parse_duration(text)?,
+            None => Duration::ZERO,
+        };
+        Ok(Self {
+            condition,
+            message: message.to_string(),
+            severity: settings.severity.clone(),
+            hysteresis: settings.hysteresis,
+            clear_after,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RuleState {
+    rule: Rule,
+    /// When the comparison started holding while the rule was clear.
+    pending_since: Option<Instant>,
+    /// When the value came back past the hysteresis while raised.
+    clearing_since: Option<Instant>,
+    raised: bool,
+    /// The reading that raised or last kept the rule raised.
+    value: Option<Value>,
+}
+
+/// Every configured rule and how long each has held or cleared.
+#[derive(Debug, Clone, Default)]
+pub struct RuleEngine {
+    rules: Vec<RuleState>,
+}
+
+impl RuleEngine {
+    /// Rules that fail validation are skipped; `settings::load_shared` has
+    /// already dropped each one and reported why.
+    pub fn new(settings: &[RuleSettings]) -> Self {
+        Self {
+            rules: settings
+                .iter()
+                .filter_map(|rule| Rule::from_settings(rule).ok())
+                .map(|rule| RuleState {
+                    rule,
+                    pending_since: None,
+                    clearing_since: None,
+                    raised: false,
+                    value: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Evaluate every rule against the snapshot. A field with no reading,
+    /// such as a missing partition or an unread temperature, leaves a rule
+    /// as it was but restarts its `for` and `clear_after` timers.
+    pub fn observe(&mut self, snapshot: &SystemSnapshot, now: Instant) {
+        let mut sections: HashMap<&str, Option<Value>> = HashMap::new();
+        for state in &mut self.rules {
+            let condition = &state.rule.condition;
+            let section = sections
+                .entry(condition.section.as_str())
+                .or_insert_with(|| section_value(snapshot, &condition.section));
+            let value = section.as_ref().and_then(|value| condition.resolve(value));
+            let margin = if state.raised {
+                state.rule.hysteresis
+            } else {
+                0.0
+            };
+            let Some(holds) = value.and_then(|value| condition.holds(value, margin)) else {
+                state.pending_since = None;
+                state.clearing_since = None;
+                continue;
+            };
+            if state.raised {
+                if holds {
+                    state.clearing_since = None;
+                    state.value = value.cloned();
+                } else {
+                    let since = *state.clearing_since.get_or_insert(now);
+                    if now.duration_since(since) >= state.rule.clear_after {
+                        state.raised = false;
+                        state.clearing_since = None;
+                        state.value = None;
+                    }
+                }
+            } else if holds {
+                let since = *state.pending_since.get_or_insert(now);
+                if now.duration_since(since) >= condition.hold {
+                    state.raised = true;
+                    state.pending_since = None;
+                    state.value = value.cloned();
+                }
+            } else {
+                state.pending_since = None;
+            }
+        }
+    }
+
+    /// One warning per raised rule. `{{value}}` in a message is replaced
+    /// with the reading that keeps it raised.
+    pub fn warnings(&self) -> Vec<DiagnosticWarning> {
+        self.rules
+            .iter()
+            .filter(|state| state.raised)
+            .map(|state| DiagnosticWarning {
+                source: WARNING_SOURCE.into(),
+                message: state
+                    .rule
+                    .message
+                    .replace("{{value}}", &display(state.value.as_ref())),
+                severity: state.rule.severity.clone(),
+            })
+            .collect()
+    }
+}
+
+fn display(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::Number(number)) => match number.as_f64() {
+            Some(value) if value.fract() == 0.0 => format!("{value:.0}"),
+            Some(value) => format!("{value:.1}"),
+            None => number.to_string(),
+        },
+        Some(Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+        None => "unknown".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::disk::{DiskType, PartitionInfo};
+
+    fn rule(when: &str, hysteresis: f64, clear_after: Option<&str>) -> RuleSettings {
+        RuleSettings {
+            when: when.into(),
+            message: "Rule raised at {{value}}".into(),
+            severity: WarningSeverity::Warning,
+            hysteresis,
+            clear_after: clear_after.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn conditions_parse_paths_selectors_values_and_durations() {
+        let condition = Condition::parse("disk.partitions[/].usage_percent > 95").expect("parse");
+        assert_eq!(condition.section, "disk");
+        assert_eq!(
+            condition.steps,
+            [
+                Step::Field("partitions".into()),
+                Step::Select("/".into()),
+                Step::Field("usage_percent".into()),
+            ]
+        );
+        assert_eq!(condition.hold, Duration::ZERO);
+
+        let condition =
+            Condition::parse("network_diag.internet.reachable == false for 60s").expect("parse");
+        assert_eq!(condition.literal, Literal::Bool(false));
+        assert_eq!(condition.hold, Duration::from_secs(60));
+
+        let condition = Condition::parse("cpu.total_usage>=90 for 5m").expect("parse");
+        assert_eq!(condition.operator, Operator::GreaterOrEqual);
+        assert_eq!(condition.hold, Duration::from_secs(300));
+
+        for invalid in [
+            "cpu.total_usage 90",
+            "kernel.load > 1",
+            "cpu.total_usage > hot",
+            "thermals.cpu_temp > \"85\"",
+            "cpu.total_usage > 90 for ever",
+            "cpu.total_usage > 90 for 2d",
+            "disk.partitions[/.usage_percent > 95",
+            "cpu. > 90",
+        ] {
+            assert!(Condition::parse(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn rules_raise_after_holding_and_clear_past_hysteresis() {
+        let mut snapshot = SystemSnapshot::default();
+        let mut engine = RuleEngine::new(&[rule("cpu.total_usage > 90 for 5m", 5.0, Some("30s"))]);
+        let start = Instant::now();
+        let at = |seconds| start + Duration::from_secs(seconds);
+
+        snapshot.cpu.total_usage = 95.0;
+        engine.observe(&snapshot, at(0));
+        engine.observe(&snapshot, at(299));
+        assert!(engine.warnings().is_empty());
+        engine.observe(&snapshot, at(300));
+        let warnings = engine.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].source, WARNING_SOURCE);
+        assert_eq!(warnings[0].message, "Rule raised at 95");
+
+        // Inside the hysteresis band the rule stays raised indefinitely.
+        snapshot.cpu.total_usage = 88.0;
+        engine.observe(&snapshot, at(400));
+        engine.observe(&snapshot, at(1000));
+        assert_eq!(engine.warnings()[0].message, "Rule raised at 88");
+
+        // Past the band it clears once it has stayed there for clear_after.
+        snapshot.cpu.total_usage = 80.0;
+        engine.observe(&snapshot, at(1001));
+        assert_eq!(engine.warnings().len(), 1);
+        engine.observe(&snapshot, at(1031));
+        assert!(engine.warnings().is_empty());
+
+        // A dip resets the hold timer.
+        snapshot.cpu.total_usage = 95.0;
+        engine.observe(&snapshot, at(1100));
+        snapshot.cpu.total_usage = 50.0;
+        engine.observe(&snapshot, at(1200));
+        snapshot.cpu.total_usage = 95.0;
+        engine.observe(&snapshot, at(1300));
+        engine.observe(&snapshot, at(1500));
+        assert!(engine.warnings().is_empty());
+    }
+
+    #[test]
+    fn selectors_and_derived_percentages_resolve_against_the_snapshot() {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.disk.partitions = vec![PartitionInfo {
+            name: "sda1".into(),
+            mount_point: "/".into(),
+            filesystem: "ext4".into(),
+            total_bytes: 100,
+            used_bytes: 97,
+            available_bytes: 3,
+            is_removable: false,
+            disk_type: DiskType::Ssd,
+        }];
+        snapshot.thermals.cpu_temp = None;
+        let mut engine = RuleEngine::new(&[
+            rule("disk.partitions[/].usage_percent > 95", 0.0, None),
+            rule("disk.partitions[sda1].disk_type == \"ssd\"", 0.0, None),
+            rule("disk.partitions[/data].usage_percent > 95", 0.0, None),
+            rule("thermals.cpu_temp > 85", 0.0, None),
+        ]);
+        engine.observe(&snapshot, Instant::now());
+        let messages: Vec<_> = engine
+            .warnings()
+            .into_iter()
+            .map(|warning| warning.message)
+            .collect();
+        assert_eq!(messages, ["Rule raised at 97", "Rule raised at ssd"]);
+    }
+
+    #[test]
+    fn skipped_rules_stay_among_the_rule_warnings() {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.apply_settings(&crate::settings::SharedSettings {
+            rules: vec![rule("cpu.total_usage >= 0", 0.0, None)],
+            problems: vec![crate::settings::SettingsProblem {
+                source: WARNING_SOURCE,
+                message: "Skipping rule 2 (`cpu.total_usage >> 90`): bad".into(),
+            }],
+            ..Default::default()
+        });
+        for _ in 0..2 {
+            snapshot.refresh_rule_warnings();
+            let messages: Vec<_> = snapshot
+                .warnings
+                .iter()
+                .filter(|warning| warning.source == WARNING_SOURCE)
+                .map(|warning| warning.message.as_str())
+                .collect();
+            assert_eq!(
+                messages,
+                [
+                    "Rule raised at 0",
+                    "Skipping rule 2 (`cpu.total_usage >> 90`): bad"
+                ]
+            );
+        }
+    }
+}
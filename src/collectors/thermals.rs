@@ -1,11 +1,12 @@
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use sysinfo::Components;
 
 use super::{DiagnosticWarning, WarningSeverity};
 use crate::collectors::gpu::GpuData;
 use crate::observation::Observation;
 
-#[derive(Debug, Clone, Default, Serialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct ThermalData {
     pub cpu_temp: Option<f64>,
     pub gpu_temp: Option<f64>,
@@ -20,7 +21,7 @@ pub struct ThermalData {
     pub battery_status: Observation,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SensorInfo {
     pub label: String,
     pub temperature: f64,
@@ -29,14 +30,14 @@ pub struct SensorInfo {
     pub source: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FanInfo {
     pub label: String,
     pub rpm: u64,
     pub source: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SensorKind {
     Cpu,
@@ -44,19 +45,29 @@ pub enum SensorKind {
     Other,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BatteryInfo {
     pub percent: f64,
     pub is_charging: bool,
     pub is_on_ac: bool,
     pub time_remaining: Option<String>,
     pub full_charged_capacity_mwh: Option<u64>,
+    pub design_capacity_mwh: Option<u64>,
     pub design_voltage_mv: Option<u64>,
     pub cycle_count: Option<u32>,
     pub provider_status: Option<String>,
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+impl BatteryInfo {
+    /// Percentage of the design capacity a full charge no longer holds.
+    pub fn wear_percent(&self) -> Option<f64> {
+        let full = self.full_charged_capacity_mwh? as f64;
+        let design = self.design_capacity_mwh.filter(|design| *design > 0)? as f64;
+        Some((100.0 - full / design * 100.0).clamp(0.0, 100.0))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum PowerSource {
     #[default]
@@ -297,9 +308,6 @@ fn gpu_temperature_sensor(gpu: &GpuData) -> Option<(f64, String, String)> {
 
 // --- WMI fallback for Windows ---
 
-#[cfg(windows)]
-use serde::Deserialize;
-
 #[cfg(windows)]
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
@@ -864,6 +872,13 @@ struct WmiFullChargedCapacity {
     full_charged_capacity: Option<u64>,
 }
 
+#[cfg(windows)]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "PascalCase")]
+struct WmiBatteryStaticData {
+    designed_capacity: Option<u64>,
+}
+
 #[cfg(windows)]
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "PascalCase")]
@@ -930,7 +945,8 @@ fn collect_battery_windows() -> (Option<BatteryInfo>, Observation) {
         .estimated_run_time
         .filter(|minutes| *minutes != 71_582_788)
         .map(|minutes| format!("{minutes} minutes"));
-    let (full_charged_capacity_mwh, cycle_count) = collect_battery_details_windows();
+    let (full_charged_capacity_mwh, design_capacity_mwh, cycle_count) =
+        collect_battery_details_windows();
 
     (
         Some(BatteryInfo {
@@ -939,6 +955,7 @@ fn collect_battery_windows() -> (Option<BatteryInfo>, Observation) {
             is_on_ac,
             time_remaining,
             full_charged_capacity_mwh,
+            design_capacity_mwh,
             design_voltage_mv: row.design_voltage,
             cycle_count,
             provider_status: row.status,
@@ -948,14 +965,14 @@ fn collect_battery_windows() -> (Option<BatteryInfo>, Observation) {
 }
 
 #[cfg(windows)]
-fn collect_battery_details_windows() -> (Option<u64>, Option<u32>) {
+fn collect_battery_details_windows() -> (Option<u64>, Option<u64>, Option<u32>) {
     use wmi::{COMLibrary, WMIConnection};
 
     let Ok(com) = COMLibrary::new() else {
-        return (None, None);
+        return (None, None, None);
     };
     let Ok(connection) = WMIConnection::with_namespace_path("root\\WMI", com) else {
-        return (None, None);
+        return (None, None, None);
     };
     let capacity = connection
         .raw_query::<WmiFullChargedCapacity>(
@@ -963,11 +980,15 @@ fn collect_battery_details_windows() -> (Option<u64>, Option<u32>) {
         )
         .ok()
         .and_then(|rows| rows.into_iter().find_map(|row| row.full_charged_capacity));
+    let design = connection
+        .raw_query::<WmiBatteryStaticData>("SELECT DesignedCapacity FROM BatteryStaticData")
+        .ok()
+        .and_then(|rows| rows.into_iter().find_map(|row| row.designed_capacity));
     let cycles = connection
         .raw_query::<WmiBatteryCycleCount>("SELECT CycleCount FROM BatteryCycleCount")
         .ok()
         .and_then(|rows| rows.into_iter().find_map(|row| row.cycle_count));
-    (capacity, cycles)
+    (capacity, design, cycles)
 }
 
 #[cfg(windows)]
@@ -0,0 +1,287 @@
+//! Levels measured against the configured health thresholds.
+//!
+//! Memory, partition usage, temperatures, and interfaces with a configured
+//! limit are checked after every refresh that updates them, and each one past
+//! its warning level is reported under one warning source. Processor and
+//! graphics load are rated against their thresholds where they are shown and
+//! in overall health, but raise no warning: a single busy second would raise
+//! and clear one.
+
+use super::thermals::SensorKind;
+use super::{DiagnosticWarning, SystemSnapshot, WarningSeverity};
+use crate::health::Subsystem;
+use crate::settings::Limits;
+use crate::types::HealthStatus;
+
+/// Warning source for threshold reports, replaced wholesale on every pass.
+pub const WARNING_SOURCE: &str = "Thresholds";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Percent,
+    Celsius,
+    Mbits,
+}
+
+impl Unit {
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Self::Percent => "%",
+            Self::Celsius => "°C",
+            Self::Mbits => " Mbit/s",
+        }
+    }
+}
+
+/// One level and the limits it is rated against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    /// How warnings name the level, such as `Partition /`.
+    pub subject: String,
+    pub value: f64,
+    pub unit: Unit,
+    pub limits: Limits,
+    /// Where the level counts in overall health.
+    pub subsystem: Subsystem,
+}
+
+impl Check {
+    pub fn status(&self) -> HealthStatus {
+        HealthStatus::from_limits(self.value, self.limits)
+    }
+
+    /// The status and the limit it was reached at, once past warning.
+    pub fn reached(&self) -> Option<(HealthStatus, f64)> {
+        match self.status() {
+            HealthStatus::Critical => Some((HealthStatus::Critical, self.limits.critical)),
+            HealthStatus::Warning => Some((HealthStatus::Warning, self.limits.warning)),
+            HealthStatus::Good | HealthStatus::Unknown => None,
+        }
+    }
+
+    /// Such as `Partition / is at 92% (critical from 90%)`, once past
+    /// warning.
+    pub fn message(&self) -> Option<String> {
+        let (status, limit) = self.reached()?;
+        let level = if status == HealthStatus::Critical {
+            "critical"
+        } else {
+            "warning"
+        };
+        let suffix = self.unit.suffix();
+        Some(format!(
+            "{} is at {}{suffix} ({level} from {}{suffix})",
+            self.subject,
+            number(self.value),
+            number(limit)
+        ))
+    }
+
+    fn warning(&self) -> Option<DiagnosticWarning> {
+        let severity = match self.reached()?.0 {
+            HealthStatus::Critical => WarningSeverity::Error,
+            _ => WarningSeverity::Warning,
+        };
+        Some(DiagnosticWarning {
+            source: WARNING_SOURCE.into(),
+            message: self.message()?,
+            severity,
+        })
+    }
+}
+
+/// Whole numbers without a fraction, anything else to one decimal place.
+fn number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+/// Every level the snapshot has a reading for. CPU and GPU sensors are
+/// covered by the processor and graphics temperatures unless a sensor has
+/// its own entry.
+pub fn checks(snapshot: &SystemSnapshot) -> Vec<Check> {
+    let thresholds = &snapshot.thresholds;
+    let mut checks = Vec::new();
+    let mut push = |subject: String, value: f64, unit, limits, subsystem| {
+        checks.push(Check {
+            subject,
+            value,
+            unit,
+            limits,
+            subsystem,
+        })
+    };
+
+    if snapshot.memory.total_bytes > 0 {
+        push(
+            "Memory".into(),
+            snapshot.memory.usage_percent(),
+            Unit::Percent,
+            thresholds.memory,
+            Subsystem::Memory,
+        );
+    }
+    for partition in &snapshot.disk.partitions {
+        if partition.total_bytes == 0 {
+            continue;
+        }
+        let name = if partition.mount_point.is_empty() {
+            &partition.name
+        } else {
+            &partition.mount_point
+        };
+        push(
+            format!("Partition {name}"),
+            partition.usage_percent(),
+            Unit::Percent,
+            thresholds.disk_for(partition),
+            Subsystem::Storage,
+        );
+    }
+    if let Some(temp) = snapshot.thermals.cpu_temp {
+        push(
+            "Processor temperature".into(),
+            temp,
+            Unit::Celsius,
+            thresholds.cpu_temperature,
+            Subsystem::Thermals,
+        );
+    }
+    if let Some(temp) = snapshot.thermals.gpu_temp {
+        push(
+            "Graphics temperature".into(),
+            temp,
+            Unit::Celsius,
+            thresholds.gpu_temperature,
+            Subsystem::Thermals,
+        );
+    }
+    for sensor in &snapshot.thermals.sensors {
+        if sensor.kind != SensorKind::Other && !thresholds.sensors.contains_key(&sensor.label) {
+            continue;
+        }
+        push(
+            format!("Sensor {}", sensor.label),
+            sensor.temperature,
+            Unit::Celsius,
+            thresholds.sensor_for(&sensor.label),
+            Subsystem::Thermals,
+        );
+    }
+    for interface in &snapshot.network.interfaces {
+        if let Some(limits) = thresholds.interface_for(&interface.name) {
+            let bits = (interface.download_rate + interface.upload_rate) as f64 * 8.0;
+            push(
+                format!("Interface {}", interface.name),
+                bits / 1_000_000.0,
+                Unit::Mbits,
+                limits,
+                Subsystem::Connectivity,
+            );
+        }
+    }
+    checks
+}
+
+pub fn warnings(snapshot: &SystemSnapshot) -> Vec<DiagnosticWarning> {
+    checks(snapshot).iter().filter_map(Check::warning).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::disk::{DiskType, PartitionInfo};
+    use crate::collectors::network::InterfaceInfo;
+    use crate::collectors::thermals::SensorInfo;
+
+    fn partition(mount_point: &str, used_percent: u64) -> PartitionInfo {
+        PartitionInfo {
+            name: format!("disk{used_percent}"),
+            mount_point: mount_point.into(),
+            filesystem: "ext4".into(),
+            total_bytes: 100,
+            used_bytes: used_percent,
+            available_bytes: 100 - used_percent,
+            is_removable: false,
+            disk_type: DiskType::Ssd,
+        }
+    }
+
+    fn sensor(label: &str, temperature: f64, kind: SensorKind) -> SensorInfo {
+        SensorInfo {
+            label: label.into(),
+            temperature,
+            critical: None,
+            kind,
+            source: "test".into(),
+        }
+    }
+
+    #[test]
+    fn overrides_take_precedence_over_each_metric_default() {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.memory.total_bytes = 100;
+        snapshot.memory.used_bytes = 80;
+        snapshot.disk.partitions = vec![partition("/", 92), partition("/scratch", 92)];
+        snapshot.thermals.cpu_temp = None;
+        snapshot.thermals.gpu_temp = None;
+        snapshot.thermals.sensors = vec![
+            sensor("nvme Composite", 72.0, SensorKind::Other),
+            sensor("Package id 0", 99.0, SensorKind::Cpu),
+            sensor("edge", 80.0, SensorKind::Gpu),
+        ];
+        snapshot.network.interfaces = vec![InterfaceInfo {
+            name: "eth0".into(),
+            ip_addresses: Vec::new(),
+            mac_address: String::new(),
+            received_bytes: 0,
+            transmitted_bytes: 0,
+            download_rate: 100_000_000,
+            upload_rate: 25_000_000,
+            is_up: true,
+            operational_state: "up".into(),
+        }];
+        snapshot.thresholds.memory = Limits::new(85.0, 95.0);
+        snapshot
+            .thresholds
+            .disks
+            .insert("/scratch".into(), Limits::new(95.0, 99.5));
+        snapshot
+            .thresholds
+            .sensors
+            .insert("edge".into(), Limits::new(70.0, 75.0));
+        snapshot
+            .thresholds
+            .interfaces
+            .insert("eth0".into(), Limits::new(500.0, 900.0));
+
+        let messages: Vec<_> = warnings(&snapshot)
+            .into_iter()
+            .map(|warning| (warning.severity, warning.message))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                (
+                    WarningSeverity::Error,
+                    "Partition / is at 92% (critical from 90%)".to_string()
+                ),
+                (
+                    WarningSeverity::Warning,
+                    "Sensor nvme Composite is at 72°C (warning from 70°C)".into()
+                ),
+                (
+                    WarningSeverity::Error,
+                    "Sensor edge is at 80°C (critical from 75°C)".into()
+                ),
+                (
+                    WarningSeverity::Error,
+                    "Interface eth0 is at 1000 Mbit/s (critical from 900 Mbit/s)".into()
+                ),
+            ]
+        );
+    }
+}
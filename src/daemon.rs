@@ -0,0 +1,733 @@
+//! `sd300 daemon`: the desktop engine's topics for local tools.
+//!
+//! The daemon collects at the TUI's cadence through [`LiveCollector`] and
+//! publishes the same versioned envelopes the desktop engine does, one per
+//! topic. A topic's sequence advances only when its data or the warning list
+//! changes, so a client can tell fresh data from a repeat without diffing.
+//!
+//! Clients speak line-delimited JSON over a Unix socket, or a named pipe on
+//! Windows, that only the account running the daemon can open:
+//!
+//! * `{"op":"list"}` answers with every topic and its latest sequence (0
+//!   until first published).
+//! * `{"op":"get","topic":"fast"}` answers with the topic's latest envelope.
+//! * `{"op":"subscribe","topics":["fast","warnings"]}` sends the latest
+//!   envelope of each topic (all topics when `topics` is omitted) and then
+//!   every new one until the client disconnects. A subscription takes over
+//!   the connection; a client that falls behind is sent the latest envelopes
+//!   again, so it may see a sequence twice but never misses the newest.
+//!
+//! Errors come back as `{"error":"..."}` and leave the connection open.
+//! Envelopes carry the redacted report's values unless the daemon was started
+//! with `--include-sensitive`.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::broadcast;
+
+use crate::cli::DaemonArgs;
+use crate::collectors::DiagnosticWarning;
+use crate::error::{AppError, Result};
+use crate::live::LiveCollector;
+use crate::record::unix_ms;
+use crate::report::DiagnosticReport;
+use crate::topics::{
+    self, FastProjection, MediumProjection, SlowProjection, StaticProjection, Topic,
+};
+use crate::warning_log::WarningForwarder;
+use crate::webhook::Notifier;
+
+/// Requests longer than this close the connection.
+const MAX_REQUEST_BYTES: usize = 4 * 1024;
+/// Envelopes a subscriber may fall behind by before it is resynchronized.
+const UPDATE_BACKLOG: usize = 64;
+
+/// One publication of a topic: its sequence and serialized envelope.
+#[derive(Debug, Clone)]
+pub struct Published {
+    pub topic: Topic,
+    pub sequence: u64,
+    pub envelope: Arc<[u8]>,
+}
+
+struct Slot {
+    fingerprint: Vec<u8>,
+    published: Published,
+}
+
+/// The latest envelope of every topic.
+#[derive(Default)]
+pub struct TopicBoard {
+    slots: [Option<Slot>; Topic::ALL.len()],
+}
+
+impl TopicBoard {
+    /// Publish each topic of `report` whose data or warnings changed since
+    /// the last call, returning the new publications.
+    pub fn publish(
+        &mut self,
+        report: &DiagnosticReport,
+        captured_unix_ms: u64,
+    ) -> Result<Vec<Published>> {
+        let memory = &report.memory;
+        let network = &report.network;
+        let diagnostics = &report.network_diagnostics;
+        let warnings = &report.warnings;
+        let at = captured_unix_ms;
+        let published = [
+            self.publish_topic(
+                Topic::Static,
+                &StaticProjection {
+                    system: &report.system,
+                    displays: &report.displays,
+                    memory_modules: &memory.modules,
+                    memory_module_status: &memory.module_status,
+                    network_adapters: &network.adapters,
+                    network_adapter_status: &network.adapter_status,
+                },
+                warnings,
+                at,
+            )?,
+            self.publish_topic(
+                Topic::Fast,
+                &FastProjection {
+                    cpu: &report.cpu,
+                    memory,
+                    network,
+                    processes: &report.processes,
+                },
+                warnings,
+                at,
+            )?,
+            self.publish_topic(
+                Topic::Medium,
+                &MediumProjection {
+                    active_connections: &diagnostics.active_connections,
+                    listening_ports: &diagnostics.listening_ports,
+                },
+                warnings,
+                at,
+            )?,
+            self.publish_topic(
+                Topic::Slow,
+                &SlowProjection {
+                    disk: &report.disk,
+                    gpu: &report.gpu,
+                    thermals: &report.thermals,
+                },
+                warnings,
+                at,
+            )?,
+            self.publish_topic(Topic::Diagnostics, diagnostics, warnings, at)?,
+            self.publish_topic(Topic::Health, &report.disk_health, warnings, at)?,
+            self.publish_topic(Topic::Drivers, &report.drivers, warnings, at)?,
+            self.publish_topic(Topic::Warnings, warnings, warnings, at)?,
+            self.publish_topic(Topic::Capabilities, &report.capabilities, warnings, at)?,
+        ];
+        Ok(published.into_iter().flatten().collect())
+    }
+
+    fn publish_topic<T: Serialize + ?Sized>(
+        &mut self,
+        topic: Topic,
+        data: &T,
+        warnings: &[DiagnosticWarning],
+        captured_unix_ms: u64,
+    ) -> Result<Option<Published>> {
+        let fingerprint = serde_json::to_vec(&(data, warnings)).map_err(serialization)?;
+        let slot = &mut self.slots[topic as usize];
+        if slot
+            .as_ref()
+            .is_some_and(|slot| slot.fingerprint == fingerprint)
+        {
+            return Ok(None);
+        }
+        let sequence = slot
+            .as_ref()
+            .map_or(0, |slot| slot.published.sequence)
+            .saturating_add(1);
+        let envelope = topics::envelope(topic, sequence, captured_unix_ms, data, warnings)
+            .map_err(serialization)?;
+        let published = Published {
+            topic,
+            sequence,
+            envelope: envelope.into(),
+        };
+        *slot = Some(Slot {
+            fingerprint,
+            published: published.clone(),
+        });
+        Ok(Some(published))
+    }
+
+    pub fn latest(&self, topic: Topic) -> Option<&Published> {
+        self.slots[topic as usize]
+            .as_ref()
+            .map(|slot| &slot.published)
+    }
+}
+
+fn serialization(error: serde_json::Error) -> AppError {
+    AppError::platform(format!("topic serialization failed: {error}"))
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+enum Request {
+    List,
+    Get { topic: String },
+    Subscribe { topics: Option<Vec<String>> },
+}
+
+#[derive(Serialize)]
+struct TopicSequence {
+    topic: &'static str,
+    sequence: u64,
+}
+
+#[derive(Debug, PartialEq)]
+enum Answer {
+    Line(Vec<u8>),
+    Subscribe(Vec<Topic>),
+}
+
+fn answer(board: &TopicBoard, request: &str) -> Answer {
+    let request = match serde_json::from_str::<Request>(request) {
+        Ok(request) => request,
+        Err(error) => return error_line(format!("invalid request: {error}")),
+    };
+    match request {
+        Request::List => {
+            let topics: Vec<_> = Topic::ALL
+                .into_iter()
+                .map(|topic| TopicSequence {
+                    topic: topic.name(),
+                    sequence: board.latest(topic).map_or(0, |latest| latest.sequence),
+                })
+                .collect();
+            Answer::Line(json!({ "topics": topics }).to_string().into_bytes())
+        }
+        Request::Get { topic } => match parse_topic(&topic) {
+            Ok(topic) => match board.latest(topic) {
+                Some(latest) => Answer::Line(latest.envelope.to_vec()),
+                None => error_line(format!("{} has not been published yet", topic.name())),
+            },
+            Err(answer) => answer,
+        },
+        Request::Subscribe { topics: None } => Answer::Subscribe(Topic::ALL.to_vec()),
+        Request::Subscribe {
+            topics: Some(names),
+        } => {
+            let mut topics = Vec::new();
+            for name in names {
+                match parse_topic(&name) {
+                    Ok(topic) if !topics.contains(&topic) => topics.push(topic),
+                    Ok(_) => {}
+                    Err(answer) => return answer,
+                }
+            }
+            if topics.is_empty() {
+                return error_line("subscribe needs at least one topic".into());
+            }
+            Answer::Subscribe(topics)
+        }
+    }
+}
+
+fn parse_topic(name: &str) -> std::result::Result<Topic, Answer> {
+    Topic::from_name(name).ok_or_else(|| error_line(format!("unknown topic {name:?}")))
+}
+
+fn error_line(message: String) -> Answer {
+    Answer::Line(json!({ "error": message }).to_string().into_bytes())
+}
+
+/// State shared between the collector loop and client connections.
+pub struct Shared {
+    board: RwLock<TopicBoard>,
+    updates: broadcast::Sender<Published>,
+}
+
+impl Default for Shared {
+    fn default() -> Self {
+        Self {
+            board: RwLock::default(),
+            updates: broadcast::channel(UPDATE_BACKLOG).0,
+        }
+    }
+}
+
+impl Shared {
+    pub fn publish(&self, report: &DiagnosticReport, captured_unix_ms: u64) -> Result<()> {
+        let published = self
+            .board
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .publish(report, captured_unix_ms)?;
+        for update in published {
+            // No subscribers is not an error.
+            let _ = self.updates.send(update);
+        }
+        Ok(())
+    }
+
+    fn latest(&self, topics: &[Topic]) -> Vec<Arc<[u8]>> {
+        let board = self.board.read().unwrap_or_else(PoisonError::into_inner);
+        topics
+            .iter()
+            .filter_map(|topic| board.latest(*topic))
+            .map(|latest| Arc::clone(&latest.envelope))
+            .collect()
+    }
+}
+
+async fn serve_connection<S: AsyncRead + AsyncWrite>(stream: S, shared: Arc<Shared>) {
+    let (read, mut write) = tokio::io::split(stream);
+    let mut reader = BufReader::new(read);
+    loop {
+        let mut request = String::new();
+        let limit = MAX_REQUEST_BYTES as u64 + 1;
+        match (&mut reader).take(limit).read_line(&mut request).await {
+            Ok(0) | Err(_) => return,
+            Ok(_) if !request.ends_with('\n') => return,
+            Ok(_) => {}
+        }
+        let request = request.trim();
+        if request.is_empty() {
+            continue;
+        }
+        let answer = answer(
+            &shared.board.read().unwrap_or_else(PoisonError::into_inner),
+            request,
+        );
+        match answer {
+            Answer::Line(line) => {
+                if write_line(&mut write, &line).await.is_err() {
+                    return;
+                }
+            }
+            Answer::Subscribe(topics) => {
+                let _ = stream_updates(&mut write, &shared, &topics).await;
+                return;
+            }
+        }
+    }
+}
+
+async fn stream_updates<W: AsyncWrite + Unpin>(
+    write: &mut W,
+    shared: &Shared,
+    topics: &[Topic],
+) -> std::io::Result<()> {
+    // Subscribe before reading the board so nothing published in between is
+    // lost; at worst the first update repeats an envelope already sent.
+    let mut updates = shared.updates.subscribe();
+    for envelope in shared.latest(topics) {
+        write_line(write, &envelope).await?;
+    }
+    loop {
+        match updates.recv().await {
+            Ok(update) if topics.contains(&update.topic) => {
+                write_line(write, &update.envelope).await?;
+            }
+            Ok(_) => {}
+            Err(broadcast::error::RecvError::Lagged(_)) => {
+                for envelope in shared.latest(topics) {
+                    write_line(write, &envelope).await?;
+                }
+            }
+            Err(broadcast::error::RecvError::Closed) => return Ok(()),
+        }
+    }
+}
+
+async fn write_line<W: AsyncWrite + Unpin>(write: &mut W, line: &[u8]) -> std::io::Result<()> {
+    write.write_all(line).await?;
+    write.write_all(b"\n").await?;
+    write.flush().await
+}
+
+pub async fn run(args: &DaemonArgs) -> Result<()> {
+    let address = match &args.socket {
+        Some(address) => address.clone(),
+        None => default_address()?,
+    };
+    let mut forwarder = WarningForwarder::from_args(&args.warning_log)?;
+    let mut notifier = Notifier::from_settings();
+    let shared = Arc::new(Shared::default());
+    let server = listen(&address, Arc::clone(&shared), args.socket.is_none())?;
+
+    let mut live = LiveCollector::start();
+    shared.publish(
+        &DiagnosticReport::from_snapshot(&live.snapshot, args.include_sensitive),
+        unix_ms(),
+    )?;
+    eprintln!(
+        "Serving topics on {}; press Ctrl+C to stop.",
+        address.display()
+    );
+
+    let interrupt = tokio::signal::ctrl_c();
+    tokio::pin!(interrupt);
+    let result = loop {
+        tokio::select! {
+            _ = live.refresh() => {
+                if let Some(forwarder) = &mut forwarder {
+                    forwarder.observe(&live.snapshot.warnings);
+                }
+                if let Some(notifier) = &mut notifier {
+                    notifier.observe(&live.snapshot);
+                }
+                let report = DiagnosticReport::from_snapshot(&live.snapshot, args.include_sensitive);
+                if let Err(error) = shared.publish(&report, unix_ms()) {
+                    break Err(error);
+                }
+            }
+            _ = &mut interrupt => break Ok(()),
+        }
+    };
+    server.abort();
+    #[cfg(unix)]
+    let _ = std::fs::remove_file(&address);
+    result
+}
+
+/// A per-user socket in the runtime directory, falling back to the settings
+/// directory where there is none.
+#[cfg(unix)]
+pub fn default_address() -> Result<PathBuf> {
+    if let Some(runtime) = std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(runtime).join("sd300").join("daemon.sock"));
+    }
+    crate::settings::settings_path()
+        .map_err(AppError::platform)?
+        .parent()
+        .map(|parent| parent.join("daemon.sock"))
+        .ok_or_else(|| AppError::platform("settings path had no parent directory"))
+}
+
+/// Pipe names are machine-wide, so the default carries the user name.
+#[cfg(windows)]
+pub fn default_address() -> Result<PathBuf> {
+    let user = std::env::var("USERNAME")
+        .map_err(|_| AppError::platform("USERNAME is unavailable for the current user"))?;
+    Ok(PathBuf::from(format!(r"\\.\pipe\sd300-daemon-{user}")))
+}
+
+#[cfg(unix)]
+fn listen(
+    path: &Path,
+    shared: Arc<Shared>,
+    create_parent: bool,
+) -> Result<tokio::task::JoinHandle<()>> {
+    if create_parent {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+            crate::settings::restrict_directory(parent).map_err(AppError::platform)?;
+        }
+    }
+    let listener = bind_socket(path)?;
+    Ok(tokio::spawn(accept_socket(listener, shared)))
+}
+
+/// Bind `path` with owner-only permissions, replacing a socket left behind
+/// by a daemon that did not shut down cleanly.
+#[cfg(unix)]
+fn bind_socket(path: &Path) -> Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            if std::os::unix::net::UnixStream::connect(path).is_ok() {
+                return Err(AppError::platform(format!(
+                    "another sd300 daemon is already serving {}",
+                    path.display()
+                )));
+            }
+            std::fs::remove_file(path)?;
+        }
+        Ok(_) => {
+            return Err(AppError::platform(format!(
+                "{} exists and is not a socket",
+                path.display()
+            )))
+        }
+        Err(_) => {}
+    }
+    let listener = tokio::net::UnixListener::bind(path).map_err(|error| {
+        AppError::platform(format!("cannot listen on {}: {error}", path.display()))
+    })?;
+    // Another account could connect between bind and chmod; the peer check
+    // in accept_socket still turns it away.
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+#[cfg(unix)]
+async fn accept_socket(listener: tokio::net::UnixListener, shared: Arc<Shared>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) if same_user(&stream) => {
+                tokio::spawn(serve_connection(stream, Arc::clone(&shared)));
+            }
+            Ok(_) => {}
+            // Usually descriptor exhaustion; give open connections a moment
+            // to close rather than spinning.
+            Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+        }
+    }
+}
+
+#[cfg(unix)]
+fn same_user(stream: &tokio::net::UnixStream) -> bool {
+    // SAFETY: geteuid has no preconditions and cannot fail.
+    let uid = unsafe { libc::geteuid() };
+    stream
+        .peer_cred()
+        .is_ok_and(|credentials| credentials.uid() == uid)
+}
+
+#[cfg(windows)]
+fn listen(
+    name: &Path,
+    shared: Arc<Shared>,
+    _create_parent: bool,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let name = name.as_os_str().to_owned();
+    let server = pipe::create(&name, true).map_err(|error| {
+        AppError::platform(format!(
+            "cannot serve {} (is another sd300 daemon running?): {error}",
+            name.to_string_lossy()
+        ))
+    })?;
+    Ok(tokio::spawn(accept_pipe(name, server, shared)))
+}
+
+#[cfg(windows)]
+async fn accept_pipe(
+    name: std::ffi::OsString,
+    mut server: tokio::net::windows::named_pipe::NamedPipeServer,
+    shared: Arc<Shared>,
+) {
+    loop {
+        let connected = server.connect().await;
+        // The next client needs a fresh instance before this one is handed off.
+        let client = server;
+        server = loop {
+            match pipe::create(&name, false) {
+                Ok(next) => break next,
+                Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+            }
+        };
+        if connected.is_ok() {
+            tokio::spawn(serve_connection(client, Arc::clone(&shared)));
+        }
+    }
+}
+
+#[cfg(windows)]
+mod pipe {
+    use std::ffi::{c_void, OsStr};
+
+    use tokio::net::windows::named_pipe::{NamedPipeServer, ServerOptions};
+    use windows::core::PCWSTR;
+    use windows::Win32::Foundation::{LocalFree, HLOCAL};
+    use windows::Win32::Security::Authorization::{
+        ConvertStringSecurityDescriptorToSecurityDescriptorW, SDDL_REVISION_1,
+    };
+    use windows::Win32::Security::{PSECURITY_DESCRIPTOR, SECURITY_ATTRIBUTES};
+
+    /// Full access for the pipe's owner, the account running the daemon, and
+    /// no one else.
+    const OWNER_ONLY: &str = "D:P(A;;GA;;;OW)";
+
+    pub fn create(name: &OsStr, first: bool) -> std::io::Result<NamedPipeServer> {
+        let sddl: Vec<u16> = OWNER_ONLY.encode_utf16().chain(Some(0)).collect();
+        let mut descriptor = PSECURITY_DESCRIPTOR(std::ptr::null_mut());
+        // SAFETY: `sddl` is NUL-terminated and outlives the call; the
+        // descriptor it allocates is freed below.
+        unsafe {
+            ConvertStringSecurityDescriptorToSecurityDescriptorW(
+                PCWSTR(sddl.as_ptr()),
+                SDDL_REVISION_1,
+                &mut descriptor,
+                None,
+            )
+        }
+        .map_err(|error| std::io::Error::other(error.to_string()))?;
+        let mut attributes = SECURITY_ATTRIBUTES {
+            nLength: std::mem::size_of::<SECURITY_ATTRIBUTES>() as u32,
+            lpSecurityDescriptor: descriptor.0,
+            bInheritHandle: false.into(),
+        };
+        // SAFETY: `attributes` points at a valid descriptor for the call.
+        let server = unsafe {
+            ServerOptions::new()
+                .first_pipe_instance(first)
+                .reject_remote_clients(true)
+                .create_with_security_attributes_raw(
+                    name,
+                    &mut attributes as *mut SECURITY_ATTRIBUTES as *mut c_void,
+                )
+        };
+        // SAFETY: the descriptor was allocated by LocalAlloc above.
+        unsafe { LocalFree(Some(HLOCAL(descriptor.0))) };
+        server
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::SystemSnapshot;
+    use serde_json::Value;
+
+    fn report(cpu: f32) -> DiagnosticReport {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.system.hostname = "lab-07".into();
+        snapshot.cpu.total_usage = cpu;
+        DiagnosticReport::from_snapshot(&snapshot, false)
+    }
+
+    fn parse(bytes: &[u8]) -> Value {
+        serde_json::from_slice(bytes).expect("valid JSON line")
+    }
+
+    #[test]
+    fn topics_are_republished_only_when_they_change() {
+        let mut board = TopicBoard::default();
+        let first = board.publish(&report(10.0), 1_000).unwrap();
+        assert_eq!(first.len(), Topic::ALL.len());
+        assert!(first.iter().all(|published| published.sequence == 1));
+
+        assert!(board.publish(&report(10.0), 2_000).unwrap().is_empty());
+
+        let changed = board.publish(&report(20.0), 3_000).unwrap();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].topic, Topic::Fast);
+        let envelope = parse(&changed[0].envelope);
+        assert_eq!(envelope["topic"], "fast");
+        assert_eq!(envelope["sequence"], 2);
+        assert_eq!(envelope["captured_unix_ms"], 3_000);
+        assert_eq!(envelope["schema_version"], topics::SCHEMA_VERSION);
+        assert_eq!(envelope["data"]["cpu"]["total_usage"], 20.0);
+
+        let system = parse(&board.latest(Topic::Static).unwrap().envelope);
+        assert_eq!(system["data"]["system"]["hostname"], "[redacted]");
+        assert_eq!(system["sequence"], 1);
+    }
+
+    #[test]
+    fn answers_list_get_and_bad_requests() {
+        let mut board = TopicBoard::default();
+        let Answer::Line(line) = answer(&board, r#"{"op":"get","topic":"fast"}"#) else {
+            panic!("expected a line");
+        };
+        assert!(parse(&line)["error"]
+            .as_str()
+            .unwrap()
+            .contains("not been published"));
+
+        board.publish(&report(10.0), 1_000).unwrap();
+        let Answer::Line(line) = answer(&board, r#"{"op":"list"}"#) else {
+            panic!("expected a line");
+        };
+        let list = parse(&line);
+        assert_eq!(list["topics"].as_array().unwrap().len(), Topic::ALL.len());
+        assert_eq!(
+            list["topics"][0],
+            json!({ "topic": "static", "sequence": 1 })
+        );
+
+        let Answer::Line(line) = answer(&board, r#"{"op":"get","topic":"warnings"}"#) else {
+            panic!("expected a line");
+        };
+        assert_eq!(parse(&line)["topic"], "warnings");
+
+        assert_eq!(
+            answer(
+                &board,
+                r#"{"op":"subscribe","topics":["slow","fast","slow"]}"#
+            ),
+            Answer::Subscribe(vec![Topic::Slow, Topic::Fast])
+        );
+        assert_eq!(
+            answer(&board, r#"{"op":"subscribe"}"#),
+            Answer::Subscribe(Topic::ALL.to_vec())
+        );
+        for bad in [
+            r#"{"op":"get","topic":"processes"}"#,
+            r#"{"op":"subscribe","topics":[]}"#,
+            r#"{"op":"delete"}"#,
+            "not json",
+        ] {
+            let Answer::Line(line) = answer(&board, bad) else {
+                panic!("expected an error for {bad}");
+            };
+            assert!(parse(&line)["error"].is_string(), "{bad}");
+        }
+    }
+
+    #[tokio::test]
+    async fn subscribers_get_the_latest_envelopes_then_changes() {
+        let shared = Arc::new(Shared::default());
+        shared.publish(&report(10.0), 1_000).unwrap();
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        tokio::spawn(serve_connection(server, Arc::clone(&shared)));
+        let (read, mut write) = tokio::io::split(client);
+        let mut lines = BufReader::new(read).lines();
+
+        write
+            .write_all(b"{\"op\":\"get\",\"topic\":\"medium\"}\n")
+            .await
+            .unwrap();
+        let line = lines.next_line().await.unwrap().unwrap();
+        assert_eq!(parse(line.as_bytes())["topic"], "medium");
+
+        write
+            .write_all(b"{\"op\":\"subscribe\",\"topics\":[\"fast\"]}\n")
+            .await
+            .unwrap();
+        let line = lines.next_line().await.unwrap().unwrap();
+        assert_eq!(parse(line.as_bytes())["sequence"], 1);
+
+        shared.publish(&report(30.0), 2_000).unwrap();
+        let line = lines.next_line().await.unwrap().unwrap();
+        let envelope = parse(line.as_bytes());
+        assert_eq!(envelope["topic"], "fast");
+        assert_eq!(envelope["sequence"], 2);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn serves_an_owner_only_socket_and_refuses_a_second_daemon() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("daemon.sock");
+        let shared = Arc::new(Shared::default());
+        shared.publish(&report(10.0), 1_000).unwrap();
+        let server = listen(&path, Arc::clone(&shared), false).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let error = bind_socket(&path).unwrap_err();
+        assert!(error.to_string().contains("already serving"));
+
+        let mut stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        stream.write_all(b"{\"op\":\"list\"}\n").await.unwrap();
+        let mut lines = BufReader::new(stream).lines();
+        let line = lines.next_line().await.unwrap().unwrap();
+        assert_eq!(parse(line.as_bytes())["topics"][1]["topic"], "fast");
+
+        server.abort();
+        let _ = server.await;
+        // A socket left behind by a daemon that is gone is replaced.
+        drop(bind_socket(&path).unwrap());
+    }
+}
@@ -0,0 +1,679 @@
+//! `sd300 diff`: semantic changes between two `snapshot --json` reports.
+//!
+//! Reports are compared as JSON rather than as [`DiagnosticReport`] values so
+//! that a report written by an older or newer release still loads. A section
+//! missing from either report is skipped and named in the output instead of
+//! being read as "everything was removed".
+//!
+//! [`DiagnosticReport`]: crate::report::DiagnosticReport
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::cli::DiffArgs;
+use crate::error::{AppError, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Hardware,
+    Drivers,
+    Services,
+    ListeningPorts,
+    Capabilities,
+    Warnings,
+}
+
+impl Category {
+    fn title(&self) -> &'static str {
+        match self {
+            Self::Hardware => "Hardware",
+            Self::Drivers => "Drivers",
+            Self::Services => "Services",
+            Self::ListeningPorts => "Listening ports",
+            Self::Capabilities => "Capabilities",
+            Self::Warnings => "Warnings",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change {
+    pub category: Category,
+    pub kind: ChangeKind,
+    pub item: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+/// Identity of one compared report.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportIdentity {
+    pub schema_version: u64,
+    pub product_version: Option<String>,
+    pub target_os: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReportDiff {
+    pub before: ReportIdentity,
+    pub after: ReportIdentity,
+    /// Report paths that were absent from either side and not compared.
+    pub skipped: Vec<&'static str>,
+    pub changes: Vec<Change>,
+}
+
+/// Identity → item, for one list in a report.
+type Items<'a> = BTreeMap<String, &'a Value>;
+
+struct Differ<'a> {
+    before: &'a Value,
+    after: &'a Value,
+    skipped: Vec<&'static str>,
+    changes: Vec<Change>,
+}
+
+impl<'a> Differ<'a> {
+    /// The value at `pointer` in both reports, or `None` (recording the skip)
+    /// when either report lacks it.
+    fn both(&mut self, pointer: &'static str) -> Option<(&'a Value, &'a Value)> {
+        match (self.before.pointer(pointer), self.after.pointer(pointer)) {
+            (Some(before), Some(after)) => Some((before, after)),
+            _ => {
+                self.skipped.push(pointer);
+                None
+            }
+        }
+    }
+
+    fn lists(
+        &mut self,
+        pointer: &'static str,
+        identity: impl Fn(&Value) -> Option<String>,
+    ) -> Option<(Items<'a>, Items<'a>)> {
+        let (before, after) = self.both(pointer)?;
+        Some((items(before, &identity), items(after, &identity)))
+    }
+
+    fn push(
+        &mut self,
+        category: Category,
+        kind: ChangeKind,
+        item: String,
+        before: Option<String>,
+        after: Option<String>,
+    ) {
+        self.changes.push(Change {
+            category,
+            kind,
+            item,
+            before,
+            after,
+        });
+    }
+
+    /// Items present on only one side, described by `describe`.
+    fn presence(
+        &mut self,
+        category: Category,
+        before: &Items,
+        after: &Items,
+        describe: impl Fn(&Value) -> Option<String>,
+    ) {
+        for (key, item) in before {
+            if !after.contains_key(key) {
+                self.push(
+                    category,
+                    ChangeKind::Removed,
+                    key.clone(),
+                    describe(item),
+                    None,
+                );
+            }
+        }
+        for (key, item) in after {
+            if !before.contains_key(key) {
+                self.push(
+                    category,
+                    ChangeKind::Added,
+                    key.clone(),
+                    None,
+                    describe(item),
+                );
+            }
+        }
+    }
+
+    /// Items on both sides whose `value` differs. Unknown (`None`) values on
+    /// either side are not a change: a provider that was unavailable for one
+    /// run says nothing about the machine.
+    fn values(
+        &mut self,
+        category: Category,
+        before: &Items,
+        after: &Items,
+        label: &str,
+        value: impl Fn(&Value) -> Option<String>,
+    ) {
+        for (key, old) in before {
+            let Some(new) = after.get(key) else {
+                continue;
+            };
+            if let (Some(old), Some(new)) = (value(old), value(new)) {
+                if old != new {
+                    let item = if label.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{key} {label}")
+                    };
+                    self.push(category, ChangeKind::Changed, item, Some(old), Some(new));
+                }
+            }
+        }
+    }
+
+    fn scalar(
+        &mut self,
+        pointer: &'static str,
+        item: &str,
+        value: impl Fn(&Value) -> Option<String>,
+    ) {
+        let Some((before, after)) = self.both(pointer) else {
+            return;
+        };
+        if let (Some(old), Some(new)) = (value(before), value(after)) {
+            if old != new {
+                self.push(
+                    Category::Hardware,
+                    ChangeKind::Changed,
+                    item.into(),
+                    Some(old),
+                    Some(new),
+                );
+            }
+        }
+    }
+}
+
+pub fn diff_reports(before: &Value, after: &Value) -> Result<ReportDiff> {
+    let mut differ = Differ {
+        before,
+        after,
+        skipped: Vec::new(),
+        changes: Vec::new(),
+    };
+
+    // Hardware identity.
+    differ.scalar("/system/cpu_model", "CPU", text);
+    differ.scalar("/system/total_memory_bytes", "Installed memory", bytes);
+    differ.scalar("/system/manufacturer", "Manufacturer", text);
+    differ.scalar("/system/model", "Model", text);
+    differ.scalar("/system/bios_version", "BIOS version", text);
+
+    if let Some((old, new)) = differ.lists("/memory/modules", |module| {
+        field(module, "locator")
+            .or_else(|| field(module, "part_number"))
+            .map(|slot| format!("Memory module {slot}"))
+    }) {
+        let describe = |module: &Value| {
+            let capacity = bytes(module.get("capacity_bytes")?)?;
+            Some(match field(module, "memory_type") {
+                Some(kind) => format!("{capacity} {kind}"),
+                None => capacity,
+            })
+        };
+        differ.presence(Category::Hardware, &old, &new, describe);
+        differ.values(Category::Hardware, &old, &new, "", describe);
+    }
+    if let Some((old, new)) = differ.lists("/disk_health/drives", |drive| {
+        let model = field(drive, "model")?;
+        Some(match field(drive, "device_id") {
+            Some(device) => format!("Drive {model} ({device})"),
+            None => format!("Drive {model}"),
+        })
+    }) {
+        differ.presence(Category::Hardware, &old, &new, |drive| {
+            field(drive, "media_type")
+        });
+        differ.values(Category::Hardware, &old, &new, "firmware", |drive| {
+            field(drive, "firmware")
+        });
+    }
+    if let Some((old, new)) = differ.lists("/gpu/adapters", |adapter| {
+        field(adapter, "name").map(|name| format!("GPU {name}"))
+    }) {
+        differ.presence(Category::Hardware, &old, &new, |adapter| {
+            field(adapter, "driver_version")
+        });
+        differ.values(Category::Drivers, &old, &new, "driver", |adapter| {
+            field(adapter, "driver_version")
+        });
+    }
+    if let Some((old, new)) = differ.lists("/displays/displays", |display| {
+        field(display, "label").map(|label| format!("Display {label}"))
+    }) {
+        differ.presence(Category::Hardware, &old, &new, |display| {
+            field(display, "connection")
+        });
+    }
+    if let Some((old, new)) = differ.lists("/network/adapters", |adapter| {
+        (adapter.get("hardware_interface") != Some(&Value::Bool(false)))
+            .then(|| field(adapter, "name"))
+            .flatten()
+            .map(|name| format!("Network adapter {name}"))
+    }) {
+        differ.presence(Category::Hardware, &old, &new, |adapter| {
+            field(adapter, "description")
+        });
+    }
+    if let Some((old, new)) = differ.lists("/disk/partitions", |partition| {
+        field(partition, "mount_point").map(|mount| format!("Volume {mount}"))
+    }) {
+        differ.presence(Category::Hardware, &old, &new, |partition| {
+            let size = bytes(partition.get("total_bytes")?)?;
+            Some(match field(partition, "filesystem") {
+                Some(filesystem) => format!("{size} {filesystem}"),
+                None => size,
+            })
+        });
+    }
+
+    // Driver-backed devices, in whatever categories each report has.
+    if let Some((old, new)) = differ.both("/drivers") {
+        let (old, new) = (driver_devices(old), driver_devices(new));
+        differ.presence(Category::Hardware, &old, &new, |device| {
+            field(device, "driver_version")
+        });
+        differ.values(Category::Drivers, &old, &new, "driver", |device| {
+            field(device, "driver_version")
+        });
+        differ.values(Category::Drivers, &old, &new, "status", |device| {
+            field(device, "status")
+        });
+    }
+    if let Some((old, new)) = differ.lists("/drivers/services", |service| field(service, "name")) {
+        let state = |service: &Value| {
+            service
+                .get("is_running")?
+                .as_bool()
+                .map(|running| if running { "running" } else { "stopped" }.to_string())
+        };
+        differ.presence(Category::Services, &old, &new, state);
+        differ.values(Category::Services, &old, &new, "", state);
+    }
+
+    if let Some((old, new)) = differ.lists("/network_diagnostics/listening_ports", |port| {
+        Some(format!(
+            "{} {}:{}",
+            field(port, "protocol")?,
+            field(port, "local_addr")?,
+            port.get("local_port")?.as_u64()?
+        ))
+    }) {
+        differ.presence(Category::ListeningPorts, &old, &new, |port| {
+            field(port, "process_name")
+        });
+    }
+
+    if let Some((old, new)) = differ.lists("/capabilities", |capability| field(capability, "id")) {
+        let status = |capability: &Value| field(capability, "status");
+        differ.presence(Category::Capabilities, &old, &new, status);
+        differ.values(Category::Capabilities, &old, &new, "", status);
+    }
+
+    if let Some((old, new)) = differ.lists("/warnings", |warning| {
+        Some(format!(
+            "{}: {}",
+            field(warning, "source")?,
+            field(warning, "message")?
+        ))
+    }) {
+        let severity = |warning: &Value| field(warning, "severity");
+        differ.presence(Category::Warnings, &old, &new, severity);
+        differ.values(Category::Warnings, &old, &new, "", severity);
+    }
+
+    let mut changes = differ.changes;
+    changes.sort_by_key(|change| change.category);
+    Ok(ReportDiff {
+        before: identity(before)?,
+        after: identity(after)?,
+        skipped: differ.skipped,
+        changes,
+    })
+}
+
+pub fn run(args: &DiffArgs) -> Result<()> {
+    let before = read_report(&args.before)?;
+    let after = read_report(&args.after)?;
+    let diff = diff_reports(&before, &after)?;
+
+    let mut out = std::io::stdout().lock();
+    if args.json {
+        let text = serde_json::to_string_pretty(&diff)
+            .map_err(|error| AppError::platform(format!("JSON serialization failed: {error}")))?;
+        writeln!(out, "{text}")?;
+        return Ok(());
+    }
+
+    writeln!(
+        out,
+        "{} ({}) -> {} ({})",
+        args.before.display(),
+        describe_identity(&diff.before),
+        args.after.display(),
+        describe_identity(&diff.after)
+    )?;
+    if !diff.skipped.is_empty() {
+        writeln!(
+            out,
+            "Not compared (missing from one report): {}",
+            diff.skipped.join(", ")
+        )?;
+    }
+    if diff.changes.is_empty() {
+        writeln!(out, "No semantic changes.")?;
+        return Ok(());
+    }
+    let mut category = None;
+    for change in &diff.changes {
+        if category != Some(change.category) {
+            category = Some(change.category);
+            writeln!(out, "\n{}", change.category.title())?;
+        }
+        let detail = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(|value| format!(" ({value})"))
+                .unwrap_or_default()
+        };
+        let line = match change.kind {
+            ChangeKind::Added => format!("+ {}{}", change.item, detail(&change.after)),
+            ChangeKind::Removed => format!("- {}{}", change.item, detail(&change.before)),
+            ChangeKind::Changed => format!(
+                "~ {}: {} -> {}",
+                change.item,
+                change.before.as_deref().unwrap_or("unknown"),
+                change.after.as_deref().unwrap_or("unknown")
+            ),
+        };
+        writeln!(out, "  {line}")?;
+    }
+    Ok(())
+}
+
+fn read_report(path: &Path) -> Result<Value> {
+    let bytes = std::fs::read(path)
+        .map_err(|error| AppError::platform(format!("cannot read {}: {error}", path.display())))?;
+    serde_json::from_slice(&bytes).map_err(|error| {
+        AppError::platform(format!(
+            "{} is not a JSON diagnostic report: {error}",
+            path.display()
+        ))
+    })
+}
+
+fn identity(report: &Value) -> Result<ReportIdentity> {
+    let schema_version = report
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| {
+            AppError::platform("not an SD-300 diagnostic report: missing schema_version")
+        })?;
+    Ok(ReportIdentity {
+        schema_version,
+        product_version: field(report, "product_version"),
+        target_os: field(report, "target_os"),
+    })
+}
+
+fn describe_identity(identity: &ReportIdentity) -> String {
+    format!(
+        "SD-300 {} on {}, schema {}",
+        identity.product_version.as_deref().unwrap_or("unknown"),
+        identity.target_os.as_deref().unwrap_or("unknown"),
+        identity.schema_version
+    )
+}
+
+fn driver_devices(drivers: &Value) -> Items<'_> {
+    let mut devices = Items::new();
+    for (category, list) in drivers.as_object().into_iter().flatten() {
+        if category == "services" {
+            continue;
+        }
+        devices.extend(items(list, &|device: &Value| {
+            field(device, "name").map(|name| format!("{name} ({category})"))
+        }));
+    }
+    devices
+}
+
+fn items<'a>(list: &'a Value, identity: &impl Fn(&Value) -> Option<String>) -> Items<'a> {
+    list.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| Some((identity(item)?, item)))
+        .collect()
+}
+
+fn field(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(text)
+}
+
+/// Strings, numbers, and booleans as text; null and nested values are
+/// unknown.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) if !text.is_empty() => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn bytes(value: &Value) -> Option<String> {
+    value.as_u64().map(crate::ui::common::format_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn report() -> Value {
+        json!({
+            "schema_version": 1,
+            "product_version": "3.1.2",
+            "target_os": "linux",
+            "system": {
+                "cpu_model": "Example CPU",
+                "total_memory_bytes": 17_179_869_184u64,
+                "manufacturer": null,
+                "model": null,
+                "bios_version": "1.0.4"
+            },
+            "memory": { "modules": [
+                { "locator": "DIMM 1", "capacity_bytes": 8_589_934_592u64, "memory_type": "DDR5" },
+                { "locator": "DIMM 2", "capacity_bytes": 8_589_934_592u64, "memory_type": "DDR5" }
+            ] },
+            "disk_health": { "drives": [
+                { "device_id": "nvme0n1", "model": "Example SSD", "firmware": "A1", "media_type": "SSD" }
+            ] },
+            "drivers": {
+                "network": [
+                    { "name": "Wi-Fi", "driver_version": "22.1", "status": "OK" }
+                ],
+                "usb": [],
+                "services": [
+                    { "name": "sshd", "is_running": true },
+                    { "name": "cups", "is_running": true }
+                ]
+            },
+            "network_diagnostics": { "listening_ports": [
+                { "protocol": "TCP", "local_addr": "0.0.0.0", "local_port": 22, "process_name": "sshd" }
+            ] },
+            "capabilities": [
+                { "id": "cpu.temperature", "status": "available", "source": "hwmon" }
+            ],
+            "warnings": [
+                { "source": "Network", "message": "DNS slow", "severity": "warning" }
+            ]
+        })
+    }
+
+    fn change(
+        category: Category,
+        kind: ChangeKind,
+        item: &str,
+        before: Option<&str>,
+        after: Option<&str>,
+    ) -> Change {
+        Change {
+            category,
+            kind,
+            item: item.into(),
+            before: before.map(Into::into),
+            after: after.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn identical_reports_have_no_changes() {
+        let diff = diff_reports(&report(), &report()).expect("diff");
+        assert!(diff.changes.is_empty());
+        // Sections this fixture omits are reported, never read as removals.
+        assert!(diff.skipped.contains(&"/gpu/adapters"));
+    }
+
+    #[test]
+    fn semantic_changes_are_grouped_by_category() {
+        let before = report();
+        let mut after = report();
+        after["system"]["bios_version"] = json!("1.0.7");
+        after["memory"]["modules"]
+            .as_array_mut()
+            .expect("modules")
+            .pop();
+        after["drivers"]["network"][0]["driver_version"] = json!("22.2");
+        after["drivers"]["usb"] = json!([{ "name": "Webcam", "driver_version": "1.0" }]);
+        after["drivers"]["services"][1]["is_running"] = json!(false);
+        after["network_diagnostics"]["listening_ports"] = json!([
+            { "protocol": "TCP", "local_addr": "0.0.0.0", "local_port": 8080, "process_name": null }
+        ]);
+        after["capabilities"][0]["status"] = json!("permission_denied");
+        after["warnings"] = json!([]);
+
+        let diff = diff_reports(&before, &after).expect("diff");
+        assert_eq!(
+            diff.changes,
+            [
+                change(
+                    Category::Hardware,
+                    ChangeKind::Changed,
+                    "BIOS version",
+                    Some("1.0.4"),
+                    Some("1.0.7")
+                ),
+                change(
+                    Category::Hardware,
+                    ChangeKind::Removed,
+                    "Memory module DIMM 2",
+                    Some("8.0 GB DDR5"),
+                    None
+                ),
+                change(
+                    Category::Hardware,
+                    ChangeKind::Added,
+                    "Webcam (usb)",
+                    None,
+                    Some("1.0")
+                ),
+                change(
+                    Category::Drivers,
+                    ChangeKind::Changed,
+                    "Wi-Fi (network) driver",
+                    Some("22.1"),
+                    Some("22.2")
+                ),
+                change(
+                    Category::Services,
+                    ChangeKind::Changed,
+                    "cups",
+                    Some("running"),
+                    Some("stopped")
+                ),
+                change(
+                    Category::ListeningPorts,
+                    ChangeKind::Removed,
+                    "TCP 0.0.0.0:22",
+                    Some("sshd"),
+                    None
+                ),
+                change(
+                    Category::ListeningPorts,
+                    ChangeKind::Added,
+                    "TCP 0.0.0.0:8080",
+                    None,
+                    None
+                ),
+                change(
+                    Category::Capabilities,
+                    ChangeKind::Changed,
+                    "cpu.temperature",
+                    Some("available"),
+                    Some("permission_denied")
+                ),
+                change(
+                    Category::Warnings,
+                    ChangeKind::Removed,
+                    "Network: DNS slow",
+                    Some("warning"),
+                    None
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_schema_versions_compare_the_sections_both_reports_have() {
+        let before = report();
+        let mut after = report();
+        after["schema_version"] = json!(2);
+        after.as_object_mut().expect("object").remove("drivers");
+        after["future_section"] = json!({ "anything": true });
+        after["warnings"][0]["severity"] = json!("error");
+
+        let diff = diff_reports(&before, &after).expect("diff");
+        assert_eq!(diff.before.schema_version, 1);
+        assert_eq!(diff.after.schema_version, 2);
+        assert!(diff.skipped.contains(&"/drivers"));
+        assert!(diff.skipped.contains(&"/drivers/services"));
+        assert_eq!(
+            diff.changes,
+            [change(
+                Category::Warnings,
+                ChangeKind::Changed,
+                "Network: DNS slow",
+                Some("warning"),
+                Some("error")
+            )]
+        );
+
+        assert!(diff_reports(&before, &json!({ "cpu": {} })).is_err());
+    }
+}
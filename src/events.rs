@@ -0,0 +1,588 @@
+//! Event timeline: timestamped state transitions.
+//!
+//! Collectors replace their warnings wholesale on every pass, so a problem
+//! that lasted one refresh leaves no trace in the snapshot. The event log
+//! compares each pass with the previous one and keeps what changed: warnings
+//! raised and cleared, device status changes, connectivity probes flipping,
+//! and processes that start or exit after using a lot of CPU or memory. The
+//! first observation of each kind of state only establishes a baseline, and
+//! the log keeps the most recent `CAPACITY` events.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::collectors::drivers::{DeviceStatus, DriverScanStatus};
+use crate::collectors::network_diag::NetworkDiagData;
+use crate::collectors::{DiagnosticWarning, SystemSnapshot, WarningSeverity};
+
+/// Events kept in memory and exported with reports.
+pub const CAPACITY: usize = 500;
+/// CPU use, in percent of one core, that makes a process worth a timeline entry.
+const PROCESS_CPU_PERCENT: f32 = 50.0;
+/// Share of installed memory that makes a process worth a timeline entry.
+const PROCESS_MEMORY_PERCENT: f64 = 10.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Event {
+    pub unix_ms: u64,
+    pub kind: EventKind,
+    pub severity: WarningSeverity,
+    pub source: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    WarningRaised,
+    WarningCleared,
+    DeviceStatusChanged,
+    ConnectivityChanged,
+    ProcessStarted,
+    ProcessExited,
+}
+
+/// One process as seen by a refresh, including those below the process
+/// table's display cut-off.
+pub struct ProcessSample {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_percent: f32,
+    pub memory_percent: f64,
+}
+
+impl ProcessSample {
+    fn is_heavy(&self) -> bool {
+        self.cpu_percent >= PROCESS_CPU_PERCENT || self.memory_percent >= PROCESS_MEMORY_PERCENT
+    }
+}
+
+struct TrackedProcess {
+    name: String,
+    /// Seen before the log's first observation; such processes never
+    /// count as started.
+    preexisting: bool,
+    /// Crossed a resource threshold at some point while tracked.
+    heavy: bool,
+}
+
+/// Bounded event history plus the state it was last compared against.
+/// Deserializing restores only the events, which is what replay shows.
+#[derive(Default, Deserialize)]
+#[serde(from = "Vec<Event>")]
+pub struct EventLog {
+    events: VecDeque<Event>,
+    warnings: Option<BTreeMap<(String, String), DiagnosticWarning>>,
+    devices: Option<BTreeMap<(String, String, usize), DeviceStatus>>,
+    connectivity: [Option<bool>; 3],
+    processes: Option<HashMap<u32, TrackedProcess>>,
+}
+
+impl From<Vec<Event>> for EventLog {
+    fn from(events: Vec<Event>) -> Self {
+        let skip = events.len().saturating_sub(CAPACITY);
+        Self {
+            events: events.into_iter().skip(skip).collect(),
+            ..Self::default()
+        }
+    }
+}
+
+impl EventLog {
+    /// Events from oldest to newest.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Event> + ExactSizeIterator {
+        self.events.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn to_vec(&self) -> Vec<Event> {
+        self.events.iter().cloned().collect()
+    }
+
+    /// Compare `snapshot` and the current processes with the previous
+    /// observation and record what changed.
+    pub fn observe(
+        &mut self,
+        snapshot: &SystemSnapshot,
+        processes: impl IntoIterator<Item = ProcessSample>,
+        unix_ms: u64,
+    ) {
+        self.observe_warnings(&snapshot.warnings, unix_ms);
+        if snapshot.drivers.scan_status == DriverScanStatus::Success {
+            self.observe_devices(snapshot, unix_ms);
+        }
+        self.observe_connectivity(&snapshot.network_diag, unix_ms);
+        self.observe_processes(processes, unix_ms);
+    }
+
+    fn push(
+        &mut self,
+        unix_ms: u64,
+        kind: EventKind,
+        severity: WarningSeverity,
+        source: &str,
+        message: String,
+    ) {
+        if self.events.len() == CAPACITY {
+            self.events.pop_front();
+        }
+        self.events.push_back(Event {
+            unix_ms,
+            kind,
+            severity,
+            source: source.into(),
+            message,
+        });
+    }
+
+    /// Warnings are matched on their source and wording with the readings
+    /// masked, so a message that restates a changing measurement is the same
+    /// warning. A change in severity is recorded as the warning being raised
+    /// again.
+    fn observe_warnings(&mut self, warnings: &[DiagnosticWarning], unix_ms: u64) {
+        let current: BTreeMap<_, _> = warnings
+            .iter()
+            .map(|warning| {
+                (
+                    (warning.source.clone(), warning_identity(&warning.message)),
+                    warning.clone(),
+                )
+            })
+            .collect();
+        let Some(previous) = self.warnings.replace(current.clone()) else {
+            return;
+        };
+        for warning in warnings {
+            let key = (warning.source.clone(), warning_identity(&warning.message));
+            if previous
+                .get(&key)
+                .is_some_and(|before| before.severity == warning.severity)
+            {
+                continue;
+            }
+            self.push(
+                unix_ms,
+                EventKind::WarningRaised,
+                warning.severity.clone(),
+                &warning.source,
+                warning.message.clone(),
+            );
+        }
+        for (key, warning) in previous {
+            if !current.contains_key(&key) {
+                self.push(
+                    unix_ms,
+                    EventKind::WarningCleared,
+                    WarningSeverity::Info,
+                    &warning.source,
+                    format!("Cleared: {}", warning.message),
+                );
+            }
+        }
+    }
+
+    fn observe_devices(&mut self, snapshot: &SystemSnapshot, unix_ms: u64) {
+        let mut current = BTreeMap::new();
+        for device in snapshot.drivers.devices() {
+            // Devices can share a name within a category; they are told apart
+            // by the order the scan reports them in.
+            let mut occurrence = 0;
+            while current.contains_key(&(
+                device.category.label().to_string(),
+                device.name.clone(),
+                occurrence,
+            )) {
+                occurrence += 1;
+            }
+            current.insert(
+                (
+                    device.category.label().to_string(),
+                    device.name.clone(),
+                    occurrence,
+                ),
+                device.status.clone(),
+            );
+        }
+        let Some(previous) = self.devices.replace(current.clone()) else {
+            return;
+        };
+        for ((category, name, occurrence), status) in &current {
+            let key = (category.clone(), name.clone(), *occurrence);
+            let message = match previous.get(&key) {
+                Some(before) if before == status => continue,
+                Some(before) => format!("{name} ({category}): {before} -> {status}"),
+                None => format!("{name} ({category}) detected: {status}"),
+            };
+            let severity = if status.requires_attention() {
+                WarningSeverity::Warning
+            } else {
+                WarningSeverity::Info
+            };
+            self.push(
+                unix_ms,
+                EventKind::DeviceStatusChanged,
+                severity,
+                "Drivers",
+                message,
+            );
+        }
+        for (category, name, occurrence) in previous.keys() {
+            if !current.contains_key(&(category.clone(), name.clone(), *occurrence)) {
+                self.push(
+                    unix_ms,
+                    EventKind::DeviceStatusChanged,
+                    WarningSeverity::Warning,
+                    "Drivers",
+                    format!("{name} ({category}) is no longer present"),
+                );
+            }
+        }
+    }
+
+    /// Probes are compared only once they have run; messages leave out the
+    /// probe targets so redacted reports stay redacted.
+    fn observe_connectivity(&mut self, diag: &NetworkDiagData, unix_ms: u64) {
+        let probes = [
+            (
+                !diag.gateway.target.is_empty(),
+                diag.gateway.reachable,
+                "Gateway became unreachable",
+                "Gateway is reachable again",
+            ),
+            (
+                !diag.dns.domain.is_empty(),
+                diag.dns.resolved,
+                "DNS resolution started failing",
+                "DNS resolution recovered",
+            ),
+            (
+                !diag.internet.target.is_empty(),
+                diag.internet.reachable,
+                "Internet became unreachable",
+                "Internet is reachable again",
+            ),
+        ];
+        for (index, (checked, up, lost, restored)) in probes.into_iter().enumerate() {
+            if !checked {
+                continue;
+            }
+            match self.connectivity[index].replace(up) {
+                Some(before) if before != up => {
+                    let (severity, message) = if up {
+                        (WarningSeverity::Info, restored)
+                    } else {
+                        (WarningSeverity::Warning, lost)
+                    };
+                    self.push(
+                        unix_ms,
+                        EventKind::ConnectivityChanged,
+                        severity,
+                        "Network",
+                        message.into(),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// A process is announced once when it starts after the first
+    /// observation and crosses a resource threshold, and its exit is recorded
+    /// if it ever crossed one.
+    fn observe_processes(
+        &mut self,
+        processes: impl IntoIterator<Item = ProcessSample>,
+        unix_ms: u64,
+    ) {
+        let baseline = self.processes.is_none();
+        let mut previous = self.processes.take().unwrap_or_default();
+        let mut current = HashMap::with_capacity(previous.len());
+        for sample in processes {
+            let heavy = sample.is_heavy();
+            let tracked = match previous.remove(&sample.pid) {
+                Some(tracked) if tracked.name == sample.name => tracked,
+                // A reused PID is a different process.
+                _ => TrackedProcess {
+                    name: sample.name.clone(),
+                    preexisting: baseline,
+                    heavy: false,
+                },
+            };
+            if heavy && !tracked.heavy && !tracked.preexisting {
+                self.push(
+                    unix_ms,
+                    EventKind::ProcessStarted,
+                    WarningSeverity::Info,
+                    "Processes",
+                    format!(
+                        "{} (PID {}) started using {:.0}% CPU and {:.1}% of memory",
+                        sample.name, sample.pid, sample.cpu_percent, sample.memory_percent
+                    ),
+                );
+            }
+            current.insert(
+                sample.pid,
+                TrackedProcess {
+                    heavy: tracked.heavy || heavy,
+                    ..tracked
+                },
+            );
+        }
+        let mut exited: Vec<_> = previous
+            .into_iter()
+            .filter(|(_, tracked)| tracked.heavy)
+            .collect();
+        exited.sort_by_key(|(pid, _)| *pid);
+        for (pid, tracked) in exited {
+            self.push(
+                unix_ms,
+                EventKind::ProcessExited,
+                WarningSeverity::Info,
+                "Processes",
+                format!(
+                    "{} (PID {pid}) exited after high resource use",
+                    tracked.name
+                ),
+            );
+        }
+        self.processes = Some(current);
+    }
+}
+
+/// Units that mark a number as a reading rather than part of a name.
+const READING_UNITS: &[&str] = &[
+    "B", "KB", "MB", "GB", "TB", "KiB", "MiB", "GiB", "TiB", "C", "F", "V", "W", "Hz", "MHz",
+    "GHz", "RPM", "rpm", "ms", "s", "seconds", "second", "minutes", "minute", "hours", "hour",
+    "days", "day",
+];
+
+/// `message` with its readings masked, so "grew 41 MiB" and "grew 58 MiB"
+/// identify the same warning. Numbers that name the subject are kept:
+/// those inside a word or path ("nvme0n1", "/mnt/disk1") and those after a
+/// capitalized word ("Core 0", "PID 4012"), unless a unit follows them.
+pub(crate) fn warning_identity(message: &str) -> String {
+    let chars: Vec<char> = message.chars().collect();
+    let mut identity = String::with_capacity(message.len());
+    let mut index = 0;
+    while index < chars.len() {
+        if !chars[index].is_ascii_digit() {
+            identity.push(chars[index]);
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < chars.len()
+            && (chars[index].is_ascii_digit()
+                || (matches!(chars[index], '.' | ',')
+                    && chars.get(index + 1).is_some_and(char::is_ascii_digit)))
+        {
+            index += 1;
+        }
+        if is_reading(&chars, start, index) {
+            identity.push('#');
+        } else {
+            identity.extend(&chars[start..index]);
+        }
+    }
+    identity
+}
+
+fn is_reading(chars: &[char], start: usize, end: usize) -> bool {
+    let naming = |c: &char| c.is_alphanumeric() || matches!(c, '/' | '\\' | ':' | '_' | '-');
+    let after = chars[end..].iter().skip_while(|c| **c == ' ');
+    let unit: String = after.clone().take_while(|c| c.is_alphabetic()).collect();
+    let spaced = chars.get(end) == Some(&' ');
+    if matches!(after.clone().next(), Some('%' | '°'))
+        || (!unit.is_empty()
+            && READING_UNITS.contains(&unit.as_str())
+            && (spaced || !chars.get(end + unit.len()).is_some_and(naming)))
+    {
+        return true;
+    }
+    if (start > 0 && naming(&chars[start - 1])) || chars.get(end).is_some_and(naming) {
+        return false;
+    }
+    let word: Vec<&char> = chars[..start]
+        .iter()
+        .rev()
+        .skip_while(|c| **c == ' ')
+        .take_while(|c| c.is_alphabetic())
+        .collect();
+    !word.last().is_some_and(|first| first.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::drivers::{DeviceCategory, DeviceInfo};
+
+    fn warning(source: &str, message: &str, severity: WarningSeverity) -> DiagnosticWarning {
+        DiagnosticWarning {
+            source: source.into(),
+            message: message.into(),
+            severity,
+        }
+    }
+
+    fn process(pid: u32, name: &str, cpu_percent: f32) -> ProcessSample {
+        ProcessSample {
+            pid,
+            name: name.into(),
+            cpu_percent,
+            memory_percent: 1.0,
+        }
+    }
+
+    fn messages(log: &EventLog) -> Vec<(EventKind, &str)> {
+        log.iter()
+            .map(|event| (event.kind, event.message.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn transitions_are_recorded_after_the_first_observation() {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.warnings = vec![warning("Disk", "C: is 91% full", WarningSeverity::Warning)];
+        snapshot.drivers.scan_status = DriverScanStatus::Success;
+        snapshot.drivers.usb = vec![DeviceInfo {
+            name: "Webcam".into(),
+            driver_version: "1.0".into(),
+            driver_date: String::new(),
+            status: DeviceStatus::Ok,
+            category: DeviceCategory::Usb,
+            extra: String::new(),
+        }];
+        snapshot.network_diag.gateway.target = "N/A".into();
+        snapshot.network_diag.gateway.reachable = true;
+
+        let mut log = EventLog::default();
+        log.observe(&snapshot, [process(10, "backup", 90.0)], 1_000);
+        assert!(log.is_empty(), "the first pass only sets the baseline");
+
+        snapshot.warnings = vec![
+            warning("Disk", "C: is 93% full", WarningSeverity::Warning),
+            warning("Network", "DNS slow", WarningSeverity::Warning),
+        ];
+        snapshot.drivers.usb[0].status = DeviceStatus::Error("code 43".into());
+        snapshot.network_diag.gateway.reachable = false;
+        log.observe(
+            &snapshot,
+            [process(11, "compiler", 20.0), process(12, "idle", 0.0)],
+            2_000,
+        );
+        log.observe(
+            &snapshot,
+            [process(11, "compiler", 75.0), process(12, "idle", 0.0)],
+            3_000,
+        );
+        snapshot.warnings.clear();
+        log.observe(&snapshot, [process(12, "idle", 0.0)], 4_000);
+
+        assert_eq!(
+            messages(&log),
+            [
+                (EventKind::WarningRaised, "DNS slow"),
+                (
+                    EventKind::DeviceStatusChanged,
+                    "Webcam (USB): OK -> Error: code 43"
+                ),
+                (EventKind::ConnectivityChanged, "Gateway became unreachable"),
+                (
+                    EventKind::ProcessExited,
+                    "backup (PID 10) exited after high resource use"
+                ),
+                (
+                    EventKind::ProcessStarted,
+                    "compiler (PID 11) started using 75% CPU and 1.0% of memory"
+                ),
+                (EventKind::WarningCleared, "Cleared: C: is 93% full"),
+                (EventKind::WarningCleared, "Cleared: DNS slow"),
+                (
+                    EventKind::ProcessExited,
+                    "compiler (PID 11) exited after high resource use"
+                ),
+            ]
+        );
+        assert_eq!(log.iter().next().unwrap().unix_ms, 2_000);
+    }
+
+    #[test]
+    fn unfinished_scans_and_probes_establish_no_baseline() {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.drivers.scan_status = DriverScanStatus::Scanning;
+        let mut log = EventLog::default();
+        log.observe(&snapshot, [], 1_000);
+
+        snapshot.drivers.scan_status = DriverScanStatus::Success;
+        snapshot.drivers.usb = vec![DeviceInfo {
+            name: "Webcam".into(),
+            driver_version: "1.0".into(),
+            driver_date: String::new(),
+            status: DeviceStatus::Ok,
+            category: DeviceCategory::Usb,
+            extra: String::new(),
+        }];
+        snapshot.network_diag.internet.target = "example.com".into();
+        log.observe(&snapshot, [], 2_000);
+        assert!(log.is_empty());
+    }
+
+    #[test]
+    fn severity_changes_raise_again_and_the_log_stays_bounded() {
+        let mut snapshot = SystemSnapshot::default();
+        let mut log = EventLog::default();
+        log.observe(&snapshot, [], 0);
+        for step in 0..CAPACITY as u64 {
+            let severity = if step % 2 == 0 {
+                WarningSeverity::Warning
+            } else {
+                WarningSeverity::Error
+            };
+            snapshot.warnings = vec![warning("Thermals", "CPU at 96 C", severity)];
+            log.observe(&snapshot, [], step + 1);
+        }
+        assert_eq!(log.len(), CAPACITY);
+        assert_eq!(log.iter().next().unwrap().unix_ms, 1);
+
+        snapshot.warnings.clear();
+        log.observe(&snapshot, [], 9_999);
+        assert_eq!(log.len(), CAPACITY);
+        assert_eq!(log.iter().next().unwrap().unix_ms, 2);
+        assert_eq!(
+            log.iter().next_back().unwrap().kind,
+            EventKind::WarningCleared
+        );
+    }
+
+    #[test]
+    fn readings_do_not_change_a_warnings_identity_but_names_do() {
+        assert_eq!(
+            warning_identity("firefox (PID 4012) grew 1,204.5 MiB"),
+            warning_identity("firefox (PID 4012) grew 1,310.0 MiB")
+        );
+        assert_eq!(
+            warning_identity("Core 0 is at 85.5°C (warning from 80°C)"),
+            warning_identity("Core 0 is at 91°C (warning from 80°C)")
+        );
+        for (one, other) in [
+            ("Drive sda failing", "Drive sdb failing"),
+            ("nvme0n1 is at 71 C", "nvme1n1 is at 71 C"),
+            ("Core 0 is at 85°C", "Core 1 is at 85°C"),
+            (
+                "Partition /mnt/disk1 is at 92%",
+                "Partition /mnt/disk2 is at 92%",
+            ),
+        ] {
+            assert_ne!(warning_identity(one), warning_identity(other), "{one}");
+        }
+    }
+}
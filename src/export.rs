@@ -0,0 +1,441 @@
+//! `sd300 export`: one diagnostic table as CSV, TSV, or JSON.
+//!
+//! Tables are cut from a [`DiagnosticReport`], so they carry exactly the
+//! redaction the JSON snapshot does. Column names are part of the contract:
+//! new columns are only ever appended, and JSON rows use the same names as
+//! keys, in column order. Text cells that a spreadsheet would evaluate as a
+//! formula are prefixed with `'`.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::cli::{ExportArgs, ExportFormat, ExportTable};
+use crate::collectors::network_diag::ConnectionInfo;
+use crate::error::{AppError, Result};
+use crate::report::DiagnosticReport;
+use crate::settings;
+
+const PROCESS_COLUMNS: &[&str] = &[
+    "pid",
+    "name",
+    "friendly_name",
+    "status",
+    "cpu_percent",
+    "memory_bytes",
+    "memory_percent",
+    "disk_read_bytes_per_sec",
+    "disk_write_bytes_per_sec",
+    "gpu_memory_bytes",
+];
+const CONNECTION_COLUMNS: &[&str] = &[
+    "protocol",
+    "local_addr",
+    "local_port",
+    "remote_addr",
+    "remote_port",
+    "state",
+    "pid",
+    "process_name",
+];
+const DRIVER_COLUMNS: &[&str] = &[
+    "category",
+    "name",
+    "status",
+    "status_detail",
+    "driver_version",
+    "driver_date",
+    "detail",
+];
+const SERVICE_COLUMNS: &[&str] = &["name", "display_name", "is_running"];
+const SENSOR_COLUMNS: &[&str] = &[
+    "type", "label", "kind", "value", "unit", "critical", "source",
+];
+const PARTITION_COLUMNS: &[&str] = &[
+    "name",
+    "mount_point",
+    "filesystem",
+    "disk_type",
+    "total_bytes",
+    "used_bytes",
+    "available_bytes",
+    "usage_percent",
+    "is_removable",
+];
+
+/// One exported table: fixed column names and a row of values per record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub columns: &'static [&'static str],
+    pub rows: Vec<Vec<Value>>,
+}
+
+pub async fn run(args: &ExportArgs) -> Result<()> {
+    let report = DiagnosticReport::collect(args.include_sensitive).await;
+    let text = table(&report, args.table).render(args.format);
+    match &args.output {
+        Some(path) => fs::write(path, text)?,
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(text.as_bytes())?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Cut one table from a report, in the report's own row order.
+pub fn table(report: &DiagnosticReport, which: ExportTable) -> Table {
+    let rows = match which {
+        ExportTable::Processes => report
+            .processes
+            .list
+            .iter()
+            .map(|process| {
+                vec![
+                    json!(process.pid),
+                    json!(process.name),
+                    json!(process.friendly_name),
+                    json!(process.status),
+                    rounded(f64::from(process.cpu_percent)),
+                    json!(process.memory_bytes),
+                    rounded(process.memory_percent),
+                    json!(process.disk_read_bytes_per_sec),
+                    json!(process.disk_write_bytes_per_sec),
+                    json!(process.gpu_memory_bytes),
+                ]
+            })
+            .collect(),
+        ExportTable::Connections => connection_rows(&report.network_diagnostics.active_connections),
+        ExportTable::Listening => connection_rows(&report.network_diagnostics.listening_ports),
+        ExportTable::Drivers => report
+            .drivers
+            .devices()
+            .map(|device| {
+                let (status, status_detail) = tag(&device.status);
+                vec![
+                    tag(&device.category).0,
+                    json!(device.name),
+                    status,
+                    status_detail,
+                    json!(device.driver_version),
+                    json!(device.driver_date),
+                    json!(device.extra),
+                ]
+            })
+            .collect(),
+        ExportTable::Services => report
+            .drivers
+            .services
+            .iter()
+            .map(|service| {
+                vec![
+                    json!(service.name),
+                    json!(service.display_name),
+                    json!(service.is_running),
+                ]
+            })
+            .collect(),
+        ExportTable::Sensors => {
+            let thermals = &report.thermals;
+            let temperatures = thermals.sensors.iter().map(|sensor| {
+                vec![
+                    json!("temperature"),
+                    json!(sensor.label),
+                    tag(&sensor.kind).0,
+                    rounded(sensor.temperature),
+                    json!("celsius"),
+                    json!(sensor.critical.map(rounded)),
+                    json!(sensor.source),
+                ]
+            });
+            let fans = thermals.fans.iter().map(|fan| {
+                vec![
+                    json!("fan"),
+                    json!(fan.label),
+                    Value::Null,
+                    json!(fan.rpm),
+                    json!("rpm"),
+                    Value::Null,
+                    json!(fan.source),
+                ]
+            });
+            temperatures.chain(fans).collect()
+        }
+        ExportTable::Partitions => report
+            .disk
+            .partitions
+            .iter()
+            .map(|partition| {
+                vec![
+                    json!(partition.name),
+                    json!(partition.mount_point),
+                    json!(partition.filesystem),
+                    tag(&partition.disk_type).0,
+                    json!(partition.total_bytes),
+                    json!(partition.used_bytes),
+                    json!(partition.available_bytes),
+                    rounded(partition.usage_percent()),
+                    json!(partition.is_removable),
+                ]
+            })
+            .collect(),
+    };
+    let columns = match which {
+        ExportTable::Processes => PROCESS_COLUMNS,
+        ExportTable::Connections | ExportTable::Listening => CONNECTION_COLUMNS,
+        ExportTable::Drivers => DRIVER_COLUMNS,
+        ExportTable::Services => SERVICE_COLUMNS,
+        ExportTable::Sensors => SENSOR_COLUMNS,
+        ExportTable::Partitions => PARTITION_COLUMNS,
+    };
+    Table { columns, rows }
+}
+
+fn connection_rows(connections: &[ConnectionInfo]) -> Vec<Vec<Value>> {
+    connections
+        .iter()
+        .map(|connection| {
+            vec![
+                tag(&connection.protocol).0,
+                json!(connection.local_addr),
+                json!(connection.local_port),
+                json!(connection.remote_addr),
+                json!(connection.remote_port),
+                tag(&connection.state).0,
+                json!(connection.pid),
+                json!(connection.process_name),
+            ]
+        })
+        .collect()
+}
+
+/// The serialized name of an enum value and the text a data-carrying
+/// variant holds, so `degraded("Code 10")` exports as `degraded`, `Code 10`.
+fn tag<T: Serialize>(value: &T) -> (Value, Value) {
+    match serde_json::to_value(value).unwrap_or(Value::Null) {
+        Value::Object(map) if map.len() == 1 => {
+            let (name, detail) = map.into_iter().next().unwrap_or_default();
+            (Value::String(name), detail)
+        }
+        other => (other, Value::Null),
+    }
+}
+
+fn rounded(value: f64) -> Value {
+    json!((value * 100.0).round() / 100.0)
+}
+
+impl Table {
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Csv => self.delimited(',', csv_field),
+            ExportFormat::Tsv => self.delimited('\t', tsv_field),
+            ExportFormat::Json => self.json_rows(),
+        }
+    }
+
+    /// An array of objects, one per line, with keys in column order.
+    fn json_rows(&self) -> String {
+        let objects: Vec<String> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let fields: Vec<String> = self
+                    .columns
+                    .iter()
+                    .zip(row)
+                    .map(|(column, value)| format!("{}:{value}", Value::from(*column)))
+                    .collect();
+                format!("  {{{}}}", fields.join(","))
+            })
+            .collect();
+        if objects.is_empty() {
+            "[]\n".to_string()
+        } else {
+            format!("[\n{}\n]\n", objects.join(",\n"))
+        }
+    }
+
+    fn delimited(&self, separator: char, field: fn(&str) -> String) -> String {
+        let separator = separator.to_string();
+        let mut text = self.columns.join(&separator);
+        text.push_str("\r\n");
+        for row in &self.rows {
+            let cells: Vec<String> = row.iter().map(|value| field(&cell_text(value))).collect();
+            text.push_str(&cells.join(&separator));
+            text.push_str("\r\n");
+        }
+        text
+    }
+}
+
+/// Cell text before quoting; only strings can start a formula, so numbers
+/// keep their sign.
+fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) if text.starts_with(['=', '+', '-', '@', '\t', '\r']) => {
+            format!("'{text}")
+        }
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// RFC 4180 quoting.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// TSV has no quoting, so separators inside a value become spaces.
+fn tsv_field(text: &str) -> String {
+    text.replace(['\t', '\r', '\n'], " ")
+}
+
+/// Save a table as CSV in the shared reports directory, as the TUI's export
+/// key does.
+pub fn save_to_reports(table: &Table, which: ExportTable, unix_ms: u64) -> Result<PathBuf> {
+    let dir = settings::reports_dir().map_err(AppError::platform)?;
+    save_in(&dir, table, which, unix_ms)
+}
+
+fn save_in(dir: &Path, table: &Table, which: ExportTable, unix_ms: u64) -> Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    settings::restrict_directory(dir).map_err(AppError::platform)?;
+    let stamp: String = crate::replay::format_utc(unix_ms)
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect();
+    let path = dir.join(format!(
+        "{}-{}-{}.csv",
+        which.name(),
+        &stamp[..8],
+        &stamp[8..]
+    ));
+    settings::write_private_atomic(&path, ".export", table.render(ExportFormat::Csv).as_bytes())
+        .map_err(AppError::platform)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::network_diag::{ConnectionState, Protocol};
+    use crate::collectors::SystemSnapshot;
+
+    fn connection(local_addr: &str, state: ConnectionState) -> ConnectionInfo {
+        ConnectionInfo {
+            protocol: Protocol::Tcp,
+            local_addr: local_addr.into(),
+            local_port: 443,
+            remote_addr: "203.0.113.9".into(),
+            remote_port: 51000,
+            state,
+            pid: Some(42),
+            process_name: Some("=cmd|' /C calc'!A0".into()),
+        }
+    }
+
+    #[test]
+    fn connection_tables_are_redacted_like_the_snapshot() {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.network_diag.active_connections = vec![connection(
+            "192.0.2.1",
+            ConnectionState::Unknown("BOUND".into()),
+        )];
+        snapshot.network_diag.listening_ports =
+            vec![connection("0.0.0.0", ConnectionState::Listening)];
+
+        let redacted = DiagnosticReport::from_snapshot(&snapshot, false);
+        let connections = table(&redacted, ExportTable::Connections);
+        assert_eq!(connections.columns, CONNECTION_COLUMNS);
+        assert_eq!(connections.rows[0][1], "[redacted]");
+        assert_eq!(connections.rows[0][3], "[redacted]");
+        assert_eq!(connections.rows[0][5], "unknown");
+        assert_eq!(
+            table(&redacted, ExportTable::Listening).rows[0][5],
+            "listening"
+        );
+
+        let sensitive = DiagnosticReport::from_snapshot(&snapshot, true);
+        assert_eq!(
+            table(&sensitive, ExportTable::Connections).rows[0][1],
+            "192.0.2.1"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_defuses_formulas() {
+        let table = Table {
+            columns: &["name", "value", "note"],
+            rows: vec![
+                vec![json!("a, \"b\""), json!(-3.5), json!("=SUM(A1)")],
+                vec![json!("line\nbreak"), Value::Null, json!(true)],
+            ],
+        };
+        assert_eq!(
+            table.render(ExportFormat::Csv),
+            "name,value,note\r\n\"a, \"\"b\"\"\",-3.5,'=SUM(A1)\r\n\"line\nbreak\",,true\r\n"
+        );
+        assert_eq!(
+            table.render(ExportFormat::Tsv),
+            "name\tvalue\tnote\r\na, \"b\"\t-3.5\t'=SUM(A1)\r\nline break\t\ttrue\r\n"
+        );
+        let json: Value = serde_json::from_str(&table.render(ExportFormat::Json)).unwrap();
+        assert_eq!(json[0]["note"], "=SUM(A1)");
+        assert_eq!(json[1]["value"], Value::Null);
+        assert!(table
+            .render(ExportFormat::Json)
+            .starts_with("[\n  {\"name\":\"a, \\\"b\\\"\",\"value\":-3.5,"));
+    }
+
+    #[test]
+    fn every_row_matches_its_header() {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.network_diag.active_connections =
+            vec![connection("192.0.2.1", ConnectionState::Established)];
+        let report = DiagnosticReport::from_snapshot(&snapshot, false);
+        for which in [
+            ExportTable::Processes,
+            ExportTable::Connections,
+            ExportTable::Listening,
+            ExportTable::Drivers,
+            ExportTable::Services,
+            ExportTable::Sensors,
+            ExportTable::Partitions,
+        ] {
+            let table = table(&report, which);
+            assert!(
+                table
+                    .rows
+                    .iter()
+                    .all(|row| row.len() == table.columns.len()),
+                "{}",
+                which.name()
+            );
+        }
+    }
+
+    #[test]
+    fn saved_tables_are_named_by_table_and_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let table = Table {
+            columns: SERVICE_COLUMNS,
+            rows: vec![vec![json!("sshd"), json!("OpenSSH"), json!(true)]],
+        };
+        let path = save_in(dir.path(), &table, ExportTable::Services, 0).unwrap();
+        assert_eq!(path.file_name().unwrap(), "services-19700101-000000.csv");
+        assert_eq!(
+            fs::read_to_string(path).unwrap(),
+            "name,display_name,is_running\r\nsshd,OpenSSH,true\r\n"
+        );
+    }
+}
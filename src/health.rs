@@ -0,0 +1,537 @@
+//! Overall health, explained.
+//!
+//! Each subsystem is rated from its own readings and keeps the reasons for
+//! its rating. A subsystem starts at a score of 100 and loses
+//! [`WARNING_PENALTY`] or [`CRITICAL_PENALTY`] for every reason at that
+//! level. The overall status is the worst subsystem status and the overall
+//! score the lowest subsystem score. A subsystem that could not be observed
+//! is listed with the reason, but counts toward neither.
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::collectors::disk_health::{DiskHealthData, DiskHealthStatus};
+use crate::collectors::drivers::{DriverData, DriverScanStatus};
+use crate::collectors::thresholds::{self, Check, Unit};
+use crate::collectors::{memory_growth, SystemSnapshot};
+use crate::observation::Observation;
+use crate::report::probe_checked;
+use crate::types::HealthStatus;
+
+/// Score lost for each reason at warning level.
+pub const WARNING_PENALTY: u8 = 25;
+/// Score lost for each reason at critical level.
+pub const CRITICAL_PENALTY: u8 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Subsystem {
+    Processor,
+    Memory,
+    Storage,
+    DiskHealth,
+    Thermals,
+    Drivers,
+    Connectivity,
+    Battery,
+}
+
+impl Subsystem {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Processor => "Processor",
+            Self::Memory => "Memory",
+            Self::Storage => "Storage",
+            Self::DiskHealth => "Drive health",
+            Self::Thermals => "Temperature",
+            Self::Drivers => "Drivers",
+            Self::Connectivity => "Connectivity",
+            Self::Battery => "Battery",
+        }
+    }
+}
+
+/// Why a subsystem is rated below good, or why it could not be rated.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct HealthReason {
+    pub status: HealthStatus,
+    pub message: String,
+    /// The reading, for a level past one of its thresholds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<f64>,
+    /// The threshold the reading passed, in the same unit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<f64>,
+}
+
+impl HealthReason {
+    fn new(status: HealthStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            value: None,
+            limit: None,
+        }
+    }
+
+    /// The reason a threshold check gives once past its warning level.
+    fn from_check(check: &Check) -> Option<Self> {
+        let (status, limit) = check.reached()?;
+        Some(Self {
+            status,
+            message: check.message()?,
+            value: Some(check.value),
+            limit: Some(limit),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct SubsystemHealth {
+    pub subsystem: Subsystem,
+    pub status: HealthStatus,
+    /// 0 to 100; absent when the subsystem could not be observed.
+    pub score: Option<u8>,
+    pub reasons: Vec<HealthReason>,
+}
+
+impl SubsystemHealth {
+    /// Rated from its reasons; no reasons means good.
+    fn rated(subsystem: Subsystem, reasons: Vec<HealthReason>) -> Self {
+        let status = reasons
+            .iter()
+            .map(|reason| reason.status)
+            .fold(HealthStatus::Good, HealthStatus::worst);
+        let penalty = reasons
+            .iter()
+            .map(|reason| match reason.status {
+                HealthStatus::Critical => CRITICAL_PENALTY,
+                HealthStatus::Warning => WARNING_PENALTY,
+                HealthStatus::Good | HealthStatus::Unknown => 0,
+            })
+            .fold(0u8, u8::saturating_add);
+        Self {
+            subsystem,
+            status,
+            score: Some(100u8.saturating_sub(penalty)),
+            reasons,
+        }
+    }
+
+    fn unobserved(subsystem: Subsystem, why: impl Into<String>) -> Self {
+        Self {
+            subsystem,
+            status: HealthStatus::Unknown,
+            score: None,
+            reasons: vec![HealthReason::new(HealthStatus::Unknown, why)],
+        }
+    }
+}
+
+/// Overall health and how each subsystem contributed to it.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct HealthExplanation {
+    pub status: HealthStatus,
+    /// Lowest subsystem score; absent when no subsystem could be observed.
+    pub score: Option<u8>,
+    pub subsystems: Vec<SubsystemHealth>,
+}
+
+impl HealthExplanation {
+    /// The subsystems holding overall health below good, worst first.
+    pub fn contributors(&self) -> Vec<&SubsystemHealth> {
+        let mut contributors: Vec<_> = self
+            .subsystems
+            .iter()
+            .filter(|item| matches!(item.status, HealthStatus::Warning | HealthStatus::Critical))
+            .collect();
+        contributors.sort_by_key(|item| item.score);
+        contributors
+    }
+}
+
+pub fn assess(snapshot: &SystemSnapshot) -> HealthExplanation {
+    let checks = thresholds::checks(snapshot);
+    let subsystems = vec![
+        processor(snapshot),
+        memory(snapshot, &checks),
+        storage(&checks),
+        disk_health(&snapshot.disk_health),
+        temperature(snapshot, &checks),
+        drivers(&snapshot.drivers),
+        connectivity(snapshot, &checks),
+        battery(snapshot),
+    ];
+    HealthExplanation {
+        status: subsystems
+            .iter()
+            .map(|item| item.status)
+            .fold(HealthStatus::Unknown, HealthStatus::worst),
+        score: subsystems.iter().filter_map(|item| item.score).min(),
+        subsystems,
+    }
+}
+
+fn check_reasons(checks: &[Check], subsystem: Subsystem) -> Vec<HealthReason> {
+    checks
+        .iter()
+        .filter(|check| check.subsystem == subsystem)
+        .filter_map(HealthReason::from_check)
+        .collect()
+}
+
+/// An observation's detail, or `fallback` when it has none.
+fn gap(observation: &Observation, fallback: &str) -> String {
+    observation
+        .detail
+        .clone()
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+fn processor(snapshot: &SystemSnapshot) -> SubsystemHealth {
+    let load = Check {
+        subject: "Processor load".into(),
+        value: snapshot.cpu.total_usage as f64,
+        unit: Unit::Percent,
+        limits: snapshot.thresholds.cpu,
+        subsystem: Subsystem::Processor,
+    };
+    SubsystemHealth::rated(
+        Subsystem::Processor,
+        HealthReason::from_check(&load).into_iter().collect(),
+    )
+}
+
+/// Memory usage, plus processes whose memory keeps growing.
+fn memory(snapshot: &SystemSnapshot, checks: &[Check]) -> SubsystemHealth {
+    if snapshot.memory.total_bytes == 0 {
+        return SubsystemHealth::unobserved(Subsystem::Memory, "Memory size was not reported");
+    }
+    let mut reasons = check_reasons(checks, Subsystem::Memory);
+    reasons.extend(
+        snapshot
+            .warnings
+            .iter()
+            .filter(|warning| warning.source == memory_growth::WARNING_SOURCE)
+            .map(|warning| HealthReason::new(HealthStatus::Warning, warning.message.clone())),
+    );
+    SubsystemHealth::rated(Subsystem::Memory, reasons)
+}
+
+fn storage(checks: &[Check]) -> SubsystemHealth {
+    if !checks
+        .iter()
+        .any(|check| check.subsystem == Subsystem::Storage)
+    {
+        return SubsystemHealth::unobserved(Subsystem::Storage, "No partitions reported");
+    }
+    SubsystemHealth::rated(
+        Subsystem::Storage,
+        check_reasons(checks, Subsystem::Storage),
+    )
+}
+
+fn disk_health(data: &DiskHealthData) -> SubsystemHealth {
+    if !data.health_status.is_available() {
+        return SubsystemHealth::unobserved(
+            Subsystem::DiskHealth,
+            gap(&data.health_status, "Drive health is unavailable"),
+        );
+    }
+    let rated: Vec<_> = data
+        .drives
+        .iter()
+        .filter(|drive| drive.health_status != DiskHealthStatus::Unknown)
+        .collect();
+    if rated.is_empty() {
+        return SubsystemHealth::unobserved(Subsystem::DiskHealth, "No drive reported its health");
+    }
+    let reasons = rated
+        .into_iter()
+        .filter_map(|drive| {
+            let status = match drive.health_status {
+                DiskHealthStatus::Critical => HealthStatus::Critical,
+                DiskHealthStatus::Warning => HealthStatus::Warning,
+                DiskHealthStatus::Healthy | DiskHealthStatus::Unknown => return None,
+            };
+            Some(HealthReason::new(
+                status,
+                format!("{}: {}", drive.model, drive.health_status.user_label()),
+            ))
+        })
+        .collect();
+    SubsystemHealth::rated(Subsystem::DiskHealth, reasons)
+}
+
+fn temperature(snapshot: &SystemSnapshot, checks: &[Check]) -> SubsystemHealth {
+    if !checks
+        .iter()
+        .any(|check| check.subsystem == Subsystem::Thermals)
+    {
+        return SubsystemHealth::unobserved(
+            Subsystem::Thermals,
+            gap(
+                &snapshot.thermals.cpu_temperature_status,
+                "Temperature unavailable",
+            ),
+        );
+    }
+    SubsystemHealth::rated(
+        Subsystem::Thermals,
+        check_reasons(checks, Subsystem::Thermals),
+    )
+}
+
+fn drivers(data: &DriverData) -> SubsystemHealth {
+    match &data.scan_status {
+        DriverScanStatus::Success => SubsystemHealth::rated(
+            Subsystem::Drivers,
+            data.attention_devices()
+                .map(|device| {
+                    HealthReason::new(
+                        HealthStatus::Warning,
+                        format!("{}: {}", device.name, device.status.user_description()),
+                    )
+                })
+                .collect(),
+        ),
+        DriverScanStatus::ScanFailed(error) => {
+            SubsystemHealth::unobserved(Subsystem::Drivers, format!("Device scan failed: {error}"))
+        }
+        DriverScanStatus::NotScanned | DriverScanStatus::Scanning => {
+            SubsystemHealth::unobserved(Subsystem::Drivers, "Devices not checked")
+        }
+    }
+}
+
+/// Link state first; the probes only say more once something is connected.
+fn connectivity(snapshot: &SystemSnapshot, checks: &[Check]) -> SubsystemHealth {
+    let interfaces = &snapshot.network.interfaces;
+    if interfaces.is_empty() {
+        return SubsystemHealth::unobserved(
+            Subsystem::Connectivity,
+            "No network interfaces reported",
+        );
+    }
+    let mut reasons = Vec::new();
+    if !interfaces.iter().any(|item| item.is_up) {
+        reasons.push(HealthReason::new(HealthStatus::Warning, "Disconnected"));
+    } else {
+        let diag = &snapshot.network_diag;
+        if probe_checked(&diag.gateway) && !diag.gateway.reachable {
+            reasons.push(HealthReason::new(
+                HealthStatus::Warning,
+                "The router (gateway) is not answering",
+            ));
+        }
+        if !diag.dns.domain.is_empty() && !diag.dns.resolved {
+            reasons.push(HealthReason::new(
+                HealthStatus::Warning,
+                "Website names are not resolving (DNS)",
+            ));
+        }
+        if probe_checked(&diag.internet) && !diag.internet.reachable {
+            reasons.push(HealthReason::new(
+                HealthStatus::Warning,
+                "Connected, but the internet is unreachable",
+            ));
+        }
+    }
+    reasons.extend(check_reasons(checks, Subsystem::Connectivity));
+    SubsystemHealth::rated(Subsystem::Connectivity, reasons)
+}
+
+/// Wear against the design capacity; charge level is not health.
+fn battery(snapshot: &SystemSnapshot) -> SubsystemHealth {
+    let thermals = &snapshot.thermals;
+    let Some(battery) = &thermals.battery else {
+        return SubsystemHealth::unobserved(
+            Subsystem::Battery,
+            gap(&thermals.battery_status, "No battery reported"),
+        );
+    };
+    let Some(wear) = battery.wear_percent() else {
+        return SubsystemHealth::unobserved(
+            Subsystem::Battery,
+            "The battery does not report its full-charge and design capacity",
+        );
+    };
+    let check = Check {
+        subject: "Battery wear".into(),
+        value: wear.round(),
+        unit: Unit::Percent,
+        limits: snapshot.thresholds.battery_wear,
+        subsystem: Subsystem::Battery,
+    };
+    SubsystemHealth::rated(
+        Subsystem::Battery,
+        HealthReason::from_check(&check).into_iter().collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::disk::{DiskType, PartitionInfo};
+    use crate::collectors::disk_health::DriveHealth;
+    use crate::collectors::drivers::{DeviceCategory, DeviceInfo, DeviceStatus};
+    use crate::collectors::network::InterfaceInfo;
+    use crate::collectors::thermals::BatteryInfo;
+
+    fn snapshot() -> SystemSnapshot {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.cpu.total_usage = 10.0;
+        snapshot.memory.total_bytes = 100;
+        snapshot.memory.used_bytes = 40;
+        snapshot.disk.partitions = vec![PartitionInfo {
+            name: "sda1".into(),
+            mount_point: "/".into(),
+            filesystem: "ext4".into(),
+            total_bytes: 100,
+            used_bytes: 50,
+            available_bytes: 50,
+            is_removable: false,
+            disk_type: DiskType::Ssd,
+        }];
+        snapshot.thermals.cpu_temp = Some(50.0);
+        snapshot.thermals.gpu_temp = None;
+        snapshot.thermals.sensors.clear();
+        snapshot.network.interfaces = vec![InterfaceInfo {
+            name: "eth0".into(),
+            ip_addresses: Vec::new(),
+            mac_address: String::new(),
+            received_bytes: 0,
+            transmitted_bytes: 0,
+            download_rate: 0,
+            upload_rate: 0,
+            is_up: true,
+            operational_state: "up".into(),
+        }];
+        snapshot.network_diag = Default::default();
+        snapshot.disk_health = Default::default();
+        snapshot.drivers = Default::default();
+        snapshot.thermals.battery = None;
+        snapshot.warnings.clear();
+        snapshot
+    }
+
+    fn subsystem(health: &HealthExplanation, subsystem: Subsystem) -> &SubsystemHealth {
+        health
+            .subsystems
+            .iter()
+            .find(|item| item.subsystem == subsystem)
+            .unwrap()
+    }
+
+    #[test]
+    fn unobserved_subsystems_do_not_rate_overall_health() {
+        let health = assess(&snapshot());
+        assert_eq!(health.status, HealthStatus::Good);
+        assert_eq!(health.score, Some(100));
+        assert!(health.contributors().is_empty());
+
+        let drives = subsystem(&health, Subsystem::DiskHealth);
+        assert_eq!(drives.status, HealthStatus::Unknown);
+        assert_eq!(drives.score, None);
+        assert_eq!(drives.reasons[0].status, HealthStatus::Unknown);
+        assert_eq!(
+            subsystem(&health, Subsystem::Battery).status,
+            HealthStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn each_reason_lowers_its_subsystem_and_the_worst_one_sets_overall_health() {
+        let mut snapshot = snapshot();
+        snapshot.disk.partitions[0].used_bytes = 95;
+        snapshot.disk_health.health_status = Observation::available("smartctl");
+        snapshot.disk_health.drives = vec![DriveHealth {
+            device_id: "sda".into(),
+            model: "Disk A".into(),
+            serial: None,
+            firmware: None,
+            media_type: Default::default(),
+            health_status: DiskHealthStatus::Warning,
+            temperature_celsius: None,
+            power_on_hours: None,
+            wear_percent: None,
+            read_errors_total: None,
+            write_errors_total: None,
+            io_stats: None,
+            health_source: "smartctl".into(),
+        }];
+        snapshot.drivers.scan_status = DriverScanStatus::Success;
+        snapshot.drivers.audio = vec![DeviceInfo {
+            name: "Speakers".into(),
+            driver_version: String::new(),
+            driver_date: String::new(),
+            status: DeviceStatus::Disabled,
+            category: DeviceCategory::Audio,
+            extra: String::new(),
+        }];
+        snapshot.network_diag.internet.target = "1.1.1.1:443".into();
+        snapshot.network_diag.internet.reachable = false;
+        snapshot.network_diag.dns.domain = "example.com".into();
+        snapshot.network_diag.dns.resolved = false;
+        snapshot.thermals.battery = Some(BatteryInfo {
+            percent: 80.0,
+            is_charging: false,
+            is_on_ac: false,
+            time_remaining: None,
+            full_charged_capacity_mwh: Some(35_000),
+            design_capacity_mwh: Some(50_000),
+            design_voltage_mv: None,
+            cycle_count: None,
+            provider_status: None,
+        });
+
+        let health = assess(&snapshot);
+        assert_eq!(health.status, HealthStatus::Critical);
+        assert_eq!(health.score, Some(40));
+
+        let storage = subsystem(&health, Subsystem::Storage);
+        assert_eq!(storage.status, HealthStatus::Critical);
+        assert_eq!(storage.reasons[0].value, Some(95.0));
+        assert_eq!(storage.reasons[0].limit, Some(90.0));
+        assert_eq!(
+            storage.reasons[0].message,
+            "Partition / is at 95% (critical from 90%)"
+        );
+        let network = subsystem(&health, Subsystem::Connectivity);
+        assert_eq!(network.score, Some(50));
+        assert_eq!(network.reasons.len(), 2);
+        assert_eq!(
+            subsystem(&health, Subsystem::Drivers).reasons[0].message,
+            "Speakers: Turned off"
+        );
+        assert_eq!(
+            subsystem(&health, Subsystem::DiskHealth).reasons[0].message,
+            "Disk A: Degrading - Back up data"
+        );
+        assert_eq!(
+            subsystem(&health, Subsystem::Battery).reasons[0].message,
+            "Battery wear is at 30% (warning from 20%)"
+        );
+
+        let order: Vec<_> = health
+            .contributors()
+            .into_iter()
+            .map(|item| item.subsystem)
+            .collect();
+        assert_eq!(order[0], Subsystem::Storage);
+        assert_eq!(order.len(), 5);
+    }
+
+    #[test]
+    fn probes_are_not_blamed_while_disconnected() {
+        let mut snapshot = snapshot();
+        snapshot.network.interfaces[0].is_up = false;
+        snapshot.network_diag.internet.target = "1.1.1.1:443".into();
+
+        let health = assess(&snapshot);
+        let network = subsystem(&health, Subsystem::Connectivity);
+        assert_eq!(network.status, HealthStatus::Warning);
+        assert_eq!(network.reasons.len(), 1);
+        assert_eq!(network.reasons[0].message, "Disconnected");
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::VecDeque;
 
+use crate::types::TimeWindow;
+
 /// Ring buffer for time-series sparkline data
 #[derive(Debug, Clone)]
 pub struct HistoryBuffer {
@@ -49,3 +51,254 @@ impl Default for HistoryBuffer {
         Self::new(60)
     }
 }
+
+/// Minimum, maximum, and mean of the samples a history column covers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Band {
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+}
+
+impl Band {
+    fn point(value: f64) -> Self {
+        Self {
+            min: value,
+            max: value,
+            avg: value,
+        }
+    }
+
+    /// Combine bands that each summarize the same number of samples.
+    fn merge(bands: &[Band]) -> Self {
+        let mut merged = bands[0];
+        let mut sum = 0.0;
+        for band in bands {
+            merged.min = merged.min.min(band.min);
+            merged.max = merged.max.max(band.max);
+            sum += band.avg;
+        }
+        merged.avg = sum / bands.len() as f64;
+        merged
+    }
+}
+
+/// Fixed-width buckets of consecutive samples, oldest dropped first.
+#[derive(Debug, Clone)]
+struct BucketRing {
+    buckets: VecDeque<Band>,
+    capacity: usize,
+    samples_per_bucket: usize,
+    pending: Vec<f64>,
+}
+
+impl BucketRing {
+    fn new(samples_per_bucket: usize, capacity: usize) -> Self {
+        Self {
+            buckets: VecDeque::with_capacity(capacity),
+            capacity,
+            samples_per_bucket,
+            pending: Vec::with_capacity(samples_per_bucket),
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        self.pending.push(value);
+        if self.pending.len() < self.samples_per_bucket {
+            return;
+        }
+        let points = self.pending.drain(..).map(Band::point).collect::<Vec<_>>();
+        if self.buckets.len() >= self.capacity {
+            self.buckets.pop_front();
+        }
+        self.buckets.push_back(Band::merge(&points));
+    }
+
+    /// The newest `count` buckets, with the partly filled bucket last so the
+    /// right edge of a chart is always current.
+    fn latest(&self, count: usize) -> Vec<Band> {
+        let partial = (!self.pending.is_empty()).then(|| {
+            Band::merge(
+                &self
+                    .pending
+                    .iter()
+                    .copied()
+                    .map(Band::point)
+                    .collect::<Vec<_>>(),
+            )
+        });
+        let complete = count.saturating_sub(usize::from(partial.is_some()));
+        let skip = self.buckets.len().saturating_sub(complete);
+        self.buckets
+            .iter()
+            .skip(skip)
+            .copied()
+            .chain(partial)
+            .collect()
+    }
+}
+
+// -- Resolution tiers (samples arrive once per fast refresh, every second) --
+const RAW_SAMPLES: usize = 10 * 60;
+const TEN_SECOND_BUCKETS: usize = 6 * 60 * 6;
+const MINUTE_BUCKETS: usize = 24 * 60;
+/// Samples needed to fill every tier
+pub const FULL_HISTORY_SAMPLES: usize = MINUTE_BUCKETS * 60;
+
+/// History for one metric at three resolutions: 1 s samples for 10 minutes,
+/// 10 s min/max/avg buckets for 6 hours, and 1 min buckets for a day.
+///
+/// `push`, `len`, `as_slice`, and `latest` behave like a [`HistoryBuffer`]
+/// holding the default one-minute window.
+#[derive(Debug, Clone)]
+pub struct MetricHistory {
+    raw: HistoryBuffer,
+    minute_samples: usize,
+    ten_second: BucketRing,
+    minute: BucketRing,
+}
+
+impl MetricHistory {
+    /// `minute_samples` is how many 1 s samples the one-minute window shows.
+    pub fn new(minute_samples: usize) -> Self {
+        Self {
+            raw: HistoryBuffer::new(RAW_SAMPLES.max(minute_samples)),
+            minute_samples,
+            ten_second: BucketRing::new(10, TEN_SECOND_BUCKETS),
+            minute: BucketRing::new(60, MINUTE_BUCKETS),
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        self.raw.push(value);
+        self.ten_second.push(value);
+        self.minute.push(value);
+    }
+
+    /// Samples in the one-minute window
+    pub fn as_slice(&self) -> Vec<f64> {
+        let raw = self.raw.as_slice();
+        raw[raw.len().saturating_sub(self.minute_samples)..].to_vec()
+    }
+
+    pub fn latest(&self) -> Option<f64> {
+        self.raw.latest()
+    }
+
+    /// Number of samples in the one-minute window
+    pub fn len(&self) -> usize {
+        self.raw.len().min(self.minute_samples)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    /// Bands covering `window`, oldest first, at its native resolution.
+    pub fn bands(&self, window: TimeWindow) -> Vec<Band> {
+        let raw = |count: usize| {
+            let samples = self.raw.as_slice();
+            samples[samples.len().saturating_sub(count)..]
+                .iter()
+                .copied()
+                .map(Band::point)
+                .collect()
+        };
+        match window {
+            TimeWindow::Minute => raw(self.minute_samples),
+            TimeWindow::TenMinutes => raw(RAW_SAMPLES),
+            TimeWindow::Hour => self.ten_second.latest(6 * 60),
+            TimeWindow::SixHours => self.ten_second.latest(TEN_SECOND_BUCKETS),
+            TimeWindow::Day => self.minute.latest(MINUTE_BUCKETS),
+        }
+    }
+
+    /// Bands covering `window`, merged so they fit in `columns`. Groups are
+    /// aligned to the newest band so the rightmost column is always current.
+    pub fn columns(&self, window: TimeWindow, columns: usize) -> Vec<Band> {
+        let bands = self.bands(window);
+        if columns == 0 || bands.len() <= columns {
+            return bands;
+        }
+        let group = bands.len().div_ceil(columns);
+        let mut merged = bands.rchunks(group).map(Band::merge).collect::<Vec<_>>();
+        merged.reverse();
+        merged
+    }
+}
+
+impl Default for MetricHistory {
+    fn default() -> Self {
+        Self::new(60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_minute_view_matches_a_sixty_sample_ring() {
+        let mut history = MetricHistory::new(60);
+        let mut ring = HistoryBuffer::new(60);
+        for sample in 0..200 {
+            history.push(sample as f64);
+            ring.push(sample as f64);
+        }
+        assert_eq!(history.as_slice(), ring.as_slice());
+        assert_eq!(history.len(), ring.len());
+        assert_eq!(history.latest(), Some(199.0));
+        assert_eq!(history.bands(TimeWindow::Minute).len(), 60);
+    }
+
+    #[test]
+    fn tiers_keep_their_spans_and_summarize_buckets() {
+        let mut history = MetricHistory::new(60);
+        for second in 0..(2 * 24 * 60 * 60) {
+            history.push((second % 50) as f64);
+        }
+        assert_eq!(history.bands(TimeWindow::TenMinutes).len(), RAW_SAMPLES);
+        assert_eq!(history.bands(TimeWindow::Hour).len(), 360);
+        assert_eq!(
+            history.bands(TimeWindow::SixHours).len(),
+            TEN_SECOND_BUCKETS
+        );
+        let day = history.bands(TimeWindow::Day);
+        assert_eq!(day.len(), MINUTE_BUCKETS);
+        // Every minute of a 50-second sawtooth spans its whole range.
+        assert!(day.iter().all(|band| band.min == 0.0 && band.max == 49.0));
+    }
+
+    #[test]
+    fn partial_buckets_keep_the_newest_sample_visible() {
+        let mut history = MetricHistory::new(60);
+        for _ in 0..25 {
+            history.push(1.0);
+        }
+        history.push(9.0);
+        let hour = history.bands(TimeWindow::Hour);
+        assert_eq!(hour.len(), 3);
+        assert_eq!(hour[2].max, 9.0);
+        assert_eq!(hour[2].min, 1.0);
+        let day = history.bands(TimeWindow::Day);
+        assert_eq!(day.len(), 1);
+        assert_eq!(day[0].max, 9.0);
+    }
+
+    #[test]
+    fn fitting_to_columns_preserves_spikes() {
+        let mut history = MetricHistory::new(60);
+        for second in 0..RAW_SAMPLES {
+            history.push(if second == 123 { 100.0 } else { 5.0 });
+        }
+        let columns = history.columns(TimeWindow::TenMinutes, 70);
+        assert!(columns.len() <= 70);
+        assert_eq!(
+            columns.iter().map(|band| band.max).fold(0.0, f64::max),
+            100.0,
+            "a one-second spike survives downsampling"
+        );
+        assert!(columns.iter().all(|band| band.min == 5.0));
+        assert_eq!(history.columns(TimeWindow::Minute, 70).len(), 60);
+    }
+}
@@ -0,0 +1,816 @@
+//! Self-contained HTML rendering of a [`DiagnosticReport`].
+//!
+//! Helpdesk customers open these files from ticket attachments, often
+//! offline, so the document carries its own CSS and SVG charts and its
+//! Content-Security-Policy refuses every external fetch. The User and
+//! Technician views live in one file and switch with two radio buttons, which
+//! needs no script. Every value taken from the report is HTML-escaped.
+
+use std::fmt::Write;
+
+use crate::collectors::disk_health::DiskHealthStatus;
+use crate::collectors::WarningSeverity;
+use crate::observation::ObservationStatus;
+use crate::report::{
+    health_verdicts, probe_checked, redacted_field_label, DiagnosticReport, HealthVerdict,
+};
+use crate::settings::Limits;
+use crate::types::HealthStatus;
+use crate::ui::common::{format_bytes, format_throughput, format_uptime};
+
+/// Rows in the Technician process table and the User "busiest apps" list.
+const TECH_PROCESS_ROWS: usize = 15;
+const USER_PROCESS_ROWS: usize = 5;
+
+const STYLE: &str = r#"
+:root{--bg:#f6f7f9;--card:#fff;--text:#1d2430;--muted:#5c6778;--line:#dde2ea;--good:#1f9d55;--warn:#c27c0e;--crit:#c8322f;--info:#2f6fca;--track:#e8ecf2}
+*{box-sizing:border-box}
+body{margin:0;background:var(--bg);color:var(--text);font:14px/1.45 system-ui,-apple-system,"Segoe UI",Roboto,sans-serif}
+header,main,.views{max-width:1080px;margin:0 auto;padding:0 20px}
+header{padding-top:24px}
+h1{font-size:22px;margin:0 0 4px}
+h2{font-size:17px;margin:0 0 12px}
+h3{font-size:14px;margin:16px 0 8px;color:var(--muted);text-transform:uppercase;letter-spacing:.04em}
+.sub{color:var(--muted);margin:0 0 16px}
+section{background:var(--card);border:1px solid var(--line);border-radius:8px;padding:16px 18px;margin:0 0 16px}
+.privacy{border-left:4px solid var(--info)}
+.privacy.sensitive{border-left-color:var(--crit);background:#fff6f5}
+.attention{border-left:4px solid var(--warn)}
+.views{margin-bottom:16px}
+body>input{position:absolute;opacity:0}
+.views label{display:inline-block;padding:6px 14px;border:1px solid var(--line);background:var(--card);cursor:pointer;margin-right:-1px}
+.views label:first-of-type{border-radius:6px 0 0 6px}
+.views label:last-of-type{border-radius:0 6px 6px 0}
+#view-user:checked~.views label[for=view-user],#view-tech:checked~.views label[for=view-tech]{background:var(--text);color:#fff}
+#view-user:checked~main .tech-only,#view-tech:checked~main .user-only{display:none}
+table{width:100%;border-collapse:collapse}
+th,td{text-align:left;padding:5px 8px;border-bottom:1px solid var(--line);vertical-align:top}
+th{color:var(--muted);font-weight:600;font-size:12px}
+td.num{text-align:right;font-variant-numeric:tabular-nums}
+code{font:12px ui-monospace,SFMono-Regular,Menlo,Consolas,monospace}
+ul.warnings{list-style:none;margin:0;padding:0}
+ul.warnings li{padding:6px 10px;margin:0 0 6px;border-radius:6px;border-left:4px solid}
+li.error{border-color:var(--crit);background:#fdf0ef}
+li.warning{border-color:var(--warn);background:#fdf6e9}
+li.info{border-color:var(--info);background:#eef4fc}
+.source{color:var(--muted);font-size:12px;margin-right:6px}
+.cards{display:grid;grid-template-columns:repeat(auto-fill,minmax(230px,1fr));gap:12px}
+.card{border:1px solid var(--line);border-radius:8px;padding:10px 12px}
+.card .verdict{font-weight:600}
+.good{color:var(--good)}.warning{color:var(--warn)}.critical{color:var(--crit)}.unknown{color:var(--muted)}
+svg.meter{width:100%;height:10px;display:block;margin-top:6px}
+svg .track{fill:var(--track)}
+svg .fill-good{fill:var(--good)}svg .fill-warning{fill:var(--warn)}svg .fill-critical{fill:var(--crit)}svg .fill-unknown{fill:var(--muted)}
+svg text{fill:var(--muted);font:9px system-ui,sans-serif}
+.empty{color:var(--muted)}
+@media print{.views{display:none}.user-only,.tech-only{display:revert!important}section{break-inside:avoid}}
+"#;
+
+/// Render `report` as one standalone HTML document. `generated_unix_ms` is
+/// the time shown in the header.
+pub fn render(report: &DiagnosticReport, generated_unix_ms: u64) -> String {
+    let mut html = String::with_capacity(64 * 1024);
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'none'; style-src 'unsafe-inline'\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <meta name=\"generator\" content=\"SD-300 {version}\">\n\
+         <title>SD-300 diagnostic report</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <header>\n<h1>SD-300 diagnostic report</h1>\n\
+         <p class=\"sub\">{os} {os_version} &middot; {cpu} &middot; {arch} &middot; generated {time} UTC by SD-300 {version}</p>\n\
+         </header>\n",
+        version = escape(report.product_version),
+        os = escape(&report.system.os_name),
+        os_version = escape(&report.system.os_version),
+        cpu = escape(&report.system.cpu_model),
+        arch = escape(report.target_arch),
+        time = crate::replay::format_utc(generated_unix_ms),
+    );
+    html.push_str(
+        "<input type=\"radio\" name=\"view\" id=\"view-user\" checked>\
+         <input type=\"radio\" name=\"view\" id=\"view-tech\">\n\
+         <div class=\"views\"><label for=\"view-user\">User view</label>\
+         <label for=\"view-tech\">Technician view</label></div>\n<main>\n",
+    );
+
+    privacy_section(&mut html, report);
+    attention_section(&mut html, report);
+    user_view(&mut html, report);
+    technician_view(&mut html, report);
+
+    html.push_str("</main>\n</body>\n</html>\n");
+    html
+}
+
+fn privacy_section(html: &mut String, report: &DiagnosticReport) {
+    let privacy = &report.privacy;
+    if privacy.sensitive_values_included {
+        html.push_str(
+            "<section class=\"privacy sensitive\">\n<h2>Contains sensitive values</h2>\n\
+             <p>This report was exported with sensitive values included: the computer name, \
+             network and hardware addresses, and drive serial numbers appear unredacted. \
+             Share it only with people you trust.</p>\n</section>\n",
+        );
+        return;
+    }
+    html.push_str(
+        "<section class=\"privacy\">\n<h2>Redacted before export</h2>\n\
+         <p>These values were replaced with <code>[redacted]</code> and do not appear anywhere in this file:</p>\n<ul>\n",
+    );
+    for field in &privacy.redacted_fields {
+        let _ = writeln!(
+            html,
+            "<li>{} <span class=\"tech-only\">(<code>{}</code>)</span></li>",
+            escape(redacted_field_label(field)),
+            escape(field)
+        );
+    }
+    html.push_str("</ul>\n</section>\n");
+}
+
+fn attention_section(html: &mut String, report: &DiagnosticReport) {
+    let devices = report.drivers.attention_devices().collect::<Vec<_>>();
+    if report.warnings.is_empty() && devices.is_empty() {
+        html.push_str(
+            "<section>\n<h2>Needs attention</h2>\n\
+             <p class=\"good\">No warnings and no devices needing attention.</p>\n</section>\n",
+        );
+        return;
+    }
+
+    html.push_str("<section class=\"attention\">\n<h2>Needs attention</h2>\n");
+    if !report.warnings.is_empty() {
+        html.push_str("<ul class=\"warnings\">\n");
+        for warning in &report.warnings {
+            let _ = writeln!(
+                html,
+                "<li class=\"{}\"><span class=\"source\">{}</span>{}</li>",
+                severity_class(&warning.severity),
+                escape(&warning.source),
+                escape(&warning.message)
+            );
+        }
+        html.push_str("</ul>\n");
+    }
+    if !devices.is_empty() {
+        let _ = write!(
+            html,
+            "<h3>Devices needing attention ({})</h3>\n<table>\n\
+             <tr><th>Device</th><th>Type</th><th>Status</th><th class=\"tech-only\">Driver</th></tr>\n",
+            devices.len()
+        );
+        for device in devices {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td class=\"warning\"><span class=\"user-only\">{}</span>\
+                 <span class=\"tech-only\">{}</span></td><td class=\"tech-only\">{} {}</td></tr>",
+                escape(&device.name),
+                device.category.label(),
+                device.status.user_description(),
+                escape(&device.status.to_string()),
+                escape(&device.driver_version),
+                escape(&device.driver_date)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</section>\n");
+}
+
+fn user_view(html: &mut String, report: &DiagnosticReport) {
+    html.push_str(
+        "<div class=\"user-only\">\n<section>\n<h2>System health</h2>\n<div class=\"cards\">\n",
+    );
+
+    for verdict in health_verdicts(report) {
+        meter_card(html, &verdict);
+    }
+    html.push_str("</div>\n</section>\n");
+
+    html.push_str("<section>\n<h2>Busiest apps</h2>\n");
+    let mut processes = report.processes.list.iter().collect::<Vec<_>>();
+    processes.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+    if processes.is_empty() {
+        html.push_str("<p class=\"empty\">No running apps were reported.</p>\n");
+    } else {
+        html.push_str("<table>\n<tr><th>App</th><th>Processor</th><th>Memory</th></tr>\n");
+        for process in processes.into_iter().take(USER_PROCESS_ROWS) {
+            let name = if process.friendly_name.is_empty() {
+                &process.name
+            } else {
+                &process.friendly_name
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"num\">{:.1}%</td><td class=\"num\">{}</td></tr>",
+                escape(name),
+                process.cpu_percent,
+                format_bytes(process.memory_bytes)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</section>\n");
+
+    let system = &report.system;
+    html.push_str("<section>\n<h2>Your computer</h2>\n<table>\n");
+    let maker = [system.manufacturer.as_deref(), system.model.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !maker.is_empty() {
+        row(html, "Model", &maker);
+    }
+    row(
+        html,
+        "System",
+        &format!("{} {}", system.os_name, system.os_version),
+    );
+    row(html, "Processor", &system.cpu_model);
+    row(html, "Memory", &format_bytes(system.total_memory_bytes));
+    row(html, "Running for", &format_uptime(system.uptime_seconds));
+    html.push_str("</table>\n</section>\n</div>\n");
+}
+
+fn technician_view(html: &mut String, report: &DiagnosticReport) {
+    html.push_str("<div class=\"tech-only\">\n");
+    let system = &report.system;
+    html.push_str("<section>\n<h2>System</h2>\n<table>\n");
+    row(html, "Hostname", &system.hostname);
+    row(
+        html,
+        "Operating system",
+        &format!("{} {}", system.os_name, system.os_version),
+    );
+    row(html, "Kernel", &system.kernel_version);
+    row(html, "Architecture", &system.architecture);
+    for (label, value) in [
+        ("Manufacturer", &system.manufacturer),
+        ("Model", &system.model),
+        ("BIOS", &system.bios_version),
+        ("BIOS date", &system.bios_release_date),
+    ] {
+        if let Some(value) = value {
+            row(html, label, value);
+        }
+    }
+    if let Some(hypervisor) = system.hypervisor_present {
+        row(
+            html,
+            "Hypervisor",
+            if hypervisor { "present" } else { "none" },
+        );
+    }
+    row(html, "Uptime", &format_uptime(system.uptime_seconds));
+    html.push_str("</table>\n</section>\n");
+
+    let cpu = &report.cpu;
+    let _ = write!(
+        html,
+        "<section>\n<h2>CPU</h2>\n<p>{} &middot; {} cores / {} threads &middot; {:.1}% total</p>\n",
+        escape(&cpu.cpu_model),
+        cpu.core_count,
+        cpu.thread_count,
+        cpu.total_usage
+    );
+    core_chart(html, &cpu.per_core_usage, report.thresholds.cpu);
+    html.push_str("</section>\n");
+
+    let memory = &report.memory;
+    html.push_str("<section>\n<h2>Memory</h2>\n<table>\n<tr><th>Pool</th><th>Used</th><th>Total</th><th>Usage</th></tr>\n");
+    for (label, used, total, percent) in [
+        (
+            "RAM",
+            memory.used_bytes,
+            memory.total_bytes,
+            memory.usage_percent(),
+        ),
+        (
+            "Swap",
+            memory.swap_used_bytes,
+            memory.swap_total_bytes,
+            memory.swap_percent(),
+        ),
+    ] {
+        let _ = writeln!(
+            html,
+            "<tr><td>{label}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+            format_bytes(used),
+            format_bytes(total),
+            meter(
+                percent,
+                &HealthStatus::from_limits(percent, report.thresholds.memory)
+            )
+        );
+    }
+    html.push_str("</table>\n");
+    if !memory.modules.is_empty() {
+        html.push_str("<h3>Modules</h3>\n<table>\n<tr><th>Slot</th><th>Capacity</th><th>Type</th><th>Speed</th><th>Part</th></tr>\n");
+        for module in &memory.modules {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"num\">{}</td><td>{}</td><td>{}</td><td>{} {}</td></tr>",
+                escape(module.locator.as_deref().unwrap_or("-")),
+                format_bytes(module.capacity_bytes),
+                escape(module.memory_type.as_deref().unwrap_or("-")),
+                module
+                    .configured_speed_mt_s
+                    .map(|speed| format!("{speed} MT/s"))
+                    .unwrap_or_else(|| "-".into()),
+                escape(module.manufacturer.as_deref().unwrap_or("")),
+                escape(module.part_number.as_deref().unwrap_or(""))
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</section>\n");
+
+    storage(html, report);
+    network(html, report);
+    processes(html, report);
+    thermals_and_gpu(html, report);
+    capabilities_and_events(html, report);
+    html.push_str("</div>\n");
+}
+
+fn storage(html: &mut String, report: &DiagnosticReport) {
+    html.push_str("<section>\n<h2>Storage</h2>\n");
+    if report.disk.partitions.is_empty() {
+        html.push_str("<p class=\"empty\">No filesystems reported.</p>\n");
+    } else {
+        html.push_str("<table>\n<tr><th>Mount</th><th>Filesystem</th><th>Type</th><th>Free</th><th>Size</th><th>Usage</th></tr>\n");
+        for partition in &report.disk.partitions {
+            let used = partition.usage_percent();
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+                escape(&partition.mount_point),
+                escape(&partition.filesystem),
+                partition.disk_type,
+                format_bytes(partition.available_bytes),
+                format_bytes(partition.total_bytes),
+                meter(
+                    used,
+                    &HealthStatus::from_limits(used, report.thresholds.disk_for(partition))
+                )
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !report.disk_health.drives.is_empty() {
+        html.push_str("<h3>Drive health</h3>\n<table>\n<tr><th>Drive</th><th>Media</th><th>Health</th><th>Temp</th><th>Power-on</th><th>Wear</th><th>Serial</th><th>Source</th></tr>\n");
+        for drive in &report.disk_health.drives {
+            let (class, label) = match drive.health_status {
+                DiskHealthStatus::Healthy => ("good", "Healthy"),
+                DiskHealthStatus::Warning => ("warning", "Warning"),
+                DiskHealthStatus::Critical => ("critical", "Critical"),
+                DiskHealthStatus::Unknown => ("unknown", "Unknown"),
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td class=\"{class}\">{label}</td><td class=\"num\">{}</td>\
+                 <td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&drive.model),
+                drive.media_type,
+                optional(drive.temperature_celsius.map(|temp| format!("{temp:.0}°C"))),
+                optional(drive.power_on_hours.map(|hours| format!("{hours} h"))),
+                optional(drive.wear_percent.map(|wear| format!("{wear}%"))),
+                escape(drive.serial.as_deref().unwrap_or("-")),
+                escape(&drive.health_source)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</section>\n");
+}
+
+fn network(html: &mut String, report: &DiagnosticReport) {
+    let network = &report.network;
+    let _ = write!(
+        html,
+        "<section>\n<h2>Network</h2>\n<p>Down {} &middot; Up {}</p>\n",
+        format_throughput(network.total_download_rate),
+        format_throughput(network.total_upload_rate)
+    );
+    if !network.interfaces.is_empty() {
+        html.push_str("<table>\n<tr><th>Interface</th><th>State</th><th>Addresses</th><th>MAC</th><th>Down</th><th>Up</th></tr>\n");
+        for interface in &network.interfaces {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"{}\">{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+                escape(&interface.name),
+                if interface.is_up { "good" } else { "unknown" },
+                escape(&interface.operational_state),
+                escape(&interface.ip_addresses.join(", ")),
+                escape(&interface.mac_address),
+                format_throughput(interface.download_rate),
+                format_throughput(interface.upload_rate)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    let diagnostics = &report.network_diagnostics;
+    html.push_str("<h3>Connectivity</h3>\n<table>\n<tr><th>Check</th><th>Result</th><th>Latency</th><th>Target</th></tr>\n");
+    for (label, probe) in [
+        ("Gateway", &diagnostics.gateway),
+        ("Internet", &diagnostics.internet),
+    ] {
+        let (class, result) = if !probe_checked(probe) {
+            ("unknown", "Not checked".to_string())
+        } else if probe.reachable {
+            ("good", "Reachable".to_string())
+        } else {
+            (
+                "critical",
+                format!(
+                    "Unreachable{}",
+                    probe
+                        .error
+                        .as_deref()
+                        .map(|error| format!(": {error}"))
+                        .unwrap_or_default()
+                ),
+            )
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{label}</td><td class=\"{class}\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+            escape(&result),
+            optional(probe.latency_ms.map(|ms| format!("{ms:.0} ms"))),
+            escape(&probe.target)
+        );
+    }
+    let dns = &diagnostics.dns;
+    let (class, result) = if dns.domain.is_empty() {
+        ("unknown", "Not checked".to_string())
+    } else if dns.resolved {
+        ("good", "Resolved".to_string())
+    } else {
+        (
+            "critical",
+            format!(
+                "Failed{}",
+                dns.error
+                    .as_deref()
+                    .map(|error| format!(": {error}"))
+                    .unwrap_or_default()
+            ),
+        )
+    };
+    let _ = writeln!(
+        html,
+        "<tr><td>DNS</td><td class=\"{class}\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+        escape(&result),
+        optional(dns.resolution_ms.map(|ms| format!("{ms:.0} ms"))),
+        escape(&dns.domain)
+    );
+    let _ = write!(
+        html,
+        "</table>\n<p class=\"sub\">{} active connection(s), {} listening port(s).</p>\n</section>\n",
+        diagnostics.active_connections.len(),
+        diagnostics.listening_ports.len()
+    );
+}
+
+fn processes(html: &mut String, report: &DiagnosticReport) {
+    let _ = write!(
+        html,
+        "<section>\n<h2>Processes</h2>\n<p>{} running; top {} by CPU.</p>\n\
+         <table>\n<tr><th>PID</th><th>Name</th><th>CPU</th><th>Memory</th><th>Disk read</th><th>Disk write</th><th>Status</th></tr>\n",
+        report.processes.total_count,
+        TECH_PROCESS_ROWS.min(report.processes.list.len())
+    );
+    let mut processes = report.processes.list.iter().collect::<Vec<_>>();
+    processes.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+    for process in processes.into_iter().take(TECH_PROCESS_ROWS) {
+        let _ = writeln!(
+            html,
+            "<tr><td class=\"num\">{}</td><td>{}</td><td class=\"num\">{:.1}%</td><td class=\"num\">{}</td>\
+             <td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+            process.pid,
+            escape(&process.name),
+            process.cpu_percent,
+            format_bytes(process.memory_bytes),
+            format_throughput(process.disk_read_bytes_per_sec),
+            format_throughput(process.disk_write_bytes_per_sec),
+            escape(&process.status)
+        );
+    }
+    html.push_str("</table>\n</section>\n");
+}
+
+fn thermals_and_gpu(html: &mut String, report: &DiagnosticReport) {
+    let gpu = &report.gpu;
+    html.push_str("<section>\n<h2>Graphics</h2>\n");
+    if gpu.adapters.is_empty() {
+        html.push_str("<p class=\"empty\">No graphics adapters reported.</p>\n");
+    } else {
+        html.push_str("<table>\n<tr><th>Adapter</th><th>Driver</th><th>Memory</th><th>Utilization</th><th>Temp</th><th>Source</th></tr>\n");
+        for adapter in &gpu.adapters {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+                escape(&adapter.name),
+                escape(adapter.driver_version.as_deref().unwrap_or("-")),
+                optional(adapter.dedicated_memory_mb.map(|mb| format!("{mb} MB"))),
+                optional(adapter.utilization_percent.map(|pct| format!("{pct:.0}%"))),
+                optional(adapter.temperature_celsius.map(|temp| format!("{temp:.0}°C"))),
+                escape(&adapter.source)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</section>\n");
+
+    let thermals = &report.thermals;
+    html.push_str("<section>\n<h2>Thermals and power</h2>\n");
+    if thermals.sensors.is_empty() && thermals.fans.is_empty() {
+        let _ = writeln!(
+            html,
+            "<p class=\"empty\">No temperature sensors reported ({}).</p>",
+            escape(
+                thermals
+                    .temperature_status
+                    .detail
+                    .as_deref()
+                    .unwrap_or(&thermals.temperature_status.source)
+            )
+        );
+    } else {
+        html.push_str(
+            "<table>\n<tr><th>Sensor</th><th>Reading</th><th>Critical</th><th>Source</th></tr>\n",
+        );
+        for sensor in &thermals.sensors {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"num\">{:.0}°C</td><td class=\"num\">{}</td><td>{}</td></tr>",
+                escape(&sensor.label),
+                sensor.temperature,
+                optional(sensor.critical.map(|temp| format!("{temp:.0}°C"))),
+                escape(&sensor.source)
+            );
+        }
+        for fan in &thermals.fans {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"num\">{} RPM</td><td class=\"num\">-</td><td>{}</td></tr>",
+                escape(&fan.label),
+                fan.rpm,
+                escape(&fan.source)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    if let Some(battery) = &thermals.battery {
+        let _ = writeln!(
+            html,
+            "<p>Battery {:.0}%{}{}{}</p>",
+            battery.percent,
+            if battery.is_charging {
+                ", charging"
+            } else if battery.is_on_ac {
+                ", on AC power"
+            } else {
+                ", on battery"
+            },
+            battery
+                .time_remaining
+                .as_deref()
+                .map(|remaining| format!(", {} remaining", escape(remaining)))
+                .unwrap_or_default(),
+            battery
+                .cycle_count
+                .map(|cycles| format!(", {cycles} cycles"))
+                .unwrap_or_default()
+        );
+    }
+    html.push_str("</section>\n");
+}
+
+fn capabilities_and_events(html: &mut String, report: &DiagnosticReport) {
+    html.push_str("<section>\n<h2>Capabilities</h2>\n<table>\n<tr><th>Capability</th><th>Status</th><th>Source</th><th>Detail</th></tr>\n");
+    for capability in &report.capabilities {
+        let observation = &capability.observation;
+        let (class, status) = match observation.status {
+            ObservationStatus::Available => ("good", "available"),
+            ObservationStatus::Unavailable => ("unknown", "unavailable"),
+            ObservationStatus::Unsupported => ("unknown", "unsupported"),
+            ObservationStatus::PermissionDenied => ("warning", "permission denied"),
+            ObservationStatus::Error => ("critical", "error"),
+            ObservationStatus::Contradictory => ("warning", "contradictory"),
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td><code>{}</code></td><td class=\"{class}\">{status}</td><td>{}</td><td>{}</td></tr>",
+            escape(capability.id),
+            escape(&observation.source),
+            escape(observation.detail.as_deref().unwrap_or(""))
+        );
+    }
+    html.push_str("</table>\n</section>\n");
+
+    let _ = write!(
+        html,
+        "<section>\n<h2>Event timeline</h2>\n<p class=\"sub\">{} event(s), newest first (UTC).</p>\n",
+        report.events.len()
+    );
+    if !report.events.is_empty() {
+        html.push_str(
+            "<table>\n<tr><th>Time</th><th>Level</th><th>Source</th><th>Message</th></tr>\n",
+        );
+        for event in report.events.iter().rev() {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"{}\">{}</td><td>{}</td><td>{}</td></tr>",
+                crate::replay::format_utc(event.unix_ms),
+                severity_class(&event.severity),
+                severity_class(&event.severity),
+                escape(&event.source),
+                escape(&event.message)
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</section>\n");
+}
+
+fn meter_card(html: &mut String, verdict: &HealthVerdict) {
+    let (class, label) = status_class(&verdict.status);
+    let _ = writeln!(
+        html,
+        "<div class=\"card\"><div>{}</div><div class=\"verdict {class}\">{label}</div><div>{}</div>{}</div>",
+        verdict.label,
+        escape(&verdict.summary),
+        verdict
+            .percent
+            .map(|percent| meter(percent, &verdict.status))
+            .unwrap_or_default()
+    );
+}
+
+/// A horizontal percentage bar.
+fn meter(percent: f64, status: &HealthStatus) -> String {
+    let percent = percent.clamp(0.0, 100.0);
+    format!(
+        "<svg class=\"meter\" viewBox=\"0 0 100 10\" preserveAspectRatio=\"none\" role=\"img\" aria-label=\"{percent:.0}%\">\
+         <title>{percent:.1}%</title><rect class=\"track\" width=\"100\" height=\"10\" rx=\"2\"/>\
+         <rect class=\"fill-{}\" width=\"{percent:.1}\" height=\"10\" rx=\"2\"/></svg>",
+        status_class(status).0
+    )
+}
+
+/// One vertical bar per logical core, colored by the same thresholds as the
+/// TUI's gauges.
+fn core_chart(html: &mut String, per_core: &[f32], limits: Limits) {
+    if per_core.is_empty() {
+        html.push_str("<p class=\"empty\">Per-core usage unavailable.</p>\n");
+        return;
+    }
+    const BAR: usize = 14;
+    const HEIGHT: f64 = 80.0;
+    let width = per_core.len() * BAR;
+    let _ = write!(
+        html,
+        "<svg viewBox=\"0 0 {width} {}\" width=\"100%\" style=\"max-width:{}px\" role=\"img\" aria-label=\"Per-core CPU usage\">",
+        HEIGHT + 12.0,
+        (width * 2).max(240)
+    );
+    for (index, usage) in per_core.iter().enumerate() {
+        let usage = (*usage as f64).clamp(0.0, 100.0);
+        let height = HEIGHT * usage / 100.0;
+        let x = index * BAR + 2;
+        let _ = write!(
+            html,
+            "<g><title>Core {index}: {usage:.1}%</title>\
+             <rect class=\"track\" x=\"{x}\" y=\"0\" width=\"{}\" height=\"{HEIGHT}\"/>\
+             <rect class=\"fill-{}\" x=\"{x}\" y=\"{:.1}\" width=\"{}\" height=\"{height:.1}\"/></g>",
+            BAR - 4,
+            status_class(&HealthStatus::from_limits(usage, limits)).0,
+            HEIGHT - height,
+            BAR - 4
+        );
+        if per_core.len() <= 32 || index % 4 == 0 {
+            let _ = write!(
+                html,
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{index}</text>",
+                x + (BAR - 4) / 2,
+                HEIGHT + 10.0
+            );
+        }
+    }
+    html.push_str("</svg>\n");
+}
+
+fn row(html: &mut String, label: &str, value: &str) {
+    let _ = writeln!(html, "<tr><th>{label}</th><td>{}</td></tr>", escape(value));
+}
+
+fn optional(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "-".into())
+}
+
+fn status_class(status: &HealthStatus) -> (&'static str, &'static str) {
+    match status {
+        HealthStatus::Good => ("good", "Good"),
+        HealthStatus::Warning => ("warning", "Needs attention"),
+        HealthStatus::Critical => ("critical", "Critical"),
+        HealthStatus::Unknown => ("unknown", "Unknown"),
+    }
+}
+
+fn severity_class(severity: &WarningSeverity) -> &'static str {
+    match severity {
+        WarningSeverity::Info => "info",
+        WarningSeverity::Warning => "warning",
+        WarningSeverity::Error => "error",
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::drivers::{DeviceCategory, DeviceInfo, DeviceStatus, DriverScanStatus};
+    use crate::collectors::{DiagnosticWarning, SystemSnapshot};
+
+    fn fixture() -> SystemSnapshot {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.system.hostname = "desk-42".into();
+        snapshot.system.os_name = "Linux".into();
+        snapshot.cpu.per_core_usage = vec![12.0, 96.0];
+        snapshot.warnings.push(DiagnosticWarning {
+            source: "Disk".into(),
+            message: "Drive <C:> is 97% full".into(),
+            severity: WarningSeverity::Error,
+        });
+        snapshot.drivers.scan_status = DriverScanStatus::Success;
+        snapshot.drivers.audio.push(DeviceInfo {
+            name: "Realtek Audio".into(),
+            driver_version: "6.0".into(),
+            driver_date: "2024-01-01".into(),
+            status: DeviceStatus::Error("Code 10".into()),
+            category: DeviceCategory::Audio,
+            extra: String::new(),
+        });
+        snapshot
+    }
+
+    #[test]
+    fn redacted_reports_list_their_redactions_and_never_reference_external_assets() {
+        let report = DiagnosticReport::from_snapshot(&fixture(), false);
+        let html = render(&report, 1_700_000_000_000);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("Redacted before export"));
+        assert!(html.contains("Computer name"));
+        assert!(html.contains("<code>network_diagnostics.*_addr</code>"));
+        assert!(!html.contains("desk-42"));
+        assert!(html.contains("generated 2023-11-14 22:13:20 UTC"));
+        for external in ["http://", "https://", "<script", "<link", "src=", "url("] {
+            assert!(!html.contains(external), "found {external}");
+        }
+    }
+
+    #[test]
+    fn warnings_and_attention_devices_are_highlighted_and_escaped() {
+        let report = DiagnosticReport::from_snapshot(&fixture(), false);
+        let html = render(&report, 0);
+
+        assert!(html.contains("<li class=\"error\"><span class=\"source\">Disk</span>Drive &lt;C:&gt; is 97% full</li>"));
+        assert!(html.contains("Devices needing attention (1)"));
+        assert!(html.contains("Not working properly"));
+        assert!(html.contains("Error: Code 10"));
+        assert!(html.contains("1 device(s) need attention"));
+        assert!(html.contains("Core 1: 96.0%"));
+        assert!(html.contains("id=\"view-user\" checked"));
+        assert!(html.contains("class=\"tech-only\""));
+    }
+
+    #[test]
+    fn sensitive_reports_say_so_instead_of_listing_redactions() {
+        let report = DiagnosticReport::from_snapshot(&fixture(), true);
+        let html = render(&report, 0);
+
+        assert!(html.contains("Contains sensitive values"));
+        assert!(!html.contains("Redacted before export"));
+        assert!(html.contains("desk-42"));
+    }
+}
@@ -1,13 +1,33 @@
 pub mod app;
+pub mod baseline;
+pub mod check;
 pub mod cli;
 pub mod collectors;
+pub mod daemon;
+pub mod diff;
 pub mod error;
+pub mod events;
+pub mod export;
 pub mod gui;
+pub mod health;
 pub mod history;
+pub mod html_report;
+pub mod live;
+pub mod metrics_history;
 pub mod migrate;
 pub mod observation;
+pub mod push;
+pub mod record;
+pub mod replay;
 pub mod report;
+pub mod serve;
+pub mod session_stats;
 pub mod settings;
+pub mod ticket;
+pub mod topics;
 pub mod types;
 pub mod ui;
 pub mod update;
+pub mod warning_log;
+pub mod watch;
+pub mod webhook;
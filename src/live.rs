@@ -0,0 +1,208 @@
+//! Headless live collection at the TUI's cadence.
+//!
+//! Noninteractive commands that watch the machine over time use this instead
+//! of inventing their own schedule, so they cost the same as an open TUI: the
+//! same refresh intervals, and the same background driver, connectivity, and
+//! disk-health scans that never block a fast refresh. When metrics history is
+//! enabled in settings they also record it, exactly as the TUI does.
+
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Interval, MissedTickBehavior};
+
+use crate::app::{REFRESH_DIAG, REFRESH_FAST, REFRESH_HEALTH, REFRESH_MEDIUM, REFRESH_SLOW};
+use crate::collectors::disk_health::DiskHealthData;
+use crate::collectors::drivers::{DriverData, DriverScanStatus};
+use crate::collectors::network_diag::NetworkDiagData;
+use crate::collectors::{DiagnosticWarning, SystemSnapshot, WarningSeverity};
+use crate::metrics_history::MetricsRecorder;
+
+/// Which refresh a call to [`LiveCollector::refresh`] performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Refresh {
+    /// CPU, memory, network, and processes (every second)
+    Fast,
+    /// Disks, GPU, and thermals
+    Slow,
+    /// Connection tables
+    Medium,
+    /// Connectivity check started in the background
+    Diagnostics,
+    /// Disk-health scan started in the background
+    Health,
+}
+
+pub struct LiveCollector {
+    pub snapshot: SystemSnapshot,
+    fast: Interval,
+    slow: Interval,
+    medium: Interval,
+    diag: Interval,
+    health: Interval,
+    driver_scan: Option<JoinHandle<DriverData>>,
+    connectivity_check: Option<JoinHandle<(NetworkDiagData, Vec<DiagnosticWarning>)>>,
+    disk_health_scan: Option<JoinHandle<(DiskHealthData, Vec<DiagnosticWarning>)>>,
+    metrics_recorder: Option<MetricsRecorder>,
+}
+
+impl LiveCollector {
+    /// Perform the TUI's initial collection and start its background scans.
+    /// Must be called from within a Tokio runtime.
+    pub fn start() -> Self {
+        let mut snapshot = SystemSnapshot::default();
+        let shared = crate::settings::load_shared();
+        shared.print_problems();
+        snapshot.apply_settings(&shared);
+        let metrics_recorder = shared.metrics_history.and_then(|history| {
+            match MetricsRecorder::open_default(history) {
+                Ok(recorder) => Some(recorder),
+                Err(error) => {
+                    snapshot
+                        .warnings
+                        .push(crate::metrics_history::stopped_warning(&error));
+                    None
+                }
+            }
+        });
+        snapshot.refresh_static();
+        snapshot.refresh_fast();
+        snapshot.refresh_slow();
+        snapshot.refresh_connections();
+        snapshot.drivers.scan_status = DriverScanStatus::Scanning;
+
+        let mut collector = Self {
+            snapshot,
+            fast: ticker(REFRESH_FAST),
+            slow: ticker(REFRESH_SLOW),
+            medium: ticker(REFRESH_MEDIUM),
+            diag: ticker(REFRESH_DIAG),
+            health: ticker(REFRESH_HEALTH),
+            driver_scan: Some(tokio::task::spawn_blocking(
+                crate::collectors::drivers::collect,
+            )),
+            connectivity_check: None,
+            disk_health_scan: None,
+            metrics_recorder,
+        };
+        // The initial collection above stands in for every interval's first
+        // immediate tick.
+        collector.fast.reset();
+        collector.slow.reset();
+        collector.medium.reset();
+        collector.start_connectivity_check();
+        collector.start_disk_health_scan();
+        collector.record_metrics_history();
+        collector
+    }
+
+    /// Wait for the next due refresh, perform it, and fold in any background
+    /// scans that have finished.
+    pub async fn refresh(&mut self) -> Refresh {
+        let refresh = tokio::select! {
+            _ = self.fast.tick() => {
+                self.snapshot.refresh_fast();
+                self.record_metrics_history();
+                Refresh::Fast
+            }
+            _ = self.slow.tick() => {
+                self.snapshot.refresh_slow();
+                Refresh::Slow
+            }
+            _ = self.medium.tick() => {
+                self.snapshot.refresh_connections();
+                Refresh::Medium
+            }
+            _ = self.diag.tick() => {
+                if self.connectivity_check.is_none() {
+                    self.start_connectivity_check();
+                }
+                Refresh::Diagnostics
+            }
+            _ = self.health.tick() => {
+                if self.disk_health_scan.is_none() {
+                    self.start_disk_health_scan();
+                }
+                Refresh::Health
+            }
+        };
+        self.poll_background_scans().await;
+        self.snapshot.record_events(crate::record::unix_ms());
+        refresh
+    }
+
+    /// A failing store stops recording for the run; the snapshot's warnings
+    /// say why.
+    fn record_metrics_history(&mut self) {
+        let Some(recorder) = self.metrics_recorder.as_mut() else {
+            return;
+        };
+        if let Err(error) = recorder.observe(&self.snapshot, crate::metrics_history::unix_secs()) {
+            self.metrics_recorder = None;
+            self.snapshot
+                .warnings
+                .push(crate::metrics_history::stopped_warning(&error));
+        }
+    }
+
+    fn start_connectivity_check(&mut self) {
+        self.connectivity_check = Some(tokio::task::spawn_blocking(
+            crate::collectors::network_diag::collect_connectivity,
+        ));
+    }
+
+    fn start_disk_health_scan(&mut self) {
+        self.disk_health_scan = Some(tokio::task::spawn_blocking(
+            crate::collectors::disk_health::collect,
+        ));
+    }
+
+    /// Same merge rules as the TUI: each finished scan replaces its data and
+    /// its own warning source, never another collector's warnings.
+    async fn poll_background_scans(&mut self) {
+        if let Some(handle) = take_finished(&mut self.driver_scan) {
+            if let Ok(data) = handle.await {
+                self.snapshot.warnings.retain(|w| w.source != "Drivers");
+                if let DriverScanStatus::ScanFailed(ref message) = data.scan_status {
+                    self.snapshot.warnings.push(DiagnosticWarning {
+                        source: "Drivers".into(),
+                        message: message.clone(),
+                        severity: WarningSeverity::Warning,
+                    });
+                }
+                self.snapshot.drivers = data;
+            }
+        }
+
+        if let Some(handle) = take_finished(&mut self.connectivity_check) {
+            if let Ok((data, warnings)) = handle.await {
+                self.snapshot.network_diag.gateway = data.gateway;
+                self.snapshot.network_diag.dns = data.dns;
+                self.snapshot.network_diag.internet = data.internet;
+                self.snapshot.warnings.retain(|w| w.source != "Network");
+                self.snapshot.warnings.extend(warnings);
+            }
+        }
+
+        if let Some(handle) = take_finished(&mut self.disk_health_scan) {
+            if let Ok((data, warnings)) = handle.await {
+                self.snapshot.disk_health = data;
+                self.snapshot.warnings.retain(|w| w.source != "Disk Health");
+                self.snapshot.warnings.extend(warnings);
+            }
+        }
+    }
+}
+
+fn ticker(period: std::time::Duration) -> Interval {
+    let mut ticker = interval(period);
+    // A stalled writer should not cause a burst of catch-up refreshes.
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    ticker
+}
+
+fn take_finished<T>(slot: &mut Option<JoinHandle<T>>) -> Option<JoinHandle<T>> {
+    if slot.as_ref().is_some_and(JoinHandle::is_finished) {
+        slot.take()
+    } else {
+        None
+    }
+}
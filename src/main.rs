@@ -1,14 +1,21 @@
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use sd_300::{
     app::App,
-    cli::{Cli, Command},
+    cli::{Cli, Command, SnapshotFormat},
     error::Result,
     types::DiagnosticMode,
 };
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let args: Vec<_> = std::env::args_os().collect();
+    let cli = match Cli::try_parse_from(&args) {
+        Ok(cli) => cli,
+        Err(error) if sd_300::check::is_invocation(&args) => {
+            std::process::exit(sd_300::check::usage_error(error));
+        }
+        Err(error) => error.exit(),
+    };
 
     // Enable UTF-8 output on Windows
     #[cfg(windows)]
@@ -36,9 +43,24 @@ async fn main() -> Result<()> {
                 std::process::exit(exit_code);
             }
             Command::Snapshot(args) => {
+                if args.max_chars.is_some() && args.format == Some(SnapshotFormat::Html) {
+                    Cli::command()
+                        .error(
+                            clap::error::ErrorKind::ArgumentConflict,
+                            "--max-chars applies to the markdown and text summaries, not html",
+                        )
+                        .exit();
+                }
                 let report =
-                    sd_300::report::DiagnosticReport::collect(args.include_sensitive).await;
-                sd_300::report::print_snapshot(&report, args.json)?;
+                    sd_300::report::DiagnosticReport::collect(args.report.include_sensitive).await;
+                match args.format {
+                    Some(format) => sd_300::report::write_snapshot(
+                        &report,
+                        format,
+                        args.max_chars.map(|chars| chars as usize),
+                    )?,
+                    None => sd_300::report::print_snapshot(&report, args.report.json)?,
+                }
                 return Ok(());
             }
             Command::Capabilities(args) => {
@@ -47,6 +69,49 @@ async fn main() -> Result<()> {
                 sd_300::report::print_capabilities(&report, args.json)?;
                 return Ok(());
             }
+            Command::Record(args) => {
+                sd_300::record::run(&args).await?;
+                return Ok(());
+            }
+            Command::Watch(args) => {
+                sd_300::watch::run(&args).await?;
+                return Ok(());
+            }
+            Command::History(args) => {
+                sd_300::metrics_history::run(&args)?;
+                return Ok(());
+            }
+            Command::Diff(args) => {
+                sd_300::diff::run(&args)?;
+                return Ok(());
+            }
+            Command::Baseline(args) => {
+                sd_300::baseline::run(&args).await?;
+                return Ok(());
+            }
+            Command::Serve(args) => {
+                sd_300::serve::run(&args).await?;
+                return Ok(());
+            }
+            Command::Push(args) => {
+                sd_300::push::run(&args).await?;
+                return Ok(());
+            }
+            Command::Daemon(args) => {
+                sd_300::daemon::run(&args).await?;
+                return Ok(());
+            }
+            Command::Check(args) => {
+                std::process::exit(sd_300::check::run(&args).await);
+            }
+            Command::Export(args) => {
+                sd_300::export::run(&args).await?;
+                return Ok(());
+            }
+            Command::Schema => {
+                sd_300::report::print_schema()?;
+                return Ok(());
+            }
             Command::Gui => {
                 std::process::exit(sd_300::gui::launch());
             }
@@ -108,18 +173,27 @@ async fn main() -> Result<()> {
         original_hook(panic_info);
     }));
 
-    // Run the app
-    let mut terminal = ratatui::init();
     let mut app = App::new(initial_mode);
-    if initial_mode.is_none() {
+    if let Some(path) = cli.replay.as_deref() {
+        // Load before taking over the terminal so a bad file reports plainly.
+        app.replay = Some(sd_300::replay::Player::open(path)?);
+    } else if initial_mode.is_none() {
         app.cargo_gui_completion_notice = sd_300::update::cargo_gui_completion_needed();
     }
+
+    // Run the app
+    let mut terminal = ratatui::init();
     let result = app.run(&mut terminal).await;
 
     // Restore terminal
     ratatui::restore();
 
-    result
+    result?;
+    if let Some(stats) = &app.session_stats {
+        let summary = stats.summary(sd_300::record::unix_ms());
+        sd_300::session_stats::finish(&summary, cli.summary_on_exit.as_deref())?;
+    }
+    Ok(())
 }
 
 /// Enable UTF-8 console output on Windows
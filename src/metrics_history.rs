@@ -0,0 +1,732 @@
+//! Long-term local metrics history and `sd300 history`.
+//!
+//! When `shared.metrics_history` is present in settings, the terminal UI and
+//! every headless live collector (`daemon`, `serve`, `push`, `record`, and
+//! `watch`) append one sample of a few key metrics every ten seconds to a
+//! per-day file, so a long-running `sd300 daemon` keeps history while no
+//! terminal is open. When several run at once, a lock file in the store lets
+//! only one of them record. Once a UTC day is over its samples are compacted
+//! into five-minute rollups (minimum, average, maximum) that are kept for the
+//! configured retention; full-rate files are kept for the current and
+//! previous day only.
+//!
+//! Every line ends in a checksum and is written with a single append, so a
+//! crash or power loss leaves at worst one torn line, which readers skip.
+//! Rollup files are staged under a temporary name and renamed into place.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::{HistoryArgs, HistoryFormat, HistoryMetric};
+use crate::collectors::{DiagnosticWarning, SystemSnapshot, WarningSeverity};
+use crate::error::{AppError, Result};
+use crate::settings::{self, MetricsHistorySettings};
+
+pub const SAMPLE_INTERVAL_SECS: u64 = 10;
+pub const WARNING_SOURCE: &str = "History";
+pub const ROLLUP_SECS: u64 = 300;
+/// Full-rate files kept: the current UTC day and the one before it.
+const RAW_DAYS: u64 = 2;
+const DAY_SECS: u64 = 86_400;
+/// Held by the one process recording into the store.
+const LOCK_FILE: &str = ".recorder.lock";
+const SPARK_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Serialize, Deserialize)]
+struct RawLine {
+    t: u64,
+    #[serde(flatten)]
+    values: BTreeMap<String, f64>,
+}
+
+/// Each value is `[min, avg, max, samples]`.
+#[derive(Serialize, Deserialize)]
+struct RollupLine {
+    t: u64,
+    #[serde(flatten)]
+    values: BTreeMap<String, [f64; 4]>,
+}
+
+/// A span of history: one raw sample, one rollup, or a query bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Point {
+    pub start_unix: u64,
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+    pub samples: u64,
+}
+
+impl Point {
+    fn merge(&mut self, other: &Point) {
+        let samples = self.samples + other.samples;
+        self.avg = (self.avg * self.samples as f64 + other.avg * other.samples as f64)
+            / samples.max(1) as f64;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.samples = samples;
+    }
+}
+
+/// Appends samples for live collection and keeps the store compacted and
+/// pruned.
+///
+/// Several live modes can run at once, such as the TUI beside `sd300
+/// daemon`. Only the process holding the store's lock file records and
+/// compacts; the others keep trying each slot and take over once it exits.
+pub struct MetricsRecorder {
+    dir: PathBuf,
+    retention_days: u64,
+    last_slot: Option<u64>,
+    maintained_day: Option<u64>,
+    lock: File,
+    holds_lock: bool,
+}
+
+impl MetricsRecorder {
+    pub fn open(dir: PathBuf, settings: MetricsHistorySettings) -> Result<Self> {
+        fs::create_dir_all(&dir)?;
+        settings::restrict_directory(&dir).map_err(AppError::platform)?;
+        let lock = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join(LOCK_FILE))?;
+        Ok(Self {
+            dir,
+            retention_days: settings.retention_days,
+            last_slot: None,
+            maintained_day: None,
+            lock,
+            holds_lock: false,
+        })
+    }
+
+    /// Whether this process is the store's writer, taking the lock if it is
+    /// free.
+    fn acquire(&mut self) -> Result<bool> {
+        if !self.holds_lock {
+            match self.lock.try_lock() {
+                Ok(()) => self.holds_lock = true,
+                Err(TryLockError::WouldBlock) => {}
+                Err(TryLockError::Error(error)) => return Err(error.into()),
+            }
+        }
+        Ok(self.holds_lock)
+    }
+
+    /// Open the store in the per-user metrics-history directory.
+    pub fn open_default(settings: MetricsHistorySettings) -> Result<Self> {
+        let dir = settings::metrics_history_dir().map_err(AppError::platform)?;
+        Self::open(dir, settings)
+    }
+
+    /// Record `snapshot` if no sample exists yet for the ten-second slot
+    /// containing `now`, compacting and pruning first on a new UTC day.
+    pub fn observe(&mut self, snapshot: &SystemSnapshot, now: u64) -> Result<()> {
+        let slot = now / SAMPLE_INTERVAL_SECS;
+        if self.last_slot == Some(slot) {
+            return Ok(());
+        }
+        self.last_slot = Some(slot);
+        if !self.acquire()? {
+            return Ok(());
+        }
+        let day = now / DAY_SECS;
+        if self.maintained_day != Some(day) {
+            maintain(&self.dir, day, self.retention_days)?;
+            self.maintained_day = Some(day);
+        }
+        append_sample(&self.dir, now, sample_values(snapshot))
+    }
+}
+
+/// The warning shown once a failing store stops recording for the session.
+pub fn stopped_warning(error: &AppError) -> DiagnosticWarning {
+    DiagnosticWarning {
+        source: WARNING_SOURCE.into(),
+        message: format!("Metrics history stopped: {error}"),
+        severity: WarningSeverity::Warning,
+    }
+}
+
+/// The recorded metrics, keyed like [`HistoryMetric::key`]. Metrics the
+/// machine does not report are left out rather than recorded as zero.
+pub fn sample_values(snapshot: &SystemSnapshot) -> BTreeMap<String, f64> {
+    let mut values = BTreeMap::new();
+    let percent = |used: u64, total: u64| used as f64 / total as f64 * 100.0;
+    values.insert("cpu".into(), snapshot.cpu.total_usage as f64);
+    if snapshot.memory.total_bytes > 0 {
+        values.insert(
+            "memory".into(),
+            percent(snapshot.memory.used_bytes, snapshot.memory.total_bytes),
+        );
+    }
+    if snapshot.memory.swap_total_bytes > 0 {
+        values.insert(
+            "swap".into(),
+            percent(
+                snapshot.memory.swap_used_bytes,
+                snapshot.memory.swap_total_bytes,
+            ),
+        );
+    }
+    if snapshot.gpu.telemetry_available {
+        values.insert("gpu".into(), snapshot.gpu.utilization_percent as f64);
+    }
+    if let Some(temperature) = snapshot.thermals.cpu_temp {
+        values.insert("cpu_temp".into(), temperature);
+    }
+    if let Some(temperature) = snapshot.thermals.gpu_temp {
+        values.insert("gpu_temp".into(), temperature);
+    }
+    values.insert(
+        "net_down".into(),
+        snapshot.network.total_download_rate as f64,
+    );
+    values.insert("net_up".into(), snapshot.network.total_upload_rate as f64);
+    if let Some(io) = snapshot
+        .disk_health
+        .drives
+        .first()
+        .and_then(|drive| drive.io_stats.as_ref())
+    {
+        values.insert("disk_read".into(), io.read_bytes_per_sec as f64);
+        values.insert("disk_write".into(), io.write_bytes_per_sec as f64);
+    }
+    values.retain(|_, value| value.is_finite());
+    values
+}
+
+pub fn append_sample(dir: &Path, now: u64, values: BTreeMap<String, f64>) -> Result<()> {
+    let line = encode_line(&RawLine { t: now, values })?;
+    let mut options = OpenOptions::new();
+    options.read(true).create(true).append(true);
+    settings::restrict_file_options(&mut options);
+    let mut file = options.open(dir.join(day_file("raw", now / DAY_SECS)))?;
+    // A line torn by an earlier crash is terminated first so that it cannot
+    // swallow this sample.
+    let torn = file.metadata()?.len() > 0 && {
+        let mut last = [0u8];
+        file.seek(SeekFrom::End(-1))?;
+        file.read_exact(&mut last)?;
+        last[0] != b'\n'
+    };
+    let line = if torn { format!("\n{line}") } else { line };
+    // One write per line: with O_APPEND a crash can tear only this line.
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Compact finished days into rollups, then drop full-rate files older than
+/// [`RAW_DAYS`] and anything older than the retention period.
+pub fn maintain(dir: &Path, today: u64, retention_days: u64) -> Result<()> {
+    let mut raw_days = Vec::new();
+    let mut rollup_days = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with(".rollup-") && name.ends_with(".tmp") {
+            let _ = fs::remove_file(dir.join(name.as_ref()));
+        } else if let Some(day) = parse_day_file(&name, "raw") {
+            raw_days.push(day);
+        } else if let Some(day) = parse_day_file(&name, "rollup") {
+            rollup_days.push(day);
+        }
+    }
+
+    let oldest_kept = today.saturating_sub(retention_days);
+    for &day in raw_days.iter().filter(|&&day| day < today) {
+        if day >= oldest_kept && !rollup_days.contains(&day) {
+            compact_day(dir, day)?;
+            rollup_days.push(day);
+        }
+        if day + RAW_DAYS <= today || day < oldest_kept {
+            remove_if_present(&dir.join(day_file("raw", day)))?;
+        }
+    }
+    for &day in rollup_days.iter().filter(|&&day| day < oldest_kept) {
+        remove_if_present(&dir.join(day_file("rollup", day)))?;
+    }
+    Ok(())
+}
+
+fn compact_day(dir: &Path, day: u64) -> Result<()> {
+    let mut buckets: BTreeMap<u64, BTreeMap<String, Point>> = BTreeMap::new();
+    for line in read_lines::<RawLine>(&dir.join(day_file("raw", day)))? {
+        let start = line.t - line.t % ROLLUP_SECS;
+        let bucket = buckets.entry(start).or_default();
+        for (metric, value) in line.values {
+            let point = Point {
+                start_unix: start,
+                min: value,
+                avg: value,
+                max: value,
+                samples: 1,
+            };
+            bucket
+                .entry(metric)
+                .and_modify(|existing| existing.merge(&point))
+                .or_insert(point);
+        }
+    }
+
+    let mut contents = String::new();
+    for (start, metrics) in buckets {
+        contents.push_str(&encode_line(&RollupLine {
+            t: start,
+            values: metrics
+                .into_iter()
+                .map(|(metric, p)| (metric, [p.min, p.avg, p.max, p.samples as f64]))
+                .collect(),
+        })?);
+    }
+
+    settings::write_private_atomic(
+        &dir.join(day_file("rollup", day)),
+        &format!(".rollup-{day}"),
+        contents.as_bytes(),
+    )
+    .map_err(AppError::platform)
+}
+
+/// Every stored span of `metric` starting within `[from, to)`, oldest first.
+/// Full-rate samples are used wherever they still exist.
+pub fn query(dir: &Path, metric: &str, from: u64, to: u64) -> Result<Vec<Point>> {
+    let mut points = Vec::new();
+    if from >= to {
+        return Ok(points);
+    }
+    for day in from / DAY_SECS..=(to - 1) / DAY_SECS {
+        let raw = dir.join(day_file("raw", day));
+        if raw.is_file() {
+            points.extend(read_lines::<RawLine>(&raw)?.into_iter().filter_map(|line| {
+                let value = *line.values.get(metric)?;
+                Some(Point {
+                    start_unix: line.t,
+                    min: value,
+                    avg: value,
+                    max: value,
+                    samples: 1,
+                })
+            }));
+        } else {
+            let rollup = dir.join(day_file("rollup", day));
+            points.extend(
+                read_lines::<RollupLine>(&rollup)?
+                    .into_iter()
+                    .filter_map(|line| {
+                        let [min, avg, max, samples] = *line.values.get(metric)?;
+                        Some(Point {
+                            start_unix: line.t,
+                            min,
+                            avg,
+                            max,
+                            samples: samples as u64,
+                        })
+                    }),
+            );
+        }
+    }
+    points.retain(|point| (from..to).contains(&point.start_unix));
+    points.sort_by_key(|point| point.start_unix);
+    Ok(points)
+}
+
+/// Merge `points` into `count` equal buckets spanning `[from, to)`; buckets
+/// with no recorded data are `None`.
+pub fn resample(points: &[Point], from: u64, to: u64, count: usize) -> Vec<Option<Point>> {
+    let span = to.saturating_sub(from).max(1);
+    let width = span.div_ceil(count.max(1) as u64);
+    let mut buckets: Vec<Option<Point>> = vec![None; span.div_ceil(width) as usize];
+    for point in points {
+        let index = ((point.start_unix.saturating_sub(from)) / width) as usize;
+        let Some(bucket) = buckets.get_mut(index) else {
+            continue;
+        };
+        match bucket {
+            Some(existing) => existing.merge(point),
+            None => {
+                *bucket = Some(Point {
+                    start_unix: from + index as u64 * width,
+                    ..*point
+                })
+            }
+        }
+    }
+    buckets
+}
+
+pub fn run(args: &HistoryArgs) -> Result<()> {
+    let now = unix_secs();
+    let from = args.from.resolve(now);
+    let to = args.to.map_or(now, |to| to.resolve(now));
+    if from >= to {
+        return Err(AppError::platform(
+            "the history range is empty: --from must be earlier than --to",
+        ));
+    }
+    let dir = settings::metrics_history_dir().map_err(AppError::platform)?;
+    let points = query(&dir, args.metric.key(), from, to)?;
+    let count = args.points.map_or(
+        match args.format {
+            HistoryFormat::Sparkline => 60,
+            HistoryFormat::Table | HistoryFormat::Json => 24,
+        },
+        usize::from,
+    );
+    let buckets = resample(&points, from, to, count);
+
+    if points.is_empty() && args.format != HistoryFormat::Json {
+        eprintln!(
+            "No {} history between {} and {} UTC. Once `shared.metrics_history` is set in {}, history is recorded while the terminal UI or a live mode such as `sd300 daemon`, `serve`, or `push` runs.",
+            args.metric.key(),
+            crate::replay::format_utc(from * 1000),
+            crate::replay::format_utc(to * 1000),
+            settings::settings_path().unwrap_or_default().display()
+        );
+        return Ok(());
+    }
+
+    let mut out = std::io::stdout().lock();
+    match args.format {
+        HistoryFormat::Json => {
+            let json = serde_json::json!({
+                "metric": args.metric.key(),
+                "unit": unit(args.metric),
+                "from_unix": from,
+                "to_unix": to,
+                "bucket_seconds": buckets_width(from, to, count),
+                "points": buckets.iter().flatten().collect::<Vec<_>>(),
+            });
+            let text = serde_json::to_string_pretty(&json).map_err(|error| {
+                AppError::platform(format!("JSON serialization failed: {error}"))
+            })?;
+            writeln!(out, "{text}")?;
+        }
+        HistoryFormat::Table => {
+            writeln!(
+                out,
+                "{:<19}  {:>10}  {:>10}  {:>10}  {:>7}",
+                "TIME (UTC)", "MIN", "AVG", "MAX", "SAMPLES"
+            )?;
+            for bucket in buckets.iter().flatten() {
+                writeln!(
+                    out,
+                    "{:<19}  {:>10}  {:>10}  {:>10}  {:>7}",
+                    crate::replay::format_utc(bucket.start_unix * 1000),
+                    format_value(args.metric, bucket.min),
+                    format_value(args.metric, bucket.avg),
+                    format_value(args.metric, bucket.max),
+                    bucket.samples
+                )?;
+            }
+        }
+        HistoryFormat::Sparkline => {
+            let total = points.iter().fold(None, |total: Option<Point>, point| {
+                Some(total.map_or(*point, |mut total| {
+                    total.merge(point);
+                    total
+                }))
+            });
+            writeln!(
+                out,
+                "{} {} .. {} UTC",
+                args.metric.key(),
+                crate::replay::format_utc(from * 1000),
+                crate::replay::format_utc(to * 1000)
+            )?;
+            writeln!(out, "{}", sparkline(&buckets))?;
+            if let Some(total) = total {
+                writeln!(
+                    out,
+                    "min {}  avg {}  max {}",
+                    format_value(args.metric, total.min),
+                    format_value(args.metric, total.avg),
+                    format_value(args.metric, total.max)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Bucket averages scaled between the lowest and highest average; gaps in
+/// the recording are blank.
+pub fn sparkline(buckets: &[Option<Point>]) -> String {
+    let averages = buckets.iter().flatten().map(|point| point.avg);
+    let low = averages.clone().fold(f64::INFINITY, f64::min);
+    let high = averages.fold(f64::NEG_INFINITY, f64::max);
+    buckets
+        .iter()
+        .map(|bucket| match bucket {
+            None => ' ',
+            Some(point) if high > low => {
+                let level = (point.avg - low) / (high - low) * (SPARK_LEVELS.len() - 1) as f64;
+                SPARK_LEVELS[level.round() as usize]
+            }
+            Some(_) => SPARK_LEVELS[0],
+        })
+        .collect()
+}
+
+fn buckets_width(from: u64, to: u64, count: usize) -> u64 {
+    (to - from).div_ceil(count.max(1) as u64)
+}
+
+fn unit(metric: HistoryMetric) -> &'static str {
+    match metric {
+        HistoryMetric::Cpu | HistoryMetric::Memory | HistoryMetric::Swap | HistoryMetric::Gpu => {
+            "percent"
+        }
+        HistoryMetric::CpuTemp | HistoryMetric::GpuTemp => "celsius",
+        HistoryMetric::NetDown
+        | HistoryMetric::NetUp
+        | HistoryMetric::DiskRead
+        | HistoryMetric::DiskWrite => "bytes_per_second",
+    }
+}
+
+fn format_value(metric: HistoryMetric, value: f64) -> String {
+    match unit(metric) {
+        "percent" => format!("{value:.1}%"),
+        "celsius" => format!("{value:.1}°C"),
+        _ => crate::ui::common::format_throughput(value.max(0.0) as u64),
+    }
+}
+
+fn encode_line<T: Serialize>(line: &T) -> Result<String> {
+    let payload = serde_json::to_string(line)
+        .map_err(|error| AppError::platform(format!("JSON serialization failed: {error}")))?;
+    Ok(format!("{payload}\t{:08x}\n", checksum(&payload)))
+}
+
+/// Lines whose checksum does not match (a torn final append) are skipped; a
+/// missing file reads as empty.
+fn read_lines<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Vec<T>> {
+    let text = match fs::read(path) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error.into()),
+    };
+    Ok(text
+        .lines()
+        .filter_map(|line| {
+            let (payload, sum) = line.rsplit_once('\t')?;
+            (u32::from_str_radix(sum, 16).ok()? == checksum(payload))
+                .then(|| serde_json::from_str(payload).ok())
+                .flatten()
+        })
+        .collect())
+}
+
+/// 32-bit FNV-1a.
+fn checksum(payload: &str) -> u32 {
+    payload.bytes().fold(0x811c_9dc5, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+fn day_file(kind: &str, day: u64) -> String {
+    let (year, month, day) = crate::replay::civil_from_days(day);
+    format!("{kind}-{year:04}-{month:02}-{day:02}.log")
+}
+
+fn parse_day_file(name: &str, kind: &str) -> Option<u64> {
+    let date = name.strip_prefix(kind)?.strip_prefix('-')?;
+    match crate::cli::parse_time(date.strip_suffix(".log")?).ok()? {
+        crate::cli::TimeSpec::UnixSeconds(secs) => Some(secs / DAY_SECS),
+        crate::cli::TimeSpec::Ago(_) => None,
+    }
+}
+
+fn remove_if_present(path: &Path) -> Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(error.into()),
+        _ => Ok(()),
+    }
+}
+
+pub fn unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u64 = 20_379; // 2025-10-18
+
+    fn values(cpu: f64) -> BTreeMap<String, f64> {
+        BTreeMap::from([("cpu".to_string(), cpu), ("memory".to_string(), 50.0)])
+    }
+
+    #[test]
+    fn torn_appends_are_skipped_and_later_samples_still_read() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let start = DAY * DAY_SECS;
+        append_sample(temp.path(), start, values(10.0)).expect("first sample");
+        let raw = temp.path().join(day_file("raw", DAY));
+        let mut file = OpenOptions::new().append(true).open(&raw).expect("raw");
+        file.write_all(b"{\"t\":12,\"cpu\":9").expect("torn line");
+        drop(file);
+        append_sample(temp.path(), start + 10, values(30.0)).expect("second sample");
+        append_sample(temp.path(), start + 20, values(20.0)).expect("third sample");
+
+        let points = query(temp.path(), "cpu", start, start + DAY_SECS).expect("query");
+        let cpu: Vec<f64> = points.iter().map(|point| point.avg).collect();
+        assert_eq!(cpu, [10.0, 30.0, 20.0]);
+        assert!(query(temp.path(), "gpu", start, start + DAY_SECS)
+            .expect("absent metric")
+            .is_empty());
+    }
+
+    #[test]
+    fn finished_days_compact_into_rollups_and_expire_with_retention() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let dir = temp.path();
+        for day in [DAY - 10, DAY - 2, DAY - 1, DAY] {
+            for step in 0..60 {
+                let now = day * DAY_SECS + step * SAMPLE_INTERVAL_SECS;
+                append_sample(dir, now, values(step as f64)).expect("sample");
+            }
+        }
+        fs::write(dir.join(".rollup-1-2.tmp"), b"partial").expect("stale staging file");
+
+        maintain(dir, DAY, 7).expect("maintenance");
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .expect("list")
+            .map(|entry| {
+                entry
+                    .expect("entry")
+                    .file_name()
+                    .into_string()
+                    .expect("utf8")
+            })
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "raw-2025-10-17.log",
+                "raw-2025-10-18.log",
+                "rollup-2025-10-16.log",
+                "rollup-2025-10-17.log",
+            ]
+        );
+
+        // Ten minutes of 10 s samples make two five-minute rollups.
+        let start = (DAY - 2) * DAY_SECS;
+        let rollups = query(dir, "cpu", start, start + DAY_SECS).expect("rollups");
+        assert_eq!(rollups.len(), 2);
+        assert_eq!(
+            rollups[0],
+            Point {
+                start_unix: start,
+                min: 0.0,
+                avg: 14.5,
+                max: 29.0,
+                samples: 30,
+            }
+        );
+        assert_eq!(rollups[1].start_unix, start + ROLLUP_SECS);
+
+        // Yesterday still answers from full-rate samples.
+        let yesterday = (DAY - 1) * DAY_SECS;
+        assert_eq!(
+            query(dir, "cpu", yesterday, yesterday + DAY_SECS)
+                .expect("raw")
+                .len(),
+            60
+        );
+    }
+
+    #[test]
+    fn resampling_merges_spans_and_leaves_gaps_blank() {
+        let point = |start_unix, avg, samples| Point {
+            start_unix,
+            min: avg,
+            avg,
+            max: avg,
+            samples,
+        };
+        let points = [point(0, 10.0, 1), point(5, 40.0, 3), point(35, 80.0, 2)];
+        let buckets = resample(&points, 0, 40, 4);
+        assert_eq!(buckets.len(), 4);
+        assert_eq!(
+            buckets[0],
+            Some(Point {
+                start_unix: 0,
+                min: 10.0,
+                avg: 32.5,
+                max: 40.0,
+                samples: 4,
+            })
+        );
+        assert_eq!(buckets[1], None);
+        assert_eq!(buckets[3].map(|bucket| bucket.start_unix), Some(30));
+        assert_eq!(sparkline(&buckets), "▁  █");
+    }
+
+    #[test]
+    fn recorder_samples_once_per_slot_and_skips_unreported_metrics() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let mut recorder = MetricsRecorder::open(
+            temp.path().join("history"),
+            MetricsHistorySettings::default(),
+        )
+        .expect("recorder");
+        let snapshot = SystemSnapshot::default();
+        let start = DAY * DAY_SECS;
+        for now in [start, start + 3, start + 9, start + 10] {
+            recorder.observe(&snapshot, now).expect("observe");
+        }
+        let dir = temp.path().join("history");
+        assert_eq!(
+            query(&dir, "cpu", start, start + DAY_SECS)
+                .expect("cpu")
+                .len(),
+            2
+        );
+        assert!(query(&dir, "cpu_temp", start, start + DAY_SECS)
+            .expect("temperature")
+            .is_empty());
+    }
+
+    #[test]
+    fn only_the_lock_holder_records_until_it_exits() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let dir = temp.path().join("history");
+        let open = || MetricsRecorder::open(dir.clone(), MetricsHistorySettings::default());
+        let mut first = open().expect("first recorder");
+        let mut second = open().expect("second recorder");
+        let snapshot = SystemSnapshot::default();
+        let start = DAY * DAY_SECS;
+        let samples = || {
+            query(&dir, "cpu", start, start + DAY_SECS)
+                .expect("cpu")
+                .len()
+        };
+
+        first.observe(&snapshot, start).expect("first observe");
+        second.observe(&snapshot, start).expect("second observe");
+        second
+            .observe(&snapshot, start + 10)
+            .expect("second observe");
+        assert_eq!(samples(), 1, "only the lock holder writes");
+
+        drop(first);
+        second
+            .observe(&snapshot, start + 20)
+            .expect("second observe");
+        assert_eq!(samples(), 2, "the other recorder takes over");
+    }
+}
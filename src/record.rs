@@ -0,0 +1,458 @@
+//! `sd300 record`: a continuous, redacted NDJSON recording.
+//!
+//! Each file starts with a header line and a keyframe carrying every recorded
+//! section. Later frames carry only the sections whose redacted report value
+//! changed since the previous frame, so a quiet machine records little more
+//! than CPU, memory, and process churn. Files rotate by size; every rotated
+//! file starts with its own header and keyframe and can be read on its own.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::cli::{RecordArgs, ReportSection};
+use crate::error::{AppError, Result};
+use crate::live::LiveCollector;
+use crate::report::{DiagnosticReport, PrivacyMetadata};
+
+pub const FORMAT: &str = "sd300-recording";
+pub const FORMAT_VERSION: u32 = 1;
+
+const MIB: u64 = 1024 * 1024;
+
+/// First line of every recording file.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingHeader {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub format: &'static str,
+    pub format_version: u32,
+    pub schema_version: u32,
+    pub product_version: &'static str,
+    pub target_os: &'static str,
+    pub target_arch: &'static str,
+    pub privacy: PrivacyMetadata,
+    pub interval_ms: u64,
+    pub sections: Vec<&'static str>,
+    pub started_unix_ms: u64,
+    pub file_index: u32,
+}
+
+#[derive(Serialize)]
+struct FrameLine<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    sequence: u64,
+    captured_unix_ms: u64,
+    keyframe: bool,
+    sections: &'a Map<String, Value>,
+}
+
+#[derive(Serialize)]
+struct EndLine {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    frames: u64,
+    reason: StopReason,
+    ended_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    Duration,
+    Interrupted,
+    Terminated,
+}
+
+/// Wait for Ctrl+C or, on Unix, SIGTERM from `kill`, `timeout`, or a
+/// service manager.
+async fn stop_signal() -> StopReason {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            return tokio::select! {
+                _ = tokio::signal::ctrl_c() => StopReason::Interrupted,
+                _ = terminate.recv() => StopReason::Terminated,
+            };
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+    StopReason::Interrupted
+}
+
+/// Writes frames as section deltas and rotates files by size.
+pub struct RecordingWriter {
+    base: PathBuf,
+    max_bytes: u64,
+    header: RecordingHeader,
+    file: BufWriter<File>,
+    file_bytes: u64,
+    files: u32,
+    frames: u64,
+    previous: Map<String, Value>,
+    keyframe_due: bool,
+}
+
+impl RecordingWriter {
+    /// Create the first file. Existing recordings are never overwritten.
+    pub fn create(base: PathBuf, max_bytes: u64, header: RecordingHeader) -> Result<Self> {
+        let file = create_new(&base)?;
+        let mut writer = Self {
+            base,
+            max_bytes,
+            header,
+            file,
+            file_bytes: 0,
+            files: 1,
+            frames: 0,
+            previous: Map::new(),
+            keyframe_due: true,
+        };
+        writer.write_header()?;
+        Ok(writer)
+    }
+
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn files(&self) -> u32 {
+        self.files
+    }
+
+    /// Append one frame. `sections` holds the full current value of every
+    /// recorded section; only changed ones are written unless a keyframe is due.
+    pub fn write_frame(
+        &mut self,
+        captured_unix_ms: u64,
+        sections: Map<String, Value>,
+    ) -> Result<()> {
+        let keyframe = std::mem::take(&mut self.keyframe_due);
+        let changed = sections
+            .iter()
+            .filter(|(key, value)| keyframe || self.previous.get(*key) != Some(*value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<Map<_, _>>();
+        self.frames += 1;
+        self.write_line(&FrameLine {
+            kind: "frame",
+            sequence: self.frames,
+            captured_unix_ms,
+            keyframe,
+            sections: &changed,
+        })?;
+        self.file.flush()?;
+        self.previous = sections;
+        if self.file_bytes >= self.max_bytes {
+            self.rotate()?;
+        }
+        Ok(())
+    }
+
+    /// Record why the recording stopped and flush the last file.
+    pub fn finish(mut self, reason: StopReason) -> Result<()> {
+        self.write_line(&EndLine {
+            kind: "end",
+            frames: self.frames,
+            reason,
+            ended_unix_ms: unix_ms(),
+        })?;
+        self.file.flush()?;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        let next = rotated_path(&self.base, self.files);
+        self.file = create_new(&next)?;
+        self.file_bytes = 0;
+        self.header.file_index = self.files;
+        self.files += 1;
+        self.keyframe_due = true;
+        self.write_header()
+    }
+
+    fn write_header(&mut self) -> Result<()> {
+        let header = self.header.clone();
+        self.write_line(&header)?;
+        self.file.flush()?;
+        Ok(())
+    }
+
+    fn write_line(&mut self, value: &impl Serialize) -> Result<()> {
+        let mut line = serde_json::to_vec(value)
+            .map_err(|error| AppError::platform(format!("JSON serialization failed: {error}")))?;
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        self.file_bytes += line.len() as u64;
+        Ok(())
+    }
+}
+
+/// Path of rotated file `index` (1-based) next to `base`:
+/// `capture.ndjson` becomes `capture.1.ndjson`, `capture.2.ndjson`, ...
+pub fn rotated_path(base: &Path, index: u32) -> PathBuf {
+    let stem = base
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = match base.extension() {
+        Some(extension) => format!("{stem}.{index}.{}", extension.to_string_lossy()),
+        None => format!("{stem}.{index}"),
+    };
+    base.with_file_name(name)
+}
+
+fn create_new(path: &Path) -> Result<BufWriter<File>> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map(BufWriter::new)
+        .map_err(|error| {
+            if error.kind() == std::io::ErrorKind::AlreadyExists {
+                AppError::platform(format!(
+                    "{} already exists; choose another --output",
+                    path.display()
+                ))
+            } else {
+                AppError::Io(error)
+            }
+        })
+}
+
+/// The selected top-level sections of a redacted report.
+pub fn report_sections(
+    report: &DiagnosticReport,
+    sections: &[ReportSection],
+) -> Result<Map<String, Value>> {
+    let Value::Object(mut full) = serde_json::to_value(report)
+        .map_err(|error| AppError::platform(format!("JSON serialization failed: {error}")))?
+    else {
+        return Err(AppError::platform("report did not serialize to an object"));
+    };
+    Ok(sections
+        .iter()
+        .filter_map(|section| {
+            let key = section.report_key();
+            full.remove(key).map(|value| (key.to_string(), value))
+        })
+        .collect())
+}
+
+pub fn unix_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis().try_into().unwrap_or(u64::MAX))
+        .unwrap_or(0)
+}
+
+/// The requested sections in report order without repeats; none means all.
+pub fn selected_sections(requested: &[ReportSection]) -> Vec<ReportSection> {
+    let mut sections = if requested.is_empty() {
+        ReportSection::value_variants().to_vec()
+    } else {
+        requested.to_vec()
+    };
+    sections.sort();
+    sections.dedup();
+    sections
+}
+
+pub async fn run(args: &RecordArgs) -> Result<()> {
+    let sections = selected_sections(&args.sections);
+
+    let mut live = LiveCollector::start();
+    let template = DiagnosticReport::from_snapshot(&live.snapshot, false);
+    let header = RecordingHeader {
+        kind: "header",
+        format: FORMAT,
+        format_version: FORMAT_VERSION,
+        schema_version: template.schema_version,
+        product_version: template.product_version,
+        target_os: template.target_os,
+        target_arch: template.target_arch,
+        privacy: template.privacy,
+        interval_ms: args.interval * 1000,
+        sections: sections.iter().map(ReportSection::report_key).collect(),
+        started_unix_ms: unix_ms(),
+        file_index: 0,
+    };
+    let mut writer = RecordingWriter::create(args.output.clone(), args.max_file_mib * MIB, header)?;
+
+    let mut frame_tick = tokio::time::interval(Duration::from_secs(args.interval));
+    frame_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let stop_at = args
+        .duration
+        .map(|duration| tokio::time::Instant::now() + duration);
+    let deadline = async {
+        match stop_at {
+            Some(at) => tokio::time::sleep_until(at).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(deadline);
+    let stop = stop_signal();
+    tokio::pin!(stop);
+
+    eprintln!(
+        "Recording to {} every {}s{}; press Ctrl+C to stop.",
+        args.output.display(),
+        args.interval,
+        args.duration
+            .map(|duration| format!(" for {}s", duration.as_secs()))
+            .unwrap_or_default()
+    );
+    let reason = loop {
+        tokio::select! {
+            _ = live.refresh() => {}
+            _ = frame_tick.tick() => {
+                let report = DiagnosticReport::from_snapshot(&live.snapshot, false);
+                writer.write_frame(unix_ms(), report_sections(&report, &sections)?)?;
+            }
+            _ = &mut deadline => break StopReason::Duration,
+            reason = &mut stop => break reason,
+        }
+    };
+
+    let (frames, files) = (writer.frames(), writer.files());
+    writer.finish(reason)?;
+    eprintln!("Recorded {frames} frame(s) across {files} file(s).");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> RecordingHeader {
+        RecordingHeader {
+            kind: "header",
+            format: FORMAT,
+            format_version: FORMAT_VERSION,
+            schema_version: 1,
+            product_version: "test",
+            target_os: "test",
+            target_arch: "test",
+            privacy: PrivacyMetadata {
+                sensitive_values_included: false,
+                redacted_fields: vec!["system.hostname"],
+            },
+            interval_ms: 1000,
+            sections: vec!["cpu", "drivers"],
+            started_unix_ms: 1,
+            file_index: 0,
+        }
+    }
+
+    fn sections(cpu: f64, drivers: &str) -> Map<String, Value> {
+        let mut map = Map::new();
+        map.insert("cpu".into(), serde_json::json!({ "total_usage": cpu }));
+        map.insert(
+            "drivers".into(),
+            serde_json::json!({ "scan_status": drivers }),
+        );
+        map
+    }
+
+    fn lines(path: &Path) -> Vec<Value> {
+        std::fs::read_to_string(path)
+            .expect("recording should be readable")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("each line should be JSON"))
+            .collect()
+    }
+
+    #[test]
+    fn frames_after_the_keyframe_carry_only_changed_sections() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("capture.ndjson");
+        let mut writer = RecordingWriter::create(path.clone(), u64::MAX, header()).expect("create");
+        writer.write_frame(10, sections(5.0, "Scanning")).unwrap();
+        writer.write_frame(20, sections(7.5, "Scanning")).unwrap();
+        writer.write_frame(30, sections(7.5, "Success")).unwrap();
+        writer.finish(StopReason::Duration).unwrap();
+
+        let lines = lines(&path);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0]["type"], "header");
+        assert_eq!(lines[0]["format"], FORMAT);
+        assert_eq!(lines[1]["keyframe"], true);
+        assert_eq!(lines[1]["sections"].as_object().unwrap().len(), 2);
+        assert_eq!(lines[2]["sequence"], 2);
+        assert_eq!(lines[2]["sections"]["cpu"]["total_usage"], 7.5);
+        assert!(lines[2]["sections"].get("drivers").is_none());
+        assert!(lines[3]["sections"].get("cpu").is_none());
+        assert_eq!(lines[3]["sections"]["drivers"]["scan_status"], "Success");
+        assert_eq!(lines[4]["type"], "end");
+        assert_eq!(lines[4]["frames"], 3);
+        assert_eq!(lines[4]["reason"], "duration");
+    }
+
+    #[test]
+    fn rotation_starts_each_file_with_a_header_and_keyframe() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("capture.ndjson");
+        // Small enough that every frame fills a file.
+        let mut writer = RecordingWriter::create(path.clone(), 64, header()).expect("create");
+        writer.write_frame(10, sections(5.0, "Scanning")).unwrap();
+        writer.write_frame(20, sections(5.0, "Scanning")).unwrap();
+        assert_eq!(writer.files(), 3);
+        writer.finish(StopReason::Interrupted).unwrap();
+
+        let second = lines(&rotated_path(&path, 1));
+        assert_eq!(second[0]["type"], "header");
+        assert_eq!(second[0]["file_index"], 1);
+        assert_eq!(second[1]["keyframe"], true);
+        assert_eq!(second[1]["sequence"], 2);
+        assert_eq!(
+            second[1]["sections"].as_object().unwrap().len(),
+            2,
+            "unchanged sections are repeated in a rotated file's keyframe"
+        );
+        let last = lines(&rotated_path(&path, 2));
+        assert_eq!(last.last().unwrap()["reason"], "interrupted");
+    }
+
+    #[test]
+    fn existing_recordings_are_never_overwritten() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("capture.ndjson");
+        std::fs::write(&path, "keep me").unwrap();
+        assert!(RecordingWriter::create(path.clone(), u64::MAX, header()).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+    }
+
+    #[test]
+    fn rotated_names_keep_the_extension_last() {
+        assert_eq!(
+            rotated_path(Path::new("/tmp/capture.ndjson"), 3),
+            PathBuf::from("/tmp/capture.3.ndjson")
+        );
+        assert_eq!(
+            rotated_path(Path::new("capture"), 1),
+            PathBuf::from("capture.1")
+        );
+    }
+
+    #[test]
+    fn selected_sections_come_from_the_redacted_report() {
+        let mut snapshot = crate::collectors::SystemSnapshot::default();
+        snapshot.system.hostname = "workstation".into();
+        let report = DiagnosticReport::from_snapshot(&snapshot, false);
+        let selected =
+            report_sections(&report, &[ReportSection::System, ReportSection::Cpu]).unwrap();
+        assert_eq!(
+            selected.keys().collect::<Vec<_>>(),
+            ["cpu", "system"],
+            "only requested sections are recorded"
+        );
+        assert_eq!(selected["system"]["hostname"], "[redacted]");
+    }
+}
@@ -0,0 +1,590 @@
+//! `sd300 --replay`: drive the TUI from an `sd300 record` recording.
+//!
+//! Frames are loaded up front and resolved to full section state, with
+//! unchanged sections shared between frames, so seeking is a lookup rather
+//! than a re-read. Only the sections that differ from what is already on
+//! screen are decoded into the snapshot.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::collectors::SystemSnapshot;
+use crate::error::{AppError, Result};
+use crate::record::{rotated_path, FORMAT, FORMAT_VERSION};
+
+/// Playback speeds selectable with `-` / `+`.
+const SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+const NORMAL_SPEED: usize = 2;
+
+type Sections = BTreeMap<String, Arc<Value>>;
+
+/// Sections read by `App::update_fast_history`.
+const HISTORY_SECTIONS: [&str; 6] = ["cpu", "memory", "network", "gpu", "thermals", "disk_health"];
+
+#[derive(Debug, Deserialize)]
+struct HeaderLine {
+    format: String,
+    format_version: u32,
+    file_index: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct FrameLine {
+    sequence: u64,
+    captured_unix_ms: u64,
+    #[serde(default)]
+    keyframe: bool,
+    sections: Map<String, Value>,
+}
+
+/// One recorded frame with every section resolved to its value at that time.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub sequence: u64,
+    pub captured_unix_ms: u64,
+    sections: Sections,
+}
+
+#[derive(Debug, Clone)]
+pub struct Recording {
+    frames: Vec<Frame>,
+}
+
+impl Recording {
+    /// Read a recording and, when given its first file, every rotated file
+    /// that follows it.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut recording = Self { frames: Vec::new() };
+        let mut state = Sections::new();
+        let first = recording.read_file(path, &mut state)?;
+        if first == 0 {
+            let mut index = 1;
+            while rotated_path(path, index).exists() {
+                recording.read_file(&rotated_path(path, index), &mut state)?;
+                index += 1;
+            }
+        }
+        if recording.frames.is_empty() {
+            return Err(AppError::platform(format!(
+                "{} contains no recorded frames",
+                path.display()
+            )));
+        }
+        Ok(recording)
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    /// Append one file's frames, returning its `file_index`.
+    fn read_file(&mut self, path: &Path, state: &mut Sections) -> Result<u32> {
+        let text = std::fs::read_to_string(path).map_err(|error| {
+            AppError::platform(format!("cannot read {}: {error}", path.display()))
+        })?;
+        let invalid = |line: usize, message: String| {
+            AppError::platform(format!("{}:{line}: {message}", path.display()))
+        };
+        let mut lines = text.lines().enumerate().peekable();
+
+        let (_, first) = lines
+            .next()
+            .ok_or_else(|| invalid(1, "empty file, expected a recording header".into()))?;
+        let header: HeaderLine = serde_json::from_str(first)
+            .map_err(|error| invalid(1, format!("invalid recording header: {error}")))?;
+        if header.format != FORMAT {
+            return Err(invalid(1, format!("not an {FORMAT} file")));
+        }
+        if header.format_version > FORMAT_VERSION {
+            return Err(invalid(
+                1,
+                format!(
+                    "recording format version {} is newer than this build supports ({FORMAT_VERSION})",
+                    header.format_version
+                ),
+            ));
+        }
+
+        while let Some((number, line)) = lines.next() {
+            let value: Value = match serde_json::from_str(line) {
+                Ok(value) => value,
+                // A recorder that was killed can leave a partial last line.
+                Err(_) if lines.peek().is_none() && !text.ends_with('\n') => break,
+                Err(error) => return Err(invalid(number + 1, error.to_string())),
+            };
+            // Headers of later files and the end line carry nothing to show.
+            if value.get("type").and_then(Value::as_str) != Some("frame") {
+                continue;
+            }
+            let frame: FrameLine = serde_json::from_value(value)
+                .map_err(|error| invalid(number + 1, format!("invalid frame: {error}")))?;
+            if self
+                .frames
+                .last()
+                .is_some_and(|last| last.sequence >= frame.sequence)
+            {
+                continue;
+            }
+            if frame.keyframe {
+                state.clear();
+            }
+            for (key, value) in frame.sections {
+                state.insert(key, Arc::new(value));
+            }
+            self.frames.push(Frame {
+                sequence: frame.sequence,
+                captured_unix_ms: frame.captured_unix_ms,
+                sections: state.clone(),
+            });
+        }
+        Ok(header.file_index)
+    }
+}
+
+/// Playback position, speed, and the section values currently decoded into
+/// the snapshot.
+pub struct Player {
+    recording: Recording,
+    position: usize,
+    playing: bool,
+    speed: usize,
+    /// Recorded time at the playback head, in Unix milliseconds
+    clock_ms: f64,
+    shown: Sections,
+}
+
+impl Player {
+    pub fn open(path: &Path) -> Result<Self> {
+        Ok(Self::new(Recording::load(path)?))
+    }
+
+    pub fn new(recording: Recording) -> Self {
+        let clock_ms = recording.frames[0].captured_unix_ms as f64;
+        Self {
+            recording,
+            position: 0,
+            playing: true,
+            speed: NORMAL_SPEED,
+            clock_ms,
+            shown: Sections::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.recording.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recording.frames.is_empty()
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn frame(&self) -> &Frame {
+        &self.recording.frames[self.position]
+    }
+
+    pub fn playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn speed(&self) -> f64 {
+        SPEEDS[self.speed]
+    }
+
+    pub fn toggle_play(&mut self) {
+        if !self.playing && self.position + 1 == self.len() {
+            self.jump_to(0);
+        }
+        self.playing = !self.playing;
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = self.speed.saturating_sub(1);
+    }
+
+    /// Pause and move one frame forward or back.
+    pub fn step(&mut self, forward: bool) {
+        self.playing = false;
+        let position = if forward {
+            self.position + 1
+        } else {
+            self.position.saturating_sub(1)
+        };
+        self.jump_to(position);
+    }
+
+    /// Move by recorded time, always at least one frame when one exists.
+    pub fn seek(&mut self, offset: Duration, forward: bool) {
+        let now = self.frame().captured_unix_ms;
+        let offset = u64::try_from(offset.as_millis()).unwrap_or(u64::MAX);
+        let frames = &self.recording.frames;
+        let position = if forward {
+            let target = now.saturating_add(offset);
+            let at_or_before = frames
+                .partition_point(|frame| frame.captured_unix_ms <= target)
+                .saturating_sub(1);
+            at_or_before.max(self.position + 1)
+        } else {
+            let target = now.saturating_sub(offset);
+            let at_or_after = frames.partition_point(|frame| frame.captured_unix_ms < target);
+            at_or_after.min(self.position.saturating_sub(1))
+        };
+        self.jump_to(position);
+    }
+
+    pub fn seek_start(&mut self) {
+        self.jump_to(0);
+    }
+
+    pub fn seek_end(&mut self) {
+        self.jump_to(self.len() - 1);
+    }
+
+    /// Advance the playback clock by wall time scaled by the speed. Playback
+    /// pauses on the last frame.
+    pub fn advance(&mut self, elapsed: Duration) {
+        if !self.playing {
+            return;
+        }
+        self.clock_ms += elapsed.as_secs_f64() * 1000.0 * self.speed();
+        let frames = &self.recording.frames;
+        while self.position + 1 < frames.len()
+            && frames[self.position + 1].captured_unix_ms as f64 <= self.clock_ms
+        {
+            self.position += 1;
+        }
+        if self.position + 1 == frames.len() {
+            self.playing = false;
+        }
+    }
+
+    fn jump_to(&mut self, position: usize) {
+        self.position = position.min(self.len() - 1);
+        self.clock_ms = self.frame().captured_unix_ms as f64;
+    }
+
+    /// Decode frame `index` into `snapshot`, touching only the sections that
+    /// differ from the ones already shown.
+    pub fn apply(&mut self, index: usize, snapshot: &mut SystemSnapshot) {
+        self.apply_matching(index, snapshot, |_| true);
+    }
+
+    /// Like [`Player::apply`], limited to the sections sparklines sample.
+    /// Rebuilding a day of history this way skips decoding process and
+    /// connection tables nobody will see.
+    pub fn apply_history(&mut self, index: usize, snapshot: &mut SystemSnapshot) {
+        self.apply_matching(index, snapshot, |key| HISTORY_SECTIONS.contains(&key));
+    }
+
+    fn apply_matching(
+        &mut self,
+        index: usize,
+        snapshot: &mut SystemSnapshot,
+        wanted: impl Fn(&str) -> bool,
+    ) {
+        let Some(frame) = self.recording.frames.get(index) else {
+            return;
+        };
+        for (key, value) in frame.sections.iter().filter(|(key, _)| wanted(key)) {
+            if self
+                .shown
+                .get(key)
+                .is_some_and(|shown| Arc::ptr_eq(shown, value))
+            {
+                continue;
+            }
+            // A section this build cannot decode keeps its previous value.
+            if decode_section(snapshot, key, value) {
+                self.shown.insert(key.clone(), Arc::clone(value));
+            }
+        }
+    }
+}
+
+fn decode_section(snapshot: &mut SystemSnapshot, key: &str, value: &Value) -> bool {
+    fn set<T: DeserializeOwned>(slot: &mut T, value: &Value) -> bool {
+        match T::deserialize(value) {
+            Ok(decoded) => {
+                *slot = decoded;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    match key {
+        "system" => set(&mut snapshot.system, value),
+        "cpu" => set(&mut snapshot.cpu, value),
+        "memory" => set(&mut snapshot.memory, value),
+        "disk" => set(&mut snapshot.disk, value),
+        "disk_health" => set(&mut snapshot.disk_health, value),
+        "displays" => set(&mut snapshot.displays, value),
+        "gpu" => set(&mut snapshot.gpu, value),
+        "network" => set(&mut snapshot.network, value),
+        "network_diagnostics" => set(&mut snapshot.network_diag, value),
+        "processes" => set(&mut snapshot.processes, value),
+        "thermals" => set(&mut snapshot.thermals, value),
+        "drivers" => set(&mut snapshot.drivers, value),
+        "warnings" => set(&mut snapshot.warnings, value),
+        "events" => set(&mut snapshot.events, value),
+        // Capabilities are derived from the snapshot when a report is built.
+        _ => false,
+    }
+}
+
+/// `YYYY-MM-DD HH:MM:SS` in UTC.
+pub fn format_utc(unix_ms: u64) -> String {
+    let secs = unix_ms / 1000;
+    let (year, month, day) = civil_from_days(secs / 86_400);
+    let secs_of_day = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Days since 1970-01-01 to a proleptic Gregorian date (Howard Hinnant's
+/// `civil_from_days`, restricted to dates after the epoch).
+pub(crate) fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::{RecordingHeader, RecordingWriter, StopReason};
+    use crate::report::PrivacyMetadata;
+
+    fn header() -> RecordingHeader {
+        RecordingHeader {
+            kind: "header",
+            format: FORMAT,
+            format_version: FORMAT_VERSION,
+            schema_version: 1,
+            product_version: "test",
+            target_os: "test",
+            target_arch: "test",
+            privacy: PrivacyMetadata {
+                sensitive_values_included: false,
+                redacted_fields: Vec::new(),
+            },
+            interval_ms: 1000,
+            sections: vec!["cpu", "memory"],
+            started_unix_ms: 0,
+            file_index: 0,
+        }
+    }
+
+    fn sections(cpu: f32, used_bytes: u64) -> Map<String, Value> {
+        let cpu = crate::collectors::cpu::CpuData {
+            total_usage: cpu,
+            ..Default::default()
+        };
+        let memory = crate::collectors::memory::MemoryData {
+            total_bytes: 1000,
+            used_bytes,
+            ..Default::default()
+        };
+        let mut map = Map::new();
+        map.insert("cpu".into(), serde_json::to_value(cpu).unwrap());
+        map.insert("memory".into(), serde_json::to_value(memory).unwrap());
+        map
+    }
+
+    /// Frames at 0s, 1s, ... with CPU `cpu[i]` and constant memory, rotated
+    /// after `max_bytes`.
+    fn record(path: &Path, cpu: &[f32], max_bytes: u64) {
+        let mut writer = RecordingWriter::create(path.to_path_buf(), max_bytes, header()).unwrap();
+        for (index, usage) in cpu.iter().enumerate() {
+            writer
+                .write_frame(1_000_000 + index as u64 * 1000, sections(*usage, 500))
+                .unwrap();
+        }
+        writer.finish(StopReason::Duration).unwrap();
+    }
+
+    #[test]
+    fn delta_frames_resolve_to_full_state_across_rotated_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.ndjson");
+        record(&path, &[10.0, 20.0, 30.0, 40.0], 256);
+        assert!(rotated_path(&path, 1).exists(), "fixture should rotate");
+
+        let recording = Recording::load(&path).unwrap();
+        let frames = recording.frames();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(
+            frames
+                .iter()
+                .map(|frame| frame.sequence)
+                .collect::<Vec<_>>(),
+            [1, 2, 3, 4]
+        );
+        for frame in frames {
+            assert!(frame.sections.contains_key("memory"));
+        }
+        assert_eq!(frames[3].sections["memory"]["used_bytes"], 500);
+        assert_eq!(frames[3].sections["cpu"]["total_usage"], 40.0);
+    }
+
+    #[test]
+    fn truncated_last_line_is_ignored_but_other_damage_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.ndjson");
+        record(&path, &[10.0, 20.0], u64::MAX);
+        let complete = std::fs::read_to_string(&path).unwrap();
+        let without_end = complete.trim_end().rsplit_once('\n').unwrap().0;
+        std::fs::write(&path, format!("{without_end}\n{{\"type\":\"fra")).unwrap();
+        assert_eq!(Recording::load(&path).unwrap().frames().len(), 2);
+
+        std::fs::write(&path, format!("{without_end}\nnot json\n")).unwrap();
+        assert!(Recording::load(&path).is_err());
+
+        std::fs::write(
+            &path,
+            "{\"format\":\"other\",\"format_version\":1,\"file_index\":0}\n",
+        )
+        .unwrap();
+        assert!(Recording::load(&path).is_err());
+    }
+
+    #[test]
+    fn playback_follows_recorded_time_at_the_selected_speed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.ndjson");
+        record(&path, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0], u64::MAX);
+        let mut player = Player::open(&path).unwrap();
+        assert!(player.playing());
+
+        player.advance(Duration::from_millis(900));
+        assert_eq!(player.position(), 0);
+        player.advance(Duration::from_millis(100));
+        assert_eq!(player.position(), 1);
+
+        player.faster();
+        assert_eq!(player.speed(), 2.0);
+        player.advance(Duration::from_secs(1));
+        assert_eq!(player.position(), 3);
+
+        player.advance(Duration::from_secs(60));
+        assert_eq!(player.position(), 5);
+        assert!(!player.playing(), "playback pauses on the last frame");
+        player.toggle_play();
+        assert_eq!(player.position(), 0, "playing again restarts from the top");
+
+        player.step(true);
+        assert!(!player.playing());
+        assert_eq!(player.position(), 1);
+        player.step(false);
+        player.step(false);
+        assert_eq!(player.position(), 0);
+        for _ in 0..10 {
+            player.slower();
+        }
+        assert_eq!(player.speed(), 0.25);
+    }
+
+    #[test]
+    fn seeking_moves_by_recorded_time_and_at_least_one_frame() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.ndjson");
+        record(&path, &[0.0; 30], u64::MAX);
+        let mut player = Player::open(&path).unwrap();
+
+        player.seek(Duration::from_secs(10), true);
+        assert_eq!(player.position(), 10);
+        player.seek(Duration::from_millis(10), true);
+        assert_eq!(player.position(), 11);
+        player.seek(Duration::from_secs(4), false);
+        assert_eq!(player.position(), 7);
+        player.seek(Duration::from_secs(600), true);
+        assert_eq!(player.position(), 29);
+        player.seek_start();
+        assert_eq!(player.position(), 0);
+        player.seek(Duration::from_secs(10), false);
+        assert_eq!(player.position(), 0);
+        player.seek_end();
+        assert_eq!(player.position(), 29);
+    }
+
+    #[test]
+    fn applying_frames_decodes_recorded_sections_into_the_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.ndjson");
+        record(&path, &[12.5, 80.0], u64::MAX);
+        let mut player = Player::open(&path).unwrap();
+        let frames = player.recording.frames();
+        assert!(
+            Arc::ptr_eq(&frames[0].sections["memory"], &frames[1].sections["memory"]),
+            "unchanged sections are shared rather than copied"
+        );
+        let mut snapshot = SystemSnapshot::default();
+
+        player.apply(0, &mut snapshot);
+        assert_eq!(snapshot.cpu.total_usage, 12.5);
+        assert_eq!(snapshot.memory.used_bytes, 500);
+        player.apply(1, &mut snapshot);
+        assert_eq!(snapshot.cpu.total_usage, 80.0);
+        player.apply(0, &mut snapshot);
+        assert_eq!(
+            snapshot.cpu.total_usage, 12.5,
+            "seeking back restores values"
+        );
+    }
+
+    #[test]
+    fn every_recorded_report_section_decodes_back_into_a_snapshot() {
+        use clap::ValueEnum;
+
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.refresh_static();
+        snapshot.refresh_fast();
+        snapshot.refresh_slow();
+        let report = crate::report::DiagnosticReport::from_snapshot(&snapshot, false);
+        let sections =
+            crate::record::report_sections(&report, crate::cli::ReportSection::value_variants())
+                .unwrap();
+        let mut decoded = SystemSnapshot::default();
+        for (key, value) in &sections {
+            assert_eq!(
+                decode_section(&mut decoded, key, value),
+                key != "capabilities",
+                "section {key} should round-trip"
+            );
+        }
+        assert_eq!(decoded.cpu.core_count, snapshot.cpu.core_count);
+        assert_eq!(decoded.processes.list.len(), snapshot.processes.list.len());
+    }
+
+    #[test]
+    fn formats_recorded_timestamps_in_utc() {
+        assert_eq!(format_utc(0), "1970-01-01 00:00:00");
+        assert_eq!(format_utc(951_782_400_000), "2000-02-29 00:00:00");
+        assert_eq!(format_utc(1_792_357_460_029), "2026-10-18 21:04:20");
+    }
+}
@@ -1,13 +1,30 @@
+use std::io::Write;
+
+use schemars::JsonSchema;
 use serde::Serialize;
 
+use crate::cli::SnapshotFormat;
 use crate::collectors::disk_health::DiskHealthData;
 use crate::collectors::drivers::{DriverData, DriverScanStatus};
-use crate::collectors::network_diag::NetworkDiagData;
+use crate::collectors::network_diag::{ConnectivityResult, NetworkDiagData};
 use crate::collectors::{DiagnosticWarning, SystemSnapshot, WarningSeverity};
 use crate::error::{AppError, Result};
+use crate::events::Event;
+use crate::health::HealthExplanation;
 use crate::observation::Observation;
+use crate::settings::ThresholdSettings;
+use crate::types::HealthStatus;
+use crate::ui::common::{plain_language_cpu, plain_language_percent, plain_language_temp};
+
+/// Version of the `snapshot --json` layout, published in `sd300 schema`.
+///
+/// Adding a field, an enum variant, or a capability does not change it;
+/// readers must ignore keys they do not know. Removing or renaming a field,
+/// changing its type or units, or changing what a redacted value looks like
+/// bumps it, and the old layout's golden contract stays in `tests/fixtures`.
+pub const SCHEMA_VERSION: u32 = 1;
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DiagnosticReport {
     pub schema_version: u32,
     pub product: &'static str,
@@ -29,15 +46,32 @@ pub struct DiagnosticReport {
     pub drivers: DriverData,
     pub capabilities: Vec<CapabilityRecord>,
     pub warnings: Vec<DiagnosticWarning>,
+    pub events: Vec<Event>,
+    /// Limits the statuses and threshold warnings were rated against.
+    pub thresholds: ThresholdSettings,
+    /// Overall health and the reasons each subsystem gave for it.
+    pub health: HealthExplanation,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct PrivacyMetadata {
     pub sensitive_values_included: bool,
     pub redacted_fields: Vec<&'static str>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Plain-language name for a `redacted_fields` entry.
+pub fn redacted_field_label(field: &str) -> &str {
+    match field {
+        "system.hostname" => "Computer name",
+        "disk_health.drives[].serial" => "Drive serial numbers",
+        "network.interfaces[].mac_address" => "Network hardware (MAC) addresses",
+        "network.interfaces[].ip_addresses" => "Network IP addresses",
+        "network_diagnostics.*_addr" => "Gateway, DNS, and connection addresses",
+        _ => field,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct CapabilityRecord {
     pub id: &'static str,
     #[serde(flatten)]
@@ -47,6 +81,9 @@ pub struct CapabilityRecord {
 impl DiagnosticReport {
     pub async fn collect(include_sensitive: bool) -> Self {
         let mut snapshot = SystemSnapshot::default();
+        let shared = crate::settings::load_shared();
+        shared.print_problems();
+        snapshot.apply_settings(&shared);
         snapshot.refresh_static();
         snapshot.refresh_fast();
         tokio::time::sleep(std::time::Duration::from_millis(250)).await;
@@ -120,7 +157,7 @@ impl DiagnosticReport {
     pub fn from_snapshot(snapshot: &SystemSnapshot, include_sensitive: bool) -> Self {
         let capabilities = capabilities_for(snapshot);
         let mut report = Self {
-            schema_version: 1,
+            schema_version: SCHEMA_VERSION,
             product: "SD-300",
             product_version: env!("CARGO_PKG_VERSION"),
             target_os: std::env::consts::OS,
@@ -153,6 +190,9 @@ impl DiagnosticReport {
             drivers: snapshot.drivers.clone(),
             capabilities,
             warnings: snapshot.warnings.clone(),
+            events: snapshot.events.to_vec(),
+            thresholds: snapshot.thresholds.clone(),
+            health: snapshot.health(),
         };
         if !include_sensitive {
             report.redact();
@@ -171,7 +211,11 @@ impl DiagnosticReport {
                 *address = "[redacted]".into();
             }
         }
-        self.network_diagnostics.gateway.target = "[redacted]".into();
+        // An empty target means the probe has not run; keep it empty so
+        // readers can still tell unchecked from unreachable.
+        if !self.network_diagnostics.gateway.target.is_empty() {
+            self.network_diagnostics.gateway.target = "[redacted]".into();
+        }
         self.network_diagnostics.dns.resolved_ip = self
             .network_diagnostics
             .dns
@@ -380,6 +424,202 @@ pub fn print_snapshot(report: &DiagnosticReport, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// One plain-language verdict from the User-mode health panel.
+#[derive(Debug, Clone)]
+pub struct HealthVerdict {
+    pub label: &'static str,
+    pub status: HealthStatus,
+    pub summary: String,
+    pub percent: Option<f64>,
+}
+
+/// The User-mode health panel's verdicts for a finished report, in display
+/// order: processor, memory, storage, graphics, network, temperature, devices.
+pub fn health_verdicts(report: &DiagnosticReport) -> Vec<HealthVerdict> {
+    let verdict = |label, status, summary: String, percent| HealthVerdict {
+        label,
+        status,
+        summary,
+        percent,
+    };
+    let thresholds = &report.thresholds;
+    let mut verdicts = Vec::with_capacity(7);
+
+    let cpu = report.cpu.total_usage as f64;
+    verdicts.push(verdict(
+        "Processor",
+        HealthStatus::from_limits(cpu, thresholds.cpu),
+        plain_language_cpu(report.cpu.total_usage).into(),
+        Some(cpu),
+    ));
+    let memory = report.memory.usage_percent();
+    verdicts.push(verdict(
+        "Memory",
+        HealthStatus::from_limits(memory, thresholds.memory),
+        plain_language_percent(memory, "memory"),
+        Some(memory),
+    ));
+    verdicts.push(match report.disk.partitions.first() {
+        Some(partition) => {
+            let used = partition.usage_percent();
+            verdict(
+                "Storage",
+                HealthStatus::from_limits(used, thresholds.disk_for(partition)),
+                plain_language_percent(used, "storage"),
+                Some(used),
+            )
+        }
+        None => verdict(
+            "Storage",
+            HealthStatus::Unknown,
+            "No drives reported".into(),
+            None,
+        ),
+    });
+    verdicts.push(if report.gpu.telemetry_available {
+        let gpu = report.gpu.utilization_percent as f64;
+        verdict(
+            "Graphics",
+            HealthStatus::from_limits(gpu, thresholds.gpu),
+            plain_language_cpu(report.gpu.utilization_percent).into(),
+            Some(gpu),
+        )
+    } else {
+        verdict(
+            "Graphics",
+            HealthStatus::Unknown,
+            if report.gpu.available {
+                "Detected; utilization unavailable"
+            } else {
+                "Inventory unavailable"
+            }
+            .into(),
+            None,
+        )
+    });
+
+    let connected = report.network.interfaces.iter().any(|item| item.is_up);
+    let internet = &report.network_diagnostics.internet;
+    verdicts.push(if !connected {
+        verdict(
+            "Network",
+            HealthStatus::Warning,
+            "Disconnected".into(),
+            None,
+        )
+    } else if probe_checked(internet) && !internet.reachable {
+        verdict(
+            "Network",
+            HealthStatus::Warning,
+            "Connected, but the internet is unreachable".into(),
+            None,
+        )
+    } else {
+        verdict("Network", HealthStatus::Good, "Connected".into(), None)
+    });
+
+    let thermals = &report.thermals;
+    verdicts.push(match (thermals.cpu_temp, thermals.gpu_temp) {
+        (Some(temp), _) => verdict(
+            "Temperature",
+            HealthStatus::from_limits(temp, thresholds.cpu_temperature),
+            format!(
+                "Processor {} ({temp:.0}°C)",
+                plain_language_temp(temp).to_ascii_lowercase()
+            ),
+            None,
+        ),
+        (None, Some(temp)) => verdict(
+            "Temperature",
+            HealthStatus::from_limits(temp, thresholds.gpu_temperature),
+            format!(
+                "Graphics {} ({temp:.0}°C)",
+                plain_language_temp(temp).to_ascii_lowercase()
+            ),
+            None,
+        ),
+        (None, None) => verdict(
+            "Temperature",
+            HealthStatus::Unknown,
+            "Temperature unavailable".into(),
+            None,
+        ),
+    });
+
+    let issues = report.drivers.attention_devices().count();
+    verdicts.push(match report.drivers.scan_status {
+        DriverScanStatus::Success if issues == 0 => verdict(
+            "Devices",
+            HealthStatus::Good,
+            "All devices working".into(),
+            None,
+        ),
+        DriverScanStatus::Success => verdict(
+            "Devices",
+            HealthStatus::Warning,
+            format!("{issues} device(s) need attention"),
+            None,
+        ),
+        _ => verdict(
+            "Devices",
+            HealthStatus::Unknown,
+            "Devices not checked".into(),
+            None,
+        ),
+    });
+    verdicts
+}
+
+/// Probes count as checked once they have a target; unchecked probes report
+/// unreachable without having been tried.
+pub fn probe_checked(probe: &ConnectivityResult) -> bool {
+    !probe.target.is_empty()
+}
+
+/// Write the snapshot to stdout in one of the `--format` alternatives.
+/// `max_chars` limits the ticket summaries.
+pub fn write_snapshot(
+    report: &DiagnosticReport,
+    format: SnapshotFormat,
+    max_chars: Option<usize>,
+) -> Result<()> {
+    let now = crate::record::unix_ms();
+    let rendered = match format {
+        SnapshotFormat::Html => crate::html_report::render(report, now),
+        SnapshotFormat::Markdown => {
+            crate::ticket::render(report, crate::ticket::Style::Markdown, now, max_chars)
+        }
+        SnapshotFormat::Text => {
+            crate::ticket::render(report, crate::ticket::Style::Text, now, max_chars)
+        }
+    };
+    std::io::stdout().write_all(rendered.as_bytes())?;
+    Ok(())
+}
+
+/// JSON Schema (draft 2020-12) for the report `snapshot --json` prints.
+pub fn schema() -> serde_json::Value {
+    let mut schema = schemars::schema_for!(DiagnosticReport).to_value();
+    schema["title"] = "SD-300 diagnostic report".into();
+    schema["description"] = format!(
+        "Output of `sd300 snapshot --json`, schema_version {SCHEMA_VERSION}. \
+         Fields may be added without a version change; readers should ignore \
+         unknown keys."
+    )
+    .into();
+    schema["properties"]["schema_version"]["const"] = SCHEMA_VERSION.into();
+    schema
+}
+
+pub fn print_schema() -> Result<()> {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&schema())
+            .map_err(|error| AppError::platform(format!("JSON serialization failed: {error}")))?
+    );
+    Ok(())
+}
+
 pub fn print_capabilities(report: &DiagnosticReport, json: bool) -> Result<()> {
     if json {
         println!(
@@ -427,6 +667,76 @@ mod tests {
             .all(|drive| drive.serial.is_none() || drive.serial.as_deref() == Some("[redacted]")));
     }
 
+    fn assert_matches_schema(report: &DiagnosticReport) {
+        let validator =
+            jsonschema::validator_for(&schema()).expect("published schema should compile");
+        let instance = serde_json::to_value(report).unwrap();
+        let errors: Vec<String> = validator
+            .iter_errors(&instance)
+            .map(|error| format!("{}: {error}", error.instance_path()))
+            .collect();
+        assert!(errors.is_empty(), "{errors:#?}");
+    }
+
+    #[tokio::test]
+    async fn live_reports_match_the_published_schema() {
+        let sensitive = DiagnosticReport::collect(true).await;
+        assert_matches_schema(&sensitive);
+        let mut redacted = sensitive.clone();
+        redacted.redact();
+        assert_matches_schema(&redacted);
+    }
+
+    #[test]
+    fn data_carrying_variants_match_the_published_schema() {
+        use crate::collectors::drivers::{DeviceCategory, DeviceInfo, DeviceStatus};
+        use crate::collectors::network_diag::{ConnectionInfo, ConnectionState, Protocol};
+        use crate::events::EventKind;
+
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.drivers.usb.push(DeviceInfo {
+            name: "Hub".into(),
+            driver_version: "1.0".into(),
+            driver_date: String::new(),
+            status: DeviceStatus::Degraded("Code 43".into()),
+            category: DeviceCategory::Usb,
+            extra: String::new(),
+        });
+        snapshot.drivers.scan_status = DriverScanStatus::Success;
+        snapshot
+            .network_diag
+            .active_connections
+            .push(ConnectionInfo {
+                protocol: Protocol::Udp,
+                local_addr: "127.0.0.1".into(),
+                local_port: 53,
+                remote_addr: "*".into(),
+                remote_port: 0,
+                state: ConnectionState::Unknown("BOUND".into()),
+                pid: None,
+                process_name: None,
+            });
+        snapshot.warnings.push(DiagnosticWarning {
+            source: "Drivers".into(),
+            message: "Hub needs attention".into(),
+            severity: WarningSeverity::Warning,
+        });
+        snapshot.record_events(1_000);
+        let mut report = DiagnosticReport::from_snapshot(&snapshot, false);
+        report.events.push(Event {
+            unix_ms: 2_000,
+            kind: EventKind::DeviceStatusChanged,
+            severity: WarningSeverity::Warning,
+            source: "Drivers".into(),
+            message: "Hub: OK -> Degraded".into(),
+        });
+        assert_matches_schema(&report);
+
+        let mut newer = serde_json::to_value(&report).unwrap();
+        newer["schema_version"] = (SCHEMA_VERSION + 1).into();
+        assert!(!jsonschema::is_valid(&schema(), &newer));
+    }
+
     #[test]
     fn report_schema_serializes_observation_states() {
         let capability = capability(
@@ -0,0 +1,1052 @@
+//! Prometheus and OpenMetrics exposition for `sd300 serve`.
+//!
+//! Collection runs on the TUI's cadence through [`LiveCollector`]; every
+//! refresh publishes a fresh redacted report, and each scrape renders the
+//! latest one. Rendering from the redacted report keeps the endpoint to the
+//! same privacy rules as `snapshot --json`: no hostname, addresses, or drive
+//! serials ever reach a label. The HTTP side is deliberately minimal: one
+//! `GET` per connection, `/metrics` only, no keep-alive.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::cli::ServeArgs;
+use crate::collectors::disk::PartitionInfo;
+use crate::collectors::disk_health::{DiskHealthStatus, DriveHealth};
+use crate::collectors::drivers::DriverScanStatus;
+use crate::collectors::WarningSeverity;
+use crate::error::{AppError, Result};
+use crate::live::LiveCollector;
+use crate::observation::ObservationStatus;
+use crate::report::DiagnosticReport;
+use crate::settings;
+use crate::warning_log::WarningForwarder;
+use crate::webhook::Notifier;
+
+/// A client gets this long to send its request head.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Request heads larger than this are refused.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+type Latest = Arc<RwLock<Arc<DiagnosticReport>>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Prometheus,
+    OpenMetrics,
+}
+
+impl Format {
+    /// OpenMetrics when the scraper asks for it, as Prometheus does by
+    /// default; the classic text format otherwise.
+    fn negotiate(accept: Option<&str>) -> Self {
+        match accept {
+            Some(accept) if accept.contains("application/openmetrics-text") => Self::OpenMetrics,
+            _ => Self::Prometheus,
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Self::Prometheus => PROMETHEUS_CONTENT_TYPE,
+            Self::OpenMetrics => OPENMETRICS_CONTENT_TYPE,
+        }
+    }
+}
+
+pub async fn run(args: &ServeArgs) -> Result<()> {
+    let mut forwarder = WarningForwarder::from_args(&args.warning_log)?;
+    let mut notifier = Notifier::from_settings();
+    if let Some(path) = &args.textfile {
+        return run_textfile(
+            path,
+            Duration::from_secs(args.interval),
+            forwarder,
+            notifier,
+        )
+        .await;
+    }
+    let listener = TcpListener::bind(args.listen).await.map_err(|error| {
+        AppError::platform(format!("cannot listen on {}: {error}", args.listen))
+    })?;
+    let address = listener.local_addr()?;
+
+    let mut live = LiveCollector::start();
+    let latest: Latest = Arc::new(RwLock::new(Arc::new(DiagnosticReport::from_snapshot(
+        &live.snapshot,
+        false,
+    ))));
+    let server = tokio::spawn(accept_loop(listener, Arc::clone(&latest)));
+
+    eprintln!("Serving metrics on http://{address}/metrics; press Ctrl+C to stop.");
+    if !address.ip().is_loopback() {
+        eprintln!(
+            "The endpoint has no authentication; anyone who can reach {address} can read it."
+        );
+    }
+    let interrupt = tokio::signal::ctrl_c();
+    tokio::pin!(interrupt);
+    loop {
+        tokio::select! {
+            _ = live.refresh() => {
+                if let Some(forwarder) = &mut forwarder {
+                    forwarder.observe(&live.snapshot.warnings);
+                }
+                if let Some(notifier) = &mut notifier {
+                    notifier.observe(&live.snapshot);
+                }
+                let report = Arc::new(DiagnosticReport::from_snapshot(&live.snapshot, false));
+                *latest.write().unwrap_or_else(PoisonError::into_inner) = report;
+            }
+            _ = &mut interrupt => break,
+        }
+    }
+    server.abort();
+    Ok(())
+}
+
+/// Rewrite `path` every `interval` until Ctrl+C. The file is replaced
+/// atomically, so the collector never reads a half-written document; when
+/// this stops, node_exporter's `node_textfile_mtime_seconds` shows its age.
+async fn run_textfile(
+    path: &Path,
+    interval: Duration,
+    mut forwarder: Option<WarningForwarder>,
+    mut notifier: Option<Notifier>,
+) -> Result<()> {
+    if path.extension().and_then(|extension| extension.to_str()) != Some("prom") {
+        return Err(AppError::platform(format!(
+            "{} must end in .prom; the textfile collector ignores other files",
+            path.display()
+        )));
+    }
+
+    let mut live = LiveCollector::start();
+    let mut write_tick = tokio::time::interval(interval);
+    write_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let interrupt = tokio::signal::ctrl_c();
+    tokio::pin!(interrupt);
+
+    eprintln!(
+        "Writing textfile metrics to {} every {}s; press Ctrl+C to stop.",
+        path.display(),
+        interval.as_secs()
+    );
+    let mut write_failing = false;
+    loop {
+        tokio::select! {
+            _ = live.refresh() => {
+                if let Some(forwarder) = &mut forwarder {
+                    forwarder.observe(&live.snapshot.warnings);
+                }
+                if let Some(notifier) = &mut notifier {
+                    notifier.observe(&live.snapshot);
+                }
+            }
+            _ = write_tick.tick() => {
+                let report = DiagnosticReport::from_snapshot(&live.snapshot, false);
+                // A full disk or a permission change should not end the loop:
+                // report the failure once and keep retrying every tick.
+                match settings::write_shared_atomic(path, ".sd300-textfile", render_textfile(&report).as_bytes()) {
+                    Ok(()) if write_failing => {
+                        write_failing = false;
+                        eprintln!("Textfile metrics written to {} again.", path.display());
+                    }
+                    Ok(()) => {}
+                    Err(error) if !write_failing => {
+                        write_failing = true;
+                        eprintln!(
+                            "Could not write textfile metrics to {}: {error}; retrying every {}s.",
+                            path.display(),
+                            interval.as_secs()
+                        );
+                    }
+                    Err(_) => {}
+                }
+            }
+            _ = &mut interrupt => break,
+        }
+    }
+    Ok(())
+}
+
+async fn accept_loop(listener: TcpListener, latest: Latest) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(respond(stream, Arc::clone(&latest)));
+            }
+            // Usually descriptor exhaustion; give in-flight scrapes a moment
+            // to finish rather than spinning.
+            Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+        }
+    }
+}
+
+async fn respond(mut stream: TcpStream, latest: Latest) {
+    let head = match tokio::time::timeout(REQUEST_TIMEOUT, read_head(&mut stream)).await {
+        Ok(Some(head)) => head,
+        _ => return,
+    };
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let target = request_line.next().unwrap_or_default();
+    let path = target.split('?').next().unwrap_or_default();
+    let accept = lines.find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("accept")
+            .then(|| value.trim())
+    });
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET" | "HEAD", "/metrics") => {
+            let format = Format::negotiate(accept);
+            let report = Arc::clone(&latest.read().unwrap_or_else(PoisonError::into_inner));
+            ("200 OK", format.content_type(), render(&report, format))
+        }
+        ("GET" | "HEAD", "/") => (
+            "200 OK",
+            "text/plain; charset=utf-8",
+            "SD-300 metrics are served at /metrics\n".to_string(),
+        ),
+        ("GET" | "HEAD", _) => (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "Not found; metrics are served at /metrics\n".to_string(),
+        ),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain; charset=utf-8",
+            "Only GET and HEAD are supported\n".to_string(),
+        ),
+    };
+    let mut response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    if method != "HEAD" {
+        response.push_str(&body);
+    }
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// The request line and headers, or `None` if the client sent something
+/// that is not a bounded HTTP request head.
+async fn read_head(stream: &mut TcpStream) -> Option<String> {
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await.ok()?;
+        if read == 0 || head.len() + read > MAX_REQUEST_BYTES {
+            return None;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    String::from_utf8(head).ok()
+}
+
+/// Gauge and counter families written in either text format, or kept as
+/// samples for [`samples`].
+struct Exposition {
+    format: Format,
+    text: String,
+    samples: Option<Vec<Sample>>,
+}
+
+/// One series value, for sinks that push metrics rather than expose them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    /// The series name; counters keep their `_total` suffix.
+    pub name: String,
+    pub labels: Vec<(&'static str, String)>,
+    pub value: f64,
+}
+
+type Labels<'a> = Vec<(&'static str, &'a str)>;
+
+impl Exposition {
+    fn gauge<'a>(
+        &mut self,
+        name: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (Labels<'a>, f64)>,
+    ) {
+        self.family(name, name, "gauge", help, samples);
+    }
+
+    /// `name` excludes the `_total` suffix, which OpenMetrics keeps off the
+    /// family name and the classic format keeps on it.
+    fn counter<'a>(
+        &mut self,
+        name: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (Labels<'a>, f64)>,
+    ) {
+        let sample_name = format!("{name}_total");
+        let family = match self.format {
+            Format::Prometheus => sample_name.as_str(),
+            Format::OpenMetrics => name,
+        };
+        self.family(family, &sample_name, "counter", help, samples);
+    }
+
+    fn family<'a>(
+        &mut self,
+        family: &str,
+        sample_name: &str,
+        kind: &str,
+        help: &str,
+        samples: impl IntoIterator<Item = (Labels<'a>, f64)>,
+    ) {
+        let mut samples = samples
+            .into_iter()
+            .filter(|(_, value)| value.is_finite())
+            .peekable();
+        if samples.peek().is_none() {
+            return;
+        }
+        if let Some(collected) = &mut self.samples {
+            let mut seen = HashSet::new();
+            for (labels, value) in samples {
+                if seen.insert(labels.clone()) {
+                    collected.push(Sample {
+                        name: sample_name.to_string(),
+                        labels: labels
+                            .into_iter()
+                            .map(|(name, value)| (name, value.to_string()))
+                            .collect(),
+                        value,
+                    });
+                }
+            }
+            return;
+        }
+        let _ = writeln!(self.text, "# HELP {family} {help}");
+        let _ = writeln!(self.text, "# TYPE {family} {kind}");
+        // Scrapers reject a document that repeats a series, which two
+        // sensors or mounts sharing a label would otherwise produce.
+        let mut seen = HashSet::new();
+        for (labels, value) in samples {
+            let mut series = String::from(sample_name);
+            if !labels.is_empty() {
+                series.push('{');
+                for (index, (name, value)) in labels.iter().enumerate() {
+                    if index > 0 {
+                        series.push(',');
+                    }
+                    let _ = write!(series, "{name}=\"{}\"", escape_label(value));
+                }
+                series.push('}');
+            }
+            if seen.insert(series.clone()) {
+                let _ = writeln!(self.text, "{series} {value}");
+            }
+        }
+    }
+
+    fn finish(mut self) -> String {
+        if self.format == Format::OpenMetrics {
+            self.text.push_str("# EOF\n");
+        }
+        self.text
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn flag(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+const OBSERVATION_STATUSES: [(ObservationStatus, &str); 6] = [
+    (ObservationStatus::Available, "available"),
+    (ObservationStatus::Unavailable, "unavailable"),
+    (ObservationStatus::Unsupported, "unsupported"),
+    (ObservationStatus::PermissionDenied, "permission_denied"),
+    (ObservationStatus::Error, "error"),
+    (ObservationStatus::Contradictory, "contradictory"),
+];
+
+const DRIVE_HEALTH_STATUSES: [(DiskHealthStatus, &str); 4] = [
+    (DiskHealthStatus::Healthy, "healthy"),
+    (DiskHealthStatus::Warning, "warning"),
+    (DiskHealthStatus::Critical, "critical"),
+    (DiskHealthStatus::Unknown, "unknown"),
+];
+
+/// Render `report` as an exposition document. Callers pass a redacted
+/// report; nothing here reads the fields redaction removes.
+pub fn render(report: &DiagnosticReport, format: Format) -> String {
+    let mut out = Exposition {
+        format,
+        text: String::new(),
+        samples: None,
+    };
+    write_metrics(&mut out, report);
+    out.finish()
+}
+
+/// Every series [`render`] writes, as samples. Callers pass a redacted
+/// report.
+pub fn samples(report: &DiagnosticReport) -> Vec<Sample> {
+    let mut out = Exposition {
+        format: Format::Prometheus,
+        text: String::new(),
+        samples: Some(Vec::new()),
+    };
+    write_metrics(&mut out, report);
+    out.samples.unwrap_or_default()
+}
+
+fn write_metrics(out: &mut Exposition, report: &DiagnosticReport) {
+    out.gauge(
+        "sd300_build_info",
+        "SD-300 version and platform.",
+        [(
+            vec![
+                ("version", report.product_version),
+                ("os", report.target_os),
+                ("arch", report.target_arch),
+            ],
+            1.0,
+        )],
+    );
+
+    // CPU
+    let cpu = &report.cpu;
+    out.gauge(
+        "sd300_cpu_usage_percent",
+        "Total CPU utilization.",
+        [(vec![], cpu.total_usage as f64)],
+    );
+    let cores: Vec<String> = (0..cpu.per_core_usage.len().max(cpu.per_core_frequency.len()))
+        .map(|core| core.to_string())
+        .collect();
+    out.gauge(
+        "sd300_cpu_core_usage_percent",
+        "Per-core CPU utilization.",
+        cpu.per_core_usage
+            .iter()
+            .zip(&cores)
+            .map(|(usage, core)| (vec![("core", core.as_str())], *usage as f64)),
+    );
+    out.gauge(
+        "sd300_cpu_core_frequency_hertz",
+        "Per-core CPU clock frequency.",
+        cpu.per_core_frequency
+            .iter()
+            .zip(&cores)
+            .map(|(mhz, core)| (vec![("core", core.as_str())], *mhz as f64 * 1e6)),
+    );
+
+    // Memory
+    let memory = &report.memory;
+    for (name, help, value) in [
+        (
+            "sd300_memory_used_bytes",
+            "Memory in use.",
+            memory.used_bytes,
+        ),
+        (
+            "sd300_memory_total_bytes",
+            "Installed memory visible to the OS.",
+            memory.total_bytes,
+        ),
+        (
+            "sd300_memory_available_bytes",
+            "Memory available to new processes.",
+            memory.available_bytes,
+        ),
+        (
+            "sd300_swap_used_bytes",
+            "Swap in use.",
+            memory.swap_used_bytes,
+        ),
+        (
+            "sd300_swap_total_bytes",
+            "Configured swap.",
+            memory.swap_total_bytes,
+        ),
+    ] {
+        out.gauge(name, help, [(vec![], value as f64)]);
+    }
+
+    // Disk
+    let partitions = &report.disk.partitions;
+    out.gauge(
+        "sd300_filesystem_size_bytes",
+        "Filesystem capacity.",
+        partitions
+            .iter()
+            .map(|partition| (partition_labels(partition), partition.total_bytes as f64)),
+    );
+    out.gauge(
+        "sd300_filesystem_used_bytes",
+        "Filesystem space in use.",
+        partitions
+            .iter()
+            .map(|partition| (partition_labels(partition), partition.used_bytes as f64)),
+    );
+    out.gauge(
+        "sd300_filesystem_available_bytes",
+        "Filesystem space available.",
+        partitions.iter().map(|partition| {
+            (
+                partition_labels(partition),
+                partition.available_bytes as f64,
+            )
+        }),
+    );
+
+    // Disk health
+    drive_health(out, report);
+    let drives = &report.disk_health.drives;
+    out.gauge(
+        "sd300_drive_read_bytes_per_second",
+        "Drive read throughput.",
+        drives.iter().filter_map(|drive| {
+            Some((
+                drive_labels(drive),
+                drive.io_stats.as_ref()?.read_bytes_per_sec as f64,
+            ))
+        }),
+    );
+    out.gauge(
+        "sd300_drive_write_bytes_per_second",
+        "Drive write throughput.",
+        drives.iter().filter_map(|drive| {
+            Some((
+                drive_labels(drive),
+                drive.io_stats.as_ref()?.write_bytes_per_sec as f64,
+            ))
+        }),
+    );
+
+    // Network
+    let interfaces = &report.network.interfaces;
+    out.gauge(
+        "sd300_network_up",
+        "Whether the interface is up.",
+        interfaces.iter().map(|interface| {
+            (
+                vec![("interface", interface.name.as_str())],
+                flag(interface.is_up),
+            )
+        }),
+    );
+    out.counter(
+        "sd300_network_receive_bytes",
+        "Bytes received since the interface came up.",
+        interfaces.iter().map(|interface| {
+            (
+                vec![("interface", interface.name.as_str())],
+                interface.received_bytes as f64,
+            )
+        }),
+    );
+    out.counter(
+        "sd300_network_transmit_bytes",
+        "Bytes transmitted since the interface came up.",
+        interfaces.iter().map(|interface| {
+            (
+                vec![("interface", interface.name.as_str())],
+                interface.transmitted_bytes as f64,
+            )
+        }),
+    );
+    out.gauge(
+        "sd300_network_receive_bytes_per_second",
+        "Receive throughput.",
+        interfaces.iter().map(|interface| {
+            (
+                vec![("interface", interface.name.as_str())],
+                interface.download_rate as f64,
+            )
+        }),
+    );
+    out.gauge(
+        "sd300_network_transmit_bytes_per_second",
+        "Transmit throughput.",
+        interfaces.iter().map(|interface| {
+            (
+                vec![("interface", interface.name.as_str())],
+                interface.upload_rate as f64,
+            )
+        }),
+    );
+    connectivity(out, report);
+
+    // Thermals
+    let thermals = &report.thermals;
+    out.gauge(
+        "sd300_temperature_celsius",
+        "Temperature sensor reading.",
+        thermals.sensors.iter().map(|sensor| {
+            (
+                vec![
+                    ("sensor", sensor.label.as_str()),
+                    ("kind", sensor_kind(sensor.kind)),
+                    ("source", sensor.source.as_str()),
+                ],
+                sensor.temperature,
+            )
+        }),
+    );
+    out.gauge(
+        "sd300_cpu_temperature_celsius",
+        "CPU package temperature.",
+        thermals.cpu_temp.map(|temp| (vec![], temp)),
+    );
+    out.gauge(
+        "sd300_fan_speed_rpm",
+        "Fan speed.",
+        thermals
+            .fans
+            .iter()
+            .map(|fan| (vec![("fan", fan.label.as_str())], fan.rpm as f64)),
+    );
+
+    // GPU
+    let gpu = &report.gpu;
+    if gpu.telemetry_available {
+        let labels = || vec![("gpu", gpu.name.as_str())];
+        out.gauge(
+            "sd300_gpu_utilization_percent",
+            "GPU utilization.",
+            [(labels(), gpu.utilization_percent as f64)],
+        );
+        out.gauge(
+            "sd300_gpu_memory_used_bytes",
+            "GPU memory in use.",
+            [(labels(), gpu.memory_used_mb as f64 * 1024.0 * 1024.0)],
+        );
+        out.gauge(
+            "sd300_gpu_memory_total_bytes",
+            "GPU memory.",
+            [(labels(), gpu.memory_total_mb as f64 * 1024.0 * 1024.0)],
+        );
+        out.gauge(
+            "sd300_gpu_temperature_celsius",
+            "GPU temperature.",
+            gpu.temperature.map(|temp| (labels(), temp)),
+        );
+    }
+
+    // Battery
+    if let Some(battery) = &thermals.battery {
+        out.gauge(
+            "sd300_battery_charge_percent",
+            "Battery charge.",
+            [(vec![], battery.percent)],
+        );
+        out.gauge(
+            "sd300_battery_charging",
+            "Whether the battery is charging.",
+            [(vec![], flag(battery.is_charging))],
+        );
+        out.gauge(
+            "sd300_battery_on_ac",
+            "Whether the machine runs on AC power.",
+            [(vec![], flag(battery.is_on_ac))],
+        );
+        out.gauge(
+            "sd300_battery_cycle_count",
+            "Battery charge cycles.",
+            battery.cycle_count.map(|cycles| (vec![], cycles as f64)),
+        );
+    }
+
+    // Drivers, capabilities, and warnings
+    driver_attention(out, report);
+    capabilities_and_warnings(out, report);
+}
+
+/// The node_exporter textfile subset: what SD-300 knows that node_exporter
+/// does not. Always the classic text format, which is all the collector reads.
+pub fn render_textfile(report: &DiagnosticReport) -> String {
+    let mut out = Exposition {
+        format: Format::Prometheus,
+        text: String::new(),
+        samples: None,
+    };
+    drive_health(&mut out, report);
+    driver_attention(&mut out, report);
+    connectivity(&mut out, report);
+    capabilities_and_warnings(&mut out, report);
+    out.finish()
+}
+
+/// SMART-derived drive health, wear, and error counts.
+fn drive_health(out: &mut Exposition, report: &DiagnosticReport) {
+    let drives = &report.disk_health.drives;
+    out.gauge(
+        "sd300_drive_health",
+        "Drive health state; 1 for the current state.",
+        drives.iter().flat_map(|drive| {
+            DRIVE_HEALTH_STATUSES.iter().map(move |(status, label)| {
+                let mut labels = drive_labels(drive);
+                labels.push(("status", *label));
+                (labels, flag(drive.health_status == *status))
+            })
+        }),
+    );
+    out.gauge(
+        "sd300_drive_temperature_celsius",
+        "Drive temperature.",
+        drives
+            .iter()
+            .filter_map(|drive| Some((drive_labels(drive), drive.temperature_celsius?))),
+    );
+    out.gauge(
+        "sd300_drive_power_on_hours",
+        "Drive power-on hours.",
+        drives
+            .iter()
+            .filter_map(|drive| Some((drive_labels(drive), drive.power_on_hours? as f64))),
+    );
+    out.gauge(
+        "sd300_drive_wear_percent",
+        "Drive wear; 100 is the rated endurance.",
+        drives
+            .iter()
+            .filter_map(|drive| Some((drive_labels(drive), drive.wear_percent? as f64))),
+    );
+    out.counter(
+        "sd300_drive_read_errors",
+        "Uncorrected read errors the drive reports.",
+        drives
+            .iter()
+            .filter_map(|drive| Some((drive_labels(drive), drive.read_errors_total? as f64))),
+    );
+    out.counter(
+        "sd300_drive_write_errors",
+        "Uncorrected write errors the drive reports.",
+        drives
+            .iter()
+            .filter_map(|drive| Some((drive_labels(drive), drive.write_errors_total? as f64))),
+    );
+}
+
+/// Connectivity probe results; a probe that has not run yet is left out.
+fn connectivity(out: &mut Exposition, report: &DiagnosticReport) {
+    let diagnostics = &report.network_diagnostics;
+    out.gauge(
+        "sd300_connectivity_up",
+        "Result of the most recent connectivity probe.",
+        [
+            (
+                "gateway",
+                !diagnostics.gateway.target.is_empty(),
+                diagnostics.gateway.reachable,
+            ),
+            (
+                "dns",
+                !diagnostics.dns.domain.is_empty(),
+                diagnostics.dns.resolved,
+            ),
+            (
+                "internet",
+                !diagnostics.internet.target.is_empty(),
+                diagnostics.internet.reachable,
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, checked, _)| *checked)
+        .map(|(probe, _, up)| (vec![("probe", probe)], flag(up))),
+    );
+    out.gauge(
+        "sd300_connectivity_latency_seconds",
+        "Round-trip or resolution time of the most recent successful probe.",
+        [
+            ("gateway", diagnostics.gateway.latency_ms),
+            ("dns", diagnostics.dns.resolution_ms),
+            ("internet", diagnostics.internet.latency_ms),
+        ]
+        .into_iter()
+        .filter_map(|(probe, ms)| Some((vec![("probe", probe)], ms? / 1000.0))),
+    );
+}
+
+/// Devices needing attention per category, once a driver scan has finished.
+fn driver_attention(out: &mut Exposition, report: &DiagnosticReport) {
+    let drivers = &report.drivers;
+    if drivers.scan_status != DriverScanStatus::Success {
+        return;
+    }
+    let mut counts: BTreeMap<&str, usize> = drivers
+        .devices()
+        .map(|device| (device.category.label(), 0))
+        .collect();
+    for device in drivers.attention_devices() {
+        *counts.entry(device.category.label()).or_default() += 1;
+    }
+    out.gauge(
+        "sd300_driver_attention_devices",
+        "Devices whose driver reports a problem, is disabled, or is missing.",
+        counts
+            .into_iter()
+            .map(|(category, count)| (vec![("category", category)], count as f64)),
+    );
+}
+
+fn capabilities_and_warnings(out: &mut Exposition, report: &DiagnosticReport) {
+    out.gauge(
+        "sd300_capability_status",
+        "Capability state; 1 for the current state.",
+        report.capabilities.iter().flat_map(|capability| {
+            OBSERVATION_STATUSES.iter().map(move |(status, label)| {
+                (
+                    vec![("capability", capability.id), ("status", *label)],
+                    flag(capability.observation.status == *status),
+                )
+            })
+        }),
+    );
+    out.gauge(
+        "sd300_warnings",
+        "Active diagnostic warnings by severity.",
+        [
+            (WarningSeverity::Info, "info"),
+            (WarningSeverity::Warning, "warning"),
+            (WarningSeverity::Error, "error"),
+        ]
+        .into_iter()
+        .map(|(severity, label)| {
+            let count = report
+                .warnings
+                .iter()
+                .filter(|warning| warning.severity == severity)
+                .count();
+            (vec![("severity", label)], count as f64)
+        }),
+    );
+}
+
+fn partition_labels(partition: &PartitionInfo) -> Labels<'_> {
+    vec![
+        ("mount_point", partition.mount_point.as_str()),
+        ("filesystem", partition.filesystem.as_str()),
+    ]
+}
+
+fn drive_labels(drive: &DriveHealth) -> Labels<'_> {
+    vec![
+        ("drive", drive.device_id.as_str()),
+        ("model", drive.model.as_str()),
+    ]
+}
+
+fn sensor_kind(kind: crate::collectors::thermals::SensorKind) -> &'static str {
+    match kind {
+        crate::collectors::thermals::SensorKind::Cpu => "cpu",
+        crate::collectors::thermals::SensorKind::Gpu => "gpu",
+        crate::collectors::thermals::SensorKind::Other => "other",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::network::InterfaceInfo;
+    use crate::collectors::SystemSnapshot;
+    use std::net::SocketAddr;
+
+    fn report() -> DiagnosticReport {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.system.hostname = "lab-07".into();
+        snapshot.cpu.total_usage = 12.5;
+        snapshot.cpu.per_core_usage = vec![10.0, 15.0];
+        snapshot.network.interfaces = vec![InterfaceInfo {
+            name: "eth0".into(),
+            ip_addresses: vec!["192.0.2.7".into()],
+            mac_address: "00:11:22:33:44:55".into(),
+            received_bytes: 4096,
+            transmitted_bytes: 1024,
+            download_rate: 300,
+            upload_rate: 20,
+            is_up: true,
+            operational_state: "up".into(),
+        }];
+        snapshot.disk_health.drives = vec![DriveHealth {
+            device_id: "nvme0n1".into(),
+            model: "Fast \"Pro\" SSD".into(),
+            serial: Some("SERIAL123".into()),
+            firmware: None,
+            media_type: Default::default(),
+            health_status: DiskHealthStatus::Healthy,
+            temperature_celsius: Some(41.0),
+            power_on_hours: None,
+            wear_percent: None,
+            read_errors_total: None,
+            write_errors_total: None,
+            io_stats: None,
+            health_source: "smartctl".into(),
+        }];
+        let sensor = crate::collectors::thermals::SensorInfo {
+            label: "Composite".into(),
+            temperature: 38.0,
+            critical: None,
+            kind: crate::collectors::thermals::SensorKind::Other,
+            source: "hwmon".into(),
+        };
+        snapshot.thermals.sensors = vec![sensor.clone(), sensor];
+        DiagnosticReport::from_snapshot(&snapshot, false)
+    }
+
+    #[test]
+    fn exposition_has_labelled_families_and_nothing_redacted() {
+        let text = render(&report(), Format::Prometheus);
+        assert!(
+            text.contains("# TYPE sd300_cpu_usage_percent gauge\nsd300_cpu_usage_percent 12.5\n")
+        );
+        assert!(text.contains("sd300_cpu_core_usage_percent{core=\"1\"} 15\n"));
+        assert!(text.contains("# TYPE sd300_network_receive_bytes_total counter\n"));
+        assert!(text.contains("sd300_network_receive_bytes_total{interface=\"eth0\"} 4096\n"));
+        assert!(text.contains(
+            "sd300_drive_health{drive=\"nvme0n1\",model=\"Fast \\\"Pro\\\" SSD\",status=\"healthy\"} 1\n"
+        ));
+        assert!(text.contains("status=\"unknown\"} 0\n"));
+        assert!(text.contains("sd300_capability_status{capability="));
+        for secret in ["lab-07", "192.0.2.7", "00:11:22", "SERIAL123"] {
+            assert!(!text.contains(secret), "{secret} leaked");
+        }
+        assert_eq!(
+            text.matches("sd300_temperature_celsius{sensor=\"Composite\"")
+                .count(),
+            1,
+            "a repeated series would fail the scrape"
+        );
+        assert!(!text.contains("# EOF"));
+        // Families without samples, such as an absent battery, are left out.
+        assert!(!text.contains("sd300_battery"));
+    }
+
+    #[test]
+    fn openmetrics_names_counter_families_without_total_and_ends_with_eof() {
+        let text = render(&report(), Format::OpenMetrics);
+        assert!(text.contains("# TYPE sd300_network_receive_bytes counter\n"));
+        assert!(text.contains("sd300_network_receive_bytes_total{interface=\"eth0\"} 4096\n"));
+        assert!(text.ends_with("# EOF\n"));
+        assert_eq!(
+            Format::negotiate(Some(
+                "application/openmetrics-text;version=1.0.0,text/plain;q=0.5"
+            )),
+            Format::OpenMetrics
+        );
+        assert_eq!(Format::negotiate(None), Format::Prometheus);
+    }
+
+    #[test]
+    fn textfile_output_has_only_what_node_exporter_lacks() {
+        let mut report = report();
+        report.network_diagnostics.gateway.target = "N/A".into();
+        report.network_diagnostics.gateway.latency_ms = Some(2.5);
+        let text = render_textfile(&report);
+        assert!(text.contains("sd300_drive_health{"));
+        assert!(text.contains("sd300_capability_status{"));
+        assert!(text.contains("sd300_warnings{severity=\"error\"} 0\n"));
+        assert!(text.contains("sd300_connectivity_latency_seconds{probe=\"gateway\"} 0.0025\n"));
+        for duplicate in [
+            "sd300_cpu_",
+            "sd300_memory_",
+            "sd300_network_",
+            "sd300_filesystem_",
+        ] {
+            assert!(
+                !text.contains(duplicate),
+                "{duplicate} duplicates node_exporter"
+            );
+        }
+        assert!(!text.contains("# EOF"));
+        // The driver scan has not finished, so there is no attention count yet.
+        assert!(!text.contains("sd300_driver_attention_devices"));
+    }
+
+    #[test]
+    fn samples_carry_the_same_series_as_the_exposition() {
+        let samples = samples(&report());
+        let cpu = samples
+            .iter()
+            .find(|sample| sample.name == "sd300_cpu_usage_percent")
+            .unwrap();
+        assert_eq!(cpu.value, 12.5);
+        assert!(cpu.labels.is_empty());
+        let received = samples
+            .iter()
+            .find(|sample| sample.name == "sd300_network_receive_bytes_total")
+            .unwrap();
+        assert_eq!(received.labels, vec![("interface", "eth0".to_string())]);
+        assert_eq!(received.value, 4096.0);
+        let text = render(&report(), Format::Prometheus);
+        let series = text.lines().filter(|line| !line.starts_with('#')).count();
+        assert_eq!(samples.len(), series);
+    }
+
+    #[tokio::test]
+    async fn textfile_mode_requires_a_prom_file() {
+        let error = run_textfile(
+            Path::new("metrics.txt"),
+            Duration::from_secs(60),
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(error.to_string().contains(".prom"));
+    }
+
+    async fn get(address: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn serves_metrics_over_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let latest: Latest = Arc::new(RwLock::new(Arc::new(report())));
+        let server = tokio::spawn(accept_loop(listener, latest));
+
+        let response = get(address, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(PROMETHEUS_CONTENT_TYPE));
+        assert!(response.contains("sd300_cpu_usage_percent 12.5"));
+
+        let response = get(
+            address,
+            "GET /metrics HTTP/1.1\r\nAccept: application/openmetrics-text\r\n\r\n",
+        )
+        .await;
+        assert!(response.contains(OPENMETRICS_CONTENT_TYPE));
+        assert!(response.ends_with("# EOF\n"));
+
+        let response = get(address, "GET /other HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        let response = get(address, "POST /metrics HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+        server.abort();
+    }
+}
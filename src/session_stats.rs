@@ -0,0 +1,287 @@
+//! Whole-session statistics for the TUI's history series.
+//!
+//! The sparkline histories are bounded, so they cannot answer "how busy was
+//! this machine over the afternoon". Each series here keeps its sample count,
+//! extremes, and sum, plus a log-scale histogram whose buckets are 1% apart,
+//! which bounds memory however long the session runs and puts the reported
+//! p95 within 1% of the exact value. On exit the summary is printed, saved
+//! as JSON, or written to `--summary-on-exit`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::error::{AppError, Result};
+use crate::settings;
+use crate::ui::common::format_throughput;
+
+/// Ratio between neighbouring histogram buckets.
+const BUCKET_RATIO: f64 = 1.01;
+/// Values below this share one bucket; they read as zero in every unit shown.
+const ZERO_BELOW: f64 = 1e-3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Unit {
+    Percent,
+    BytesPerSecond,
+    Celsius,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Series {
+    samples: u64,
+    min: f64,
+    max: f64,
+    sum: f64,
+    /// Bucket index to sample count; `None` holds values below `ZERO_BELOW`.
+    buckets: BTreeMap<Option<i32>, u64>,
+}
+
+impl Series {
+    fn push(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        if self.samples == 0 {
+            self.min = value;
+            self.max = value;
+        }
+        self.samples += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value;
+        let bucket = (value >= ZERO_BELOW).then(|| (value.ln() / BUCKET_RATIO.ln()).floor() as i32);
+        *self.buckets.entry(bucket).or_default() += 1;
+    }
+
+    /// Nearest-rank percentile, reported as the geometric middle of its
+    /// bucket and clamped to the values actually seen.
+    fn percentile(&self, fraction: f64) -> f64 {
+        let rank = ((fraction * self.samples as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in &self.buckets {
+            seen += count;
+            if seen >= rank {
+                let value = match bucket {
+                    Some(index) => BUCKET_RATIO.powf(*index as f64 + 0.5),
+                    None => 0.0,
+                };
+                return value.clamp(self.min, self.max);
+            }
+        }
+        self.max
+    }
+}
+
+/// Statistics for every series the TUI keeps history for, in the order they
+/// were first sampled.
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    started_unix_ms: u64,
+    series: Vec<(String, Unit, Series)>,
+}
+
+impl SessionStats {
+    pub fn new(started_unix_ms: u64) -> Self {
+        Self {
+            started_unix_ms,
+            series: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, name: &str, unit: Unit, value: f64) {
+        let index = match self.series.iter().position(|(known, ..)| known == name) {
+            Some(index) => index,
+            None => {
+                self.series
+                    .push((name.to_string(), unit, Series::default()));
+                self.series.len() - 1
+            }
+        };
+        self.series[index].2.push(value);
+    }
+
+    pub fn summary(&self, ended_unix_ms: u64) -> SessionSummary {
+        SessionSummary {
+            started_unix_ms: self.started_unix_ms,
+            ended_unix_ms,
+            duration_secs: ended_unix_ms.saturating_sub(self.started_unix_ms) / 1000,
+            series: self
+                .series
+                .iter()
+                .filter(|(.., series)| series.samples > 0)
+                .map(|(name, unit, series)| SeriesSummary {
+                    name: name.clone(),
+                    unit: *unit,
+                    samples: series.samples,
+                    min: series.min,
+                    max: series.max,
+                    avg: series.sum / series.samples as f64,
+                    p95: series.percentile(0.95),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub started_unix_ms: u64,
+    pub ended_unix_ms: u64,
+    pub duration_secs: u64,
+    pub series: Vec<SeriesSummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesSummary {
+    pub name: String,
+    pub unit: Unit,
+    pub samples: u64,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    pub p95: f64,
+}
+
+impl SessionSummary {
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "Session summary: {} to {} UTC ({})\n\n{:<16} {:>10} {:>10} {:>10} {:>10} {:>8}\n",
+            crate::replay::format_utc(self.started_unix_ms),
+            crate::replay::format_utc(self.ended_unix_ms),
+            crate::ui::common::format_uptime(self.duration_secs),
+            "SERIES",
+            "MIN",
+            "AVG",
+            "P95",
+            "MAX",
+            "SAMPLES"
+        );
+        for series in &self.series {
+            let show = |value: f64| match series.unit {
+                Unit::Percent => format!("{value:.1}%"),
+                Unit::BytesPerSecond => format_throughput(value as u64),
+                Unit::Celsius => format!("{value:.1} C"),
+            };
+            text.push_str(&format!(
+                "{:<16} {:>10} {:>10} {:>10} {:>10} {:>8}\n",
+                series.name,
+                show(series.min),
+                show(series.avg),
+                show(series.p95),
+                show(series.max),
+                series.samples
+            ));
+        }
+        text
+    }
+
+    fn to_json(&self) -> Result<Vec<u8>> {
+        let mut bytes = serde_json::to_vec_pretty(self)
+            .map_err(|error| AppError::platform(format!("JSON serialization failed: {error}")))?;
+        bytes.push(b'\n');
+        Ok(bytes)
+    }
+}
+
+/// Hand the finished session's summary to the user: written to `path` when
+/// one was given, otherwise offered interactively when both ends of the
+/// terminal are attended.
+pub fn finish(summary: &SessionSummary, path: Option<&Path>) -> Result<()> {
+    if summary.series.is_empty() {
+        return Ok(());
+    }
+    if let Some(path) = path {
+        fs::write(path, summary.to_json()?)?;
+        return Ok(());
+    }
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Ok(());
+    }
+
+    print!("Session summary: [p]rint, [s]ave as JSON, or Enter to skip: ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    match answer.trim() {
+        "p" | "P" => print!("\n{}", summary.to_text()),
+        "s" | "S" => {
+            let path = save_to_reports(summary)?;
+            println!("Saved session summary to {}", path.display());
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn save_to_reports(summary: &SessionSummary) -> Result<PathBuf> {
+    let dir = settings::reports_dir().map_err(AppError::platform)?;
+    fs::create_dir_all(&dir)?;
+    settings::restrict_directory(&dir).map_err(AppError::platform)?;
+    let stamp: String = crate::replay::format_utc(summary.ended_unix_ms)
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect();
+    let path = dir.join(format!("session-{}-{}.json", &stamp[..8], &stamp[8..]));
+    settings::write_private_atomic(&path, ".session", &summary.to_json()?)
+        .map_err(AppError::platform)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summaries_cover_the_whole_session() {
+        let mut stats = SessionStats::new(1_000);
+        for value in 1..=100 {
+            stats.record("cpu", Unit::Percent, value as f64);
+        }
+        stats.record("net_down", Unit::BytesPerSecond, 0.0);
+        stats.record("net_down", Unit::BytesPerSecond, 2048.0);
+
+        let summary = stats.summary(61_000);
+        assert_eq!(summary.duration_secs, 60);
+        let cpu = &summary.series[0];
+        assert_eq!(cpu.name, "cpu");
+        assert_eq!(cpu.samples, 100);
+        assert_eq!((cpu.min, cpu.max, cpu.avg), (1.0, 100.0, 50.5));
+        assert!((cpu.p95 - 95.0).abs() <= 95.0 * 0.01, "p95 was {}", cpu.p95);
+
+        let net = &summary.series[1];
+        assert_eq!((net.min, net.max, net.p95), (0.0, 2048.0, 2048.0));
+        assert!(summary.to_text().contains("net_down"));
+    }
+
+    #[test]
+    fn percentiles_stay_bounded_and_within_the_seen_range() {
+        let mut stats = SessionStats::new(0);
+        for _ in 0..100_000 {
+            stats.record("temp", Unit::Celsius, 42.0);
+        }
+        stats.record("temp", Unit::Celsius, f64::NAN);
+        let series = &stats.series[0].2;
+        assert_eq!(series.buckets.len(), 1);
+        let summary = stats.summary(0);
+        assert_eq!(summary.series[0].p95, 42.0);
+        assert_eq!(summary.series[0].samples, 100_000);
+    }
+
+    #[test]
+    fn a_summary_path_receives_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.json");
+        let mut stats = SessionStats::new(0);
+        stats.record("memory", Unit::Percent, 40.0);
+        finish(&stats.summary(5_000), Some(&path)).unwrap();
+        let saved: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        assert_eq!(saved["series"][0]["name"], "memory");
+        assert_eq!(saved["series"][0]["unit"], "percent");
+        assert_eq!(saved["duration_secs"], 5);
+    }
+}
@@ -0,0 +1,459 @@
+//! Ticket-sized Markdown and plain-text summaries of a [`DiagnosticReport`].
+//!
+//! These are written to be pasted into a vendor escalation: identity, health
+//! verdicts, warnings with a suggested next step, devices needing attention,
+//! connectivity, and the top resource consumers, in that order of importance.
+//! With a character limit, lines are kept from the top until the next one
+//! would not fit, so a short ticket field loses the process lists before it
+//! loses a warning.
+
+use crate::collectors::drivers::{DeviceInfo, DeviceStatus};
+use crate::collectors::network_diag::ConnectivityResult;
+use crate::collectors::processes::ProcessInfo;
+use crate::collectors::{DiagnosticWarning, WarningSeverity};
+use crate::report::{health_verdicts, probe_checked, redacted_field_label, DiagnosticReport};
+use crate::types::HealthStatus;
+use crate::ui::common::{format_bytes, format_uptime};
+
+/// Processes listed per consumer table.
+const TOP_CONSUMERS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Markdown,
+    Text,
+}
+
+/// Render the summary, cut to at most `max_chars` characters when given.
+pub fn render(
+    report: &DiagnosticReport,
+    style: Style,
+    generated_unix_ms: u64,
+    max_chars: Option<usize>,
+) -> String {
+    let mut summary = Summary {
+        style,
+        lines: Vec::new(),
+    };
+    summary.title(&format!(
+        "SD-300 diagnostic summary ({} UTC, SD-300 {})",
+        crate::replay::format_utc(generated_unix_ms),
+        report.product_version
+    ));
+
+    let system = &report.system;
+    summary.heading("Identity");
+    let model = [system.manufacturer.as_deref(), system.model.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if !model.is_empty() {
+        summary.field("Model", &model);
+    }
+    summary.field(
+        "OS",
+        &format!(
+            "{} {} (kernel {}, {})",
+            system.os_name, system.os_version, system.kernel_version, report.target_arch
+        ),
+    );
+    summary.field(
+        "CPU",
+        &format!(
+            "{} ({} cores / {} threads)",
+            system.cpu_model, report.cpu.core_count, report.cpu.thread_count
+        ),
+    );
+    summary.field("Memory", &format_bytes(system.total_memory_bytes));
+    summary.field("Uptime", &format_uptime(system.uptime_seconds));
+    summary.field("Hostname", &system.hostname);
+    summary.field(
+        "Redacted",
+        &if report.privacy.sensitive_values_included {
+            "nothing; sensitive values are included".to_string()
+        } else {
+            report
+                .privacy
+                .redacted_fields
+                .iter()
+                .map(|field| redacted_field_label(field))
+                .collect::<Vec<_>>()
+                .join(", ")
+        },
+    );
+
+    summary.heading("Health");
+    for verdict in health_verdicts(report) {
+        let text = match verdict.percent {
+            Some(percent) => format!("{} ({percent:.0}%)", verdict.summary),
+            None => verdict.summary,
+        };
+        summary.status(&verdict.status, verdict.label, &text);
+    }
+
+    summary.heading(&format!("Warnings ({})", report.warnings.len()));
+    if report.warnings.is_empty() {
+        summary.item("None");
+    }
+    for warning in &report.warnings {
+        summary.item(&format!(
+            "[{}] {}: {}",
+            severity_label(&warning.severity),
+            warning.source,
+            warning.message
+        ));
+        if let Some(step) = remediation(warning) {
+            summary.detail(&format!("Next step: {step}"));
+        }
+    }
+
+    let devices = report.drivers.attention_devices().collect::<Vec<_>>();
+    summary.heading(&format!("Devices needing attention ({})", devices.len()));
+    if devices.is_empty() {
+        summary.item(match report.drivers.scan_status {
+            crate::collectors::drivers::DriverScanStatus::Success => "None",
+            _ => "Not checked",
+        });
+    }
+    for device in devices {
+        summary.item(&format!(
+            "{} ({}): {}",
+            device.name,
+            device.category.label(),
+            device.status
+        ));
+        summary.detail(&format!("Next step: {}", device_remediation(device)));
+    }
+
+    let diagnostics = &report.network_diagnostics;
+    summary.heading("Connectivity");
+    summary.item(&probe_line("Gateway", &diagnostics.gateway));
+    let dns = &diagnostics.dns;
+    summary.item(&if dns.domain.is_empty() {
+        "DNS: not checked".to_string()
+    } else if dns.resolved {
+        format!(
+            "DNS: resolved {}{}",
+            dns.domain,
+            dns.resolution_ms
+                .map(|ms| format!(" in {ms:.0} ms"))
+                .unwrap_or_default()
+        )
+    } else {
+        format!(
+            "DNS: FAILED for {}{}",
+            dns.domain,
+            dns.error
+                .as_deref()
+                .map(|error| format!(" ({error})"))
+                .unwrap_or_default()
+        )
+    });
+    summary.item(&probe_line("Internet", &diagnostics.internet));
+
+    let mut processes = report.processes.list.iter().collect::<Vec<_>>();
+    summary.heading("Top CPU consumers");
+    processes.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+    summary.consumers(&processes);
+    summary.heading("Top memory consumers");
+    processes.sort_by_key(|process| std::cmp::Reverse(process.memory_bytes));
+    summary.consumers(&processes);
+
+    summary.finish(max_chars)
+}
+
+struct Summary {
+    style: Style,
+    lines: Vec<String>,
+}
+
+impl Summary {
+    fn title(&mut self, text: &str) {
+        match self.style {
+            Style::Markdown => self.lines.push(format!("## {}", escape_markdown(text))),
+            Style::Text => {
+                self.lines.push(text.to_string());
+                self.lines.push("=".repeat(text.chars().count()));
+            }
+        }
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.lines.push(String::new());
+        match self.style {
+            Style::Markdown => self.lines.push(format!("### {}", escape_markdown(text))),
+            Style::Text => self.lines.push(text.to_ascii_uppercase()),
+        }
+    }
+
+    fn field(&mut self, label: &str, value: &str) {
+        match self.style {
+            Style::Markdown => self
+                .lines
+                .push(format!("- **{label}:** {}", escape_markdown(value))),
+            Style::Text => self
+                .lines
+                .push(format!("  {:<10} {value}", format!("{label}:"))),
+        }
+    }
+
+    fn status(&mut self, status: &HealthStatus, label: &str, summary: &str) {
+        let tag = match status {
+            HealthStatus::Good => "OK",
+            HealthStatus::Warning => "WARN",
+            HealthStatus::Critical => "CRIT",
+            HealthStatus::Unknown => "?",
+        };
+        match self.style {
+            Style::Markdown => self.lines.push(format!(
+                "- `{tag}` **{label}:** {}",
+                escape_markdown(summary)
+            )),
+            Style::Text => self
+                .lines
+                .push(format!("  {:<6} {label:<12} {summary}", format!("[{tag}]"))),
+        }
+    }
+
+    fn item(&mut self, text: &str) {
+        match self.style {
+            Style::Markdown => self.lines.push(format!("- {}", escape_markdown(text))),
+            Style::Text => self.lines.push(format!("  - {text}")),
+        }
+    }
+
+    fn detail(&mut self, text: &str) {
+        match self.style {
+            Style::Markdown => self.lines.push(format!("  - {}", escape_markdown(text))),
+            Style::Text => self.lines.push(format!("      {text}")),
+        }
+    }
+
+    fn consumers(&mut self, processes: &[&ProcessInfo]) {
+        if processes.is_empty() {
+            self.item("None reported");
+        }
+        for process in processes.iter().take(TOP_CONSUMERS) {
+            self.item(&format!(
+                "{} (PID {}): {:.1}% CPU, {}",
+                process.name,
+                process.pid,
+                process.cpu_percent,
+                format_bytes(process.memory_bytes)
+            ));
+        }
+    }
+
+    /// Join the lines, keeping as many as fit within `max_chars` together
+    /// with a note saying how many were left out.
+    fn finish(self, max_chars: Option<usize>) -> String {
+        let total = self.lines.len();
+        let mut text = String::new();
+        let mut used = 0;
+        for (index, line) in self.lines.iter().enumerate() {
+            let cost = line.chars().count() + 1;
+            if let Some(limit) = max_chars {
+                let remaining = total - index - 1;
+                let note = if remaining == 0 {
+                    0
+                } else {
+                    omission_note(self.style, remaining + 1).chars().count() + 1
+                };
+                if used + cost + note > limit {
+                    let note = omission_note(self.style, total - index);
+                    if used + note.chars().count() < limit {
+                        text.push_str(&note);
+                        text.push('\n');
+                    }
+                    return text;
+                }
+            }
+            text.push_str(line);
+            text.push('\n');
+            used += cost;
+        }
+        text
+    }
+}
+
+fn omission_note(style: Style, lines: usize) -> String {
+    let note = format!("[{lines} more line(s) cut to fit; run sd300 snapshot for the full report]");
+    match style {
+        Style::Markdown => format!("_{}_", escape_markdown(&note)),
+        Style::Text => note,
+    }
+}
+
+fn probe_line(label: &str, probe: &ConnectivityResult) -> String {
+    if !probe_checked(probe) {
+        format!("{label}: not checked")
+    } else if probe.reachable {
+        format!(
+            "{label}: reachable ({}){}",
+            probe.target,
+            probe
+                .latency_ms
+                .map(|ms| format!(" in {ms:.0} ms"))
+                .unwrap_or_default()
+        )
+    } else {
+        format!(
+            "{label}: UNREACHABLE ({}){}",
+            probe.target,
+            probe
+                .error
+                .as_deref()
+                .map(|error| format!(": {error}"))
+                .unwrap_or_default()
+        )
+    }
+}
+
+fn severity_label(severity: &WarningSeverity) -> &'static str {
+    match severity {
+        WarningSeverity::Info => "info",
+        WarningSeverity::Warning => "warning",
+        WarningSeverity::Error => "error",
+    }
+}
+
+/// A suggested first step for each warning source SD-300 produces.
+fn remediation(warning: &DiagnosticWarning) -> Option<&'static str> {
+    match warning.source.as_str() {
+        "Drivers" => Some(
+            "Reinstall or update the driver for the listed devices from the hardware vendor, then rescan.",
+        ),
+        "Disk Health" => Some(
+            "Back up this drive now and run the vendor's drive diagnostic; replace it if health is failing.",
+        ),
+        "Network" => Some(
+            "Check the cable or Wi-Fi link, restart the router, and confirm the DNS servers in the adapter settings.",
+        ),
+        "Thermals" => Some(
+            "Run SD-300 as administrator or install a supported hardware monitor to expose the sensors.",
+        ),
+        crate::collectors::memory_growth::WARNING_SOURCE => Some(
+            "Restart the named process; if it grows again, report the leak to its vendor.",
+        ),
+        crate::collectors::thresholds::WARNING_SOURCE => Some(
+            "Free space, improve cooling, or reduce load on the named item; adjust its limit in settings if the level is normal for this machine.",
+        ),
+        crate::baseline::WARNING_SOURCE => Some(
+            "Confirm the change was expected, then run `sd300 baseline save` to accept it.",
+        ),
+        "History" => Some(
+            "Check free space and permissions in the SD-300 reports directory.",
+        ),
+        _ => None,
+    }
+}
+
+fn device_remediation(device: &DeviceInfo) -> &'static str {
+    match device.status {
+        DeviceStatus::Disabled => "Re-enable the device if it should be in use.",
+        DeviceStatus::NotFound => "Reconnect or power the device, then rescan.",
+        _ => "Reinstall or update its driver from the hardware vendor.",
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        if matches!(
+            character,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '|' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::drivers::{DeviceCategory, DriverScanStatus};
+    use crate::collectors::SystemSnapshot;
+
+    fn fixture() -> DiagnosticReport {
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.system.os_name = "Linux".into();
+        snapshot.warnings.push(DiagnosticWarning {
+            source: "Disk Health".into(),
+            message: "Drive *nvme0* reports critical wear".into(),
+            severity: WarningSeverity::Error,
+        });
+        snapshot.drivers.scan_status = DriverScanStatus::Success;
+        snapshot.drivers.usb.push(DeviceInfo {
+            name: "USB Hub".into(),
+            driver_version: String::new(),
+            driver_date: String::new(),
+            status: DeviceStatus::Disabled,
+            category: DeviceCategory::Usb,
+            extra: String::new(),
+        });
+        for pid in 1..=8 {
+            snapshot.processes.list.push(ProcessInfo {
+                pid,
+                name: format!("proc{pid}"),
+                friendly_name: String::new(),
+                cpu_percent: pid as f32,
+                memory_bytes: 1024 * 1024 * (10 - pid as u64),
+                memory_percent: 0.0,
+                status: "Run".into(),
+                disk_read_bytes_per_sec: 0,
+                disk_write_bytes_per_sec: 0,
+                gpu_memory_bytes: None,
+            });
+        }
+        DiagnosticReport::from_snapshot(&snapshot, false)
+    }
+
+    #[test]
+    fn markdown_summaries_cover_every_ticket_section() {
+        let text = render(&fixture(), Style::Markdown, 1_700_000_000_000, None);
+
+        assert!(text.starts_with("## SD-300 diagnostic summary (2023-11-14 22:13:20 UTC"));
+        for heading in [
+            "### Identity",
+            "### Health",
+            "### Warnings (1)",
+            "### Devices needing attention (1)",
+            "### Connectivity",
+            "### Top CPU consumers",
+            "### Top memory consumers",
+        ] {
+            assert!(text.contains(heading), "missing {heading}");
+        }
+        assert!(text.contains("- **Hostname:** \\[redacted\\]"));
+        assert!(text.contains("Drive \\*nvme0\\* reports critical wear"));
+        assert!(text.contains("  - Next step: Back up this drive now"));
+        assert!(text.contains("- USB Hub (USB): Disabled"));
+        assert!(text.contains("- Gateway: not checked"));
+        assert!(text.contains("- proc8 (PID 8): 8.0% CPU"));
+    }
+
+    #[test]
+    fn text_summaries_use_plain_tags() {
+        let text = render(&fixture(), Style::Text, 0, None);
+        assert!(text.contains("\nIDENTITY\n"));
+        assert!(text.contains("  [WARN] Devices      1 device(s) need attention"));
+        assert!(text.contains("  - [error] Disk Health: Drive *nvme0* reports critical wear"));
+        assert!(!text.contains('#'));
+    }
+
+    #[test]
+    fn length_limits_drop_trailing_lines_and_say_so() {
+        let full = render(&fixture(), Style::Text, 0, None);
+        for limit in [200, 400, 800] {
+            let cut = render(&fixture(), Style::Text, 0, Some(limit));
+            assert!(cut.chars().count() <= limit, "{limit}: {}", cut.len());
+            assert!(cut.contains("more line(s) cut to fit"));
+            assert!(full.starts_with(cut.lines().next().unwrap()));
+        }
+        let roomy = render(&fixture(), Style::Markdown, 0, Some(100_000));
+        assert_eq!(roomy, render(&fixture(), Style::Markdown, 0, None));
+    }
+}
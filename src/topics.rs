@@ -0,0 +1,174 @@
+//! Versioned topic envelopes shared by the desktop engine and `sd300 daemon`.
+//!
+//! A topic is one slice of the live snapshot, grouped by how often its
+//! collectors run. Each publication is wrapped in the same envelope, so a
+//! reader of the engine's FFI and a reader of the daemon socket parse the
+//! same JSON.
+
+use serde::Serialize;
+
+use crate::collectors::{self, DiagnosticWarning};
+use crate::observation::Observation;
+
+/// Version of the envelope and projection layout.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    Static = 0,
+    Fast = 1,
+    Medium = 2,
+    Slow = 3,
+    Diagnostics = 4,
+    Health = 5,
+    Drivers = 6,
+    Warnings = 7,
+    Capabilities = 8,
+}
+
+impl Topic {
+    pub const ALL: [Self; 9] = [
+        Self::Static,
+        Self::Fast,
+        Self::Medium,
+        Self::Slow,
+        Self::Diagnostics,
+        Self::Health,
+        Self::Drivers,
+        Self::Warnings,
+        Self::Capabilities,
+    ];
+
+    pub fn from_raw(value: u32) -> Option<Self> {
+        Self::ALL.get(usize::try_from(value).ok()?).copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|topic| topic.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Static => "static",
+            Self::Fast => "fast",
+            Self::Medium => "medium",
+            Self::Slow => "slow",
+            Self::Diagnostics => "diagnostics",
+            Self::Health => "health",
+            Self::Drivers => "drivers",
+            Self::Warnings => "warnings",
+            Self::Capabilities => "capabilities",
+        }
+    }
+
+    pub fn provenance(self) -> &'static str {
+        match self {
+            Self::Static => "SD-300 system, display, memory, and network collectors",
+            Self::Fast => "SD-300 sysinfo-backed live collectors",
+            Self::Medium => "SD-300 platform connection collector",
+            Self::Slow => "SD-300 disk, GPU, and thermal collectors",
+            Self::Diagnostics => "SD-300 gateway, DNS, and internet collectors",
+            Self::Health => "SD-300 platform disk-health collector",
+            Self::Drivers => "SD-300 platform device provider",
+            Self::Warnings => "SD-300 warning deduplication pipeline",
+            Self::Capabilities => "SD-300 capability and provenance matrix",
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct StaticProjection<'a> {
+    pub system: &'a collectors::system_info::SystemInfoData,
+    pub displays: &'a collectors::display::DisplayData,
+    pub memory_modules: &'a [collectors::memory::MemoryModule],
+    pub memory_module_status: &'a Observation,
+    pub network_adapters: &'a [collectors::network::NetworkAdapterInfo],
+    pub network_adapter_status: &'a Observation,
+}
+
+#[derive(Serialize)]
+pub struct FastProjection<'a> {
+    pub cpu: &'a collectors::cpu::CpuData,
+    pub memory: &'a collectors::memory::MemoryData,
+    pub network: &'a collectors::network::NetworkData,
+    pub processes: &'a collectors::processes::ProcessData,
+}
+
+#[derive(Serialize)]
+pub struct SlowProjection<'a> {
+    pub disk: &'a collectors::disk::DiskData,
+    pub gpu: &'a collectors::gpu::GpuData,
+    pub thermals: &'a collectors::thermals::ThermalData,
+}
+
+#[derive(Serialize)]
+pub struct MediumProjection<'a> {
+    pub active_connections: &'a [collectors::network_diag::ConnectionInfo],
+    pub listening_ports: &'a [collectors::network_diag::ConnectionInfo],
+}
+
+#[derive(Serialize)]
+struct TopicEnvelope<'a, T: ?Sized> {
+    schema_version: u32,
+    product_version: &'static str,
+    target: &'static str,
+    topic: &'static str,
+    sequence: u64,
+    captured_unix_ms: u64,
+    freshness_ms: u64,
+    availability: &'static str,
+    provenance: &'static str,
+    warnings: &'a [DiagnosticWarning],
+    data: &'a T,
+}
+
+pub fn target_label() -> &'static str {
+    match (std::env::consts::ARCH, std::env::consts::OS) {
+        ("x86_64", "windows") => "x86_64-windows",
+        ("x86_64", "macos") => "x86_64-macos",
+        ("aarch64", "macos") => "aarch64-macos",
+        ("x86_64", "linux") => "x86_64-linux",
+        ("aarch64", "linux") => "aarch64-linux",
+        _ => "unsupported-target",
+    }
+}
+
+/// Serialize one publication of `topic` in the shared envelope.
+pub fn envelope<T: Serialize + ?Sized>(
+    topic: Topic,
+    sequence: u64,
+    captured_unix_ms: u64,
+    data: &T,
+    warnings: &[DiagnosticWarning],
+) -> serde_json::Result<Vec<u8>> {
+    serde_json::to_vec(&TopicEnvelope {
+        schema_version: SCHEMA_VERSION,
+        product_version: env!("CARGO_PKG_VERSION"),
+        target: target_label(),
+        topic: topic.name(),
+        sequence,
+        captured_unix_ms,
+        freshness_ms: 0,
+        availability: "available",
+        provenance: topic.provenance(),
+        warnings,
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_round_trip_through_their_raw_values_and_names() {
+        for (index, topic) in Topic::ALL.into_iter().enumerate() {
+            assert_eq!(topic as usize, index);
+            assert_eq!(Topic::from_raw(index as u32), Some(topic));
+            assert_eq!(Topic::from_name(topic.name()), Some(topic));
+        }
+        assert_eq!(Topic::from_raw(Topic::ALL.len() as u32), None);
+        assert_eq!(Topic::from_name("processes"), None);
+    }
+}
@@ -1,3 +1,8 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::settings::Limits;
+
 /// The diagnostic display mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DiagnosticMode {
@@ -5,9 +10,10 @@ pub enum DiagnosticMode {
     Technician,
 }
 
-/// The 9 navigable sections
+/// The navigable sections: 1-9 plus the event timeline on 0
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Section {
+    Events = 0,
     Overview = 1,
     Cpu = 2,
     Memory = 3,
@@ -22,6 +28,7 @@ pub enum Section {
 impl Section {
     pub fn from_number(n: u8) -> Option<Self> {
         match n {
+            0 => Some(Self::Events),
             1 => Some(Self::Overview),
             2 => Some(Self::Cpu),
             3 => Some(Self::Memory),
@@ -46,6 +53,7 @@ impl Section {
             Self::Processes => "Procs",
             Self::Thermals => "Thermals",
             Self::Drivers => "Drivers",
+            Self::Events => "Events",
         }
     }
 
@@ -53,7 +61,9 @@ impl Section {
         *self as u8
     }
 
-    pub const ALL: [Section; 9] = [
+    /// In tab order; the timeline follows Drivers, matching its key's place
+    /// on the keyboard.
+    pub const ALL: [Section; 10] = [
         Self::Overview,
         Self::Cpu,
         Self::Memory,
@@ -63,11 +73,13 @@ impl Section {
         Self::Processes,
         Self::Thermals,
         Self::Drivers,
+        Self::Events,
     ];
 }
 
 /// Health status for a subsystem
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum HealthStatus {
     Good,
     Warning,
@@ -76,16 +88,35 @@ pub enum HealthStatus {
 }
 
 impl HealthStatus {
-    pub fn from_percent(pct: f64) -> Self {
-        if pct < 75.0 {
+    pub fn from_limits(value: f64, limits: Limits) -> Self {
+        if value < limits.warning {
             Self::Good
-        } else if pct < 90.0 {
+        } else if value < limits.critical {
             Self::Warning
         } else {
             Self::Critical
         }
     }
 
+    /// Ranks statuses by urgency; Unknown ranks below Good.
+    fn rank(self) -> u8 {
+        match self {
+            Self::Unknown => 0,
+            Self::Good => 1,
+            Self::Warning => 2,
+            Self::Critical => 3,
+        }
+    }
+
+    /// The more urgent of two statuses.
+    pub fn worst(self, other: Self) -> Self {
+        if other.rank() > self.rank() {
+            other
+        } else {
+            self
+        }
+    }
+
     pub fn icon(&self) -> &'static str {
         match self {
             Self::Good => "\u{2713}",     // ✓
@@ -103,6 +134,10 @@ pub enum ProcessSortKey {
     Memory,
     Pid,
     Name,
+    /// Combined disk read and write rate
+    DiskIo,
+    /// Dedicated GPU memory (Linux DRM clients only)
+    GpuMemory,
 }
 
 /// Temperature display unit
@@ -134,3 +169,48 @@ impl TempUnit {
         }
     }
 }
+
+/// Span of history shown by every sparkline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeWindow {
+    #[default]
+    Minute,
+    TenMinutes,
+    Hour,
+    SixHours,
+    Day,
+}
+
+impl TimeWindow {
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Minute => Self::TenMinutes,
+            Self::TenMinutes => Self::Hour,
+            Self::Hour => Self::SixHours,
+            Self::SixHours => Self::Day,
+            Self::Day => Self::Minute,
+        }
+    }
+
+    /// Compact label for panel titles
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Minute => "60s",
+            Self::TenMinutes => "10m",
+            Self::Hour => "1h",
+            Self::SixHours => "6h",
+            Self::Day => "24h",
+        }
+    }
+
+    /// Plain-language label for User Mode titles
+    pub fn plain_label(&self) -> &'static str {
+        match self {
+            Self::Minute => "Last 60 Seconds",
+            Self::TenMinutes => "Last 10 Minutes",
+            Self::Hour => "Last Hour",
+            Self::SixHours => "Last 6 Hours",
+            Self::Day => "Last 24 Hours",
+        }
+    }
+}
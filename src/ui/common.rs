@@ -1,14 +1,17 @@
-use crate::types::{HealthStatus, TempUnit};
+use crate::history::MetricHistory;
+use crate::settings::Limits;
+use crate::types::{HealthStatus, TempUnit, TimeWindow};
+use ratatui::layout::Rect;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui::Frame;
 
-// -- Temperature Thresholds --
+// -- Fixed Gauge Bands --
 
-pub const TEMP_CPU_WARN: f64 = 70.0;
-pub const TEMP_CPU_CRIT: f64 = 85.0;
-pub const TEMP_GPU_WARN: f64 = 75.0;
-pub const TEMP_GPU_CRIT: f64 = 90.0;
+/// Battery charge is drawn in the bands every gauge used before thresholds
+/// became configurable; charge is not a health threshold.
+pub const BATTERY_GAUGE_LIMITS: Limits = Limits::new(75.0, 90.0);
 
 // -- Color Palette (Warm Earth) --
 
@@ -91,6 +94,89 @@ pub fn sub_block(title: &str) -> Block<'static> {
         .title_style(Style::default().fg(COLOR_MUTED))
 }
 
+// -- History Sparklines --
+
+/// Render `history` over `window` as a sparkline. Columns that summarize
+/// several samples draw their spread: the bar reaches the average, a band in
+/// the border color above it reaches the maximum, and the part below the
+/// minimum is shaded darker, so downsampling never hides a spike or a dip.
+/// `max` fixes the scale (e.g. 100 for percentages); otherwise the largest
+/// maximum in view sets it.
+pub fn render_history(
+    frame: &mut Frame,
+    area: Rect,
+    block: Block<'static>,
+    history: &MetricHistory,
+    window: TimeWindow,
+    max: Option<f64>,
+    color: Color,
+) {
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.is_empty() {
+        return;
+    }
+
+    let columns = history.columns(window, inner.width as usize);
+    let scale = max
+        .unwrap_or_else(|| columns.iter().map(|band| band.max).fold(1.0, f64::max))
+        .max(f64::EPSILON);
+    let eighths =
+        |value: f64| ((value.max(0.0) / scale) * f64::from(inner.height) * 8.0).round() as u64;
+    let bar_set = sparkline_bar_set();
+    let symbol = |fill: u64| match fill {
+        0 => bar_set.empty,
+        1 => bar_set.one_eighth,
+        2 => bar_set.one_quarter,
+        3 => bar_set.three_eighths,
+        4 => bar_set.half,
+        5 => bar_set.five_eighths,
+        6 => bar_set.three_quarters,
+        7 => bar_set.seven_eighths,
+        _ => bar_set.full,
+    };
+    let below_min = darken(color);
+    let buffer = frame.buffer_mut();
+
+    for (offset, band) in columns.iter().enumerate() {
+        let x = inner.left() + offset as u16;
+        let (min, avg, max) = (eighths(band.min), eighths(band.avg), eighths(band.max));
+        let spread = band.min < band.avg;
+        for row in 0..u64::from(inner.height) {
+            let floor = row * 8;
+            let fill = |height: u64| height.saturating_sub(floor).min(8);
+            let (avg_fill, max_fill, min_fill) = (fill(avg), fill(max), fill(min));
+            let y = inner.bottom() - 1 - row as u16;
+            let style = if avg_fill > 0 {
+                let fg = if spread && min_fill >= avg_fill {
+                    below_min
+                } else {
+                    color
+                };
+                let style = Style::default().fg(fg);
+                if max_fill > avg_fill {
+                    style.bg(COLOR_BORDER)
+                } else {
+                    style
+                }
+            } else if max_fill > 0 {
+                Style::default().fg(COLOR_BORDER)
+            } else {
+                continue;
+            };
+            let fill = if avg_fill > 0 { avg_fill } else { max_fill };
+            buffer[(x, y)].set_symbol(symbol(fill)).set_style(style);
+        }
+    }
+}
+
+fn darken(color: Color) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => Color::Rgb(r / 2, g / 2, b / 2),
+        other => other,
+    }
+}
+
 // -- Formatters --
 
 /// Format bytes to human-readable string (e.g., "12.4 GB")
@@ -236,8 +322,8 @@ pub fn gauge_bar(percent: f64, width: usize) -> String {
 }
 
 /// Create a colored gauge line with label
-pub fn gauge_line<'a>(label: &str, percent: f64, width: usize) -> Line<'a> {
-    let status = HealthStatus::from_percent(percent);
+pub fn gauge_line<'a>(label: &str, percent: f64, limits: Limits, width: usize) -> Line<'a> {
+    let status = HealthStatus::from_limits(percent, limits);
     let color = status_color(&status);
 
     let filled = ((percent / 100.0) * width as f64).round() as usize;
@@ -334,8 +420,13 @@ pub fn health_gauge_line<'a>(
 }
 
 /// Simple gauge line with label + gauge + plain language description (User Mode CPU)
-pub fn health_gauge_line_simple<'a>(label: &str, percent: f64, bar_width: usize) -> Line<'a> {
-    let status = HealthStatus::from_percent(percent);
+pub fn health_gauge_line_simple<'a>(
+    label: &str,
+    percent: f64,
+    limits: Limits,
+    bar_width: usize,
+) -> Line<'a> {
+    let status = HealthStatus::from_limits(percent, limits);
     let color = status_color(&status);
     let filled = ((percent / 100.0) * bar_width as f64).round() as usize;
     let empty = bar_width.saturating_sub(filled);
@@ -11,8 +11,20 @@ use crate::ui::common::*;
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let mode = app.mode.unwrap_or(DiagnosticMode::User);
 
-    // Clock
-    let now = chrono_free_time();
+    // Clock, or the recorded time and playback state when replaying
+    let now = match app.replay.as_ref() {
+        Some(player) => format!(
+            "{} {}x  {} UTC",
+            if player.playing() {
+                "Playing"
+            } else {
+                "Paused"
+            },
+            player.speed(),
+            crate::replay::format_utc(player.frame().captured_unix_ms)
+        ),
+        None => chrono_free_time(),
+    };
 
     // Mode badge
     let (mode_label, mode_fg, mode_bg) = match mode {
@@ -44,15 +56,45 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled(clock_text, Style::default().fg(COLOR_MUTED)),
     ]);
 
-    let separator_line = Line::from(Span::styled(
-        "\u{2500}".repeat(area.width as usize),
-        Style::default().fg(COLOR_BORDER),
-    ));
+    let separator_line = match (app.active_notice(), app.replay.as_ref()) {
+        (Some(notice), _) => notice_line(notice, area.width as usize),
+        (None, Some(player)) => replay_controls_line(player, area.width as usize),
+        (None, None) => Line::from(Span::styled(
+            "\u{2500}".repeat(area.width as usize),
+            Style::default().fg(COLOR_BORDER),
+        )),
+    };
 
     let paragraph = Paragraph::new(vec![title_line, separator_line]);
     frame.render_widget(paragraph, area);
 }
 
+/// A status notice set into the separator rule
+fn notice_line(notice: &str, width: usize) -> Line<'static> {
+    let notice = format!(" {notice} ");
+    let fill = width.saturating_sub(2 + notice.chars().count());
+    let rule = Style::default().fg(COLOR_BORDER);
+    Line::from(vec![
+        Span::styled("\u{2500}\u{2500}", rule),
+        Span::styled(notice, Style::default().fg(COLOR_ACCENT)),
+        Span::styled("\u{2500}".repeat(fill), rule),
+    ])
+}
+
+/// Replay position and playback keys in place of the separator rule
+fn replay_controls_line(player: &crate::replay::Player, width: usize) -> Line<'static> {
+    let position = format!(" Replay {}/{} ", player.position() + 1, player.len());
+    let keys = " Space play/pause  \u{2190}/\u{2192} seek 10s  ,/. step  -/+ speed ";
+    let fill = width.saturating_sub(2 + position.chars().count() + keys.chars().count());
+    let rule = Style::default().fg(COLOR_BORDER);
+    Line::from(vec![
+        Span::styled("\u{2500}\u{2500}", rule),
+        Span::styled(position, Style::default().fg(COLOR_WARN)),
+        Span::styled("\u{2500}".repeat(fill), rule),
+        Span::styled(keys, Style::default().fg(COLOR_MUTED)),
+    ])
+}
+
 /// Get current time as HH:MM:SS (without chrono dependency)
 fn chrono_free_time() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
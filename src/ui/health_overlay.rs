@@ -0,0 +1,107 @@
+use ratatui::layout::{Alignment, Constraint, Flex, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::health::HealthExplanation;
+use crate::types::HealthStatus;
+use crate::ui::common::*;
+
+const WIDTH: u16 = 72;
+/// Reasons listed under one subsystem before the rest are counted.
+const REASONS_PER_SUBSYSTEM: usize = 3;
+
+/// "Why is this yellow?": every subsystem's score, and the reasons behind
+/// each one below good.
+pub fn render(frame: &mut Frame, area: Rect, health: &HealthExplanation) {
+    let title = match health.status {
+        HealthStatus::Warning => " Why is this yellow? ",
+        HealthStatus::Critical => " Why is this red? ",
+        HealthStatus::Good | HealthStatus::Unknown => " Health Breakdown ",
+    };
+    let block = Block::default()
+        .title(title)
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(COLOR_BORDER))
+        .title_style(
+            Style::default()
+                .fg(COLOR_ACCENT)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let text_width = usize::from(WIDTH) - 10;
+    let mut lines = vec![status_line(
+        &health.status,
+        "Overall",
+        &score_text(health.score),
+    )];
+    lines.push(Line::from(""));
+    for item in &health.subsystems {
+        let summary = match item.status {
+            // An unobserved subsystem has one reason: why
+            HealthStatus::Unknown => item
+                .reasons
+                .first()
+                .map(|reason| truncate_str(&reason.message, text_width - 14))
+                .unwrap_or_default(),
+            _ => score_text(item.score),
+        };
+        lines.push(status_line(&item.status, item.subsystem.label(), &summary));
+        if item.status == HealthStatus::Unknown {
+            continue;
+        }
+        for reason in item.reasons.iter().take(REASONS_PER_SUBSYSTEM) {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "      \u{2022} ",
+                    Style::default().fg(status_color(&reason.status)),
+                ),
+                Span::styled(
+                    truncate_str(&reason.message, text_width),
+                    Style::default().fg(COLOR_TEXT),
+                ),
+            ]));
+        }
+        let hidden = item.reasons.len().saturating_sub(REASONS_PER_SUBSYSTEM);
+        if hidden > 0 {
+            lines.push(Line::from(Span::styled(
+                format!("        and {hidden} more"),
+                Style::default().fg(COLOR_DIM),
+            )));
+        }
+    }
+    // Keep the close hint on screen when the reasons run long
+    let room = usize::from(area.height).saturating_sub(4);
+    if lines.len() > room {
+        lines.truncate(room.saturating_sub(1));
+        lines.push(Line::from(Span::styled(
+            "      \u{2026}",
+            Style::default().fg(COLOR_DIM),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  Press w or Esc to close",
+        Style::default().fg(COLOR_MUTED),
+    )));
+
+    let [center_y] = Layout::vertical([Constraint::Length(lines.len() as u16 + 2)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [center] = Layout::horizontal([Constraint::Length(WIDTH)])
+        .flex(Flex::Center)
+        .areas(center_y);
+
+    // Clear the area behind the overlay
+    frame.render_widget(Clear, center);
+
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, center);
+}
+
+fn score_text(score: Option<u8>) -> String {
+    score.map_or_else(|| "Not rated".into(), |score| format!("{score}/100"))
+}
@@ -6,17 +6,7 @@ use ratatui::Frame;
 
 use crate::ui::common::*;
 
-pub fn render(frame: &mut Frame, area: Rect) {
-    let [center_y] = Layout::vertical([Constraint::Length(23)])
-        .flex(Flex::Center)
-        .areas(area);
-    let [center] = Layout::horizontal([Constraint::Length(50)])
-        .flex(Flex::Center)
-        .areas(center_y);
-
-    // Clear the area behind the overlay
-    frame.render_widget(Clear, center);
-
+pub fn render(frame: &mut Frame, area: Rect, replay: bool) {
     let block = Block::default()
         .title(" Help \u{2014} Keybindings ")
         .title_alignment(Alignment::Center)
@@ -29,13 +19,12 @@ pub fn render(frame: &mut Frame, area: Rect) {
                 .add_modifier(Modifier::BOLD),
         );
 
-    let lines = vec![
-        Line::from(""),
-        help_line("1-9", "Switch to section"),
-        help_line("q / Esc", "Quit"),
-        help_line("Ctrl+C", "Quit to shell"),
+    let mut lines = vec![
+        help_line("0-9", "Switch to section"),
+        help_line("q/Esc/^C", "Quit"),
         help_line("m", "Mode selection screen"),
         help_line("?", "Toggle this help"),
+        help_line("w", "Why health is not green (User)"),
         Line::from(""),
         Line::from(Span::styled(
             " Process Table (Section 7, Tech Mode)",
@@ -45,6 +34,8 @@ pub fn render(frame: &mut Frame, area: Rect) {
         help_line("c", "Sort by CPU"),
         help_line("n", "Sort by name"),
         help_line("p", "Sort by PID"),
+        help_line("d", "Sort by disk I/O"),
+        help_line("g", "Sort by GPU memory"),
         Line::from(""),
         Line::from(Span::styled(
             " Connections (Section 6, Tech Mode)",
@@ -53,13 +44,30 @@ pub fn render(frame: &mut Frame, area: Rect) {
         help_line("j / k", "Scroll connections"),
         Line::from(""),
         help_line("f", "Toggle \u{00B0}C / \u{00B0}F"),
-        help_line("r", "Refresh drivers (Section 9)"),
-        Line::from(""),
-        Line::from(Span::styled(
-            "  Press ? or Esc to close",
-            Style::default().fg(COLOR_MUTED),
-        )),
+        help_line("t", "Sparkline time window"),
+        help_line("e", "Save current table as CSV"),
     ];
+    if replay {
+        // Playback keys are listed in the header while replaying; there is
+        // no live driver scan to refresh.
+        lines.push(help_line("Home / End", "First / last recorded frame"));
+    } else {
+        lines.push(help_line("r", "Refresh drivers (Section 9)"));
+    }
+    lines.push(Line::from(Span::styled(
+        "  Press ? or Esc to close",
+        Style::default().fg(COLOR_MUTED),
+    )));
+
+    let [center_y] = Layout::vertical([Constraint::Length(lines.len() as u16 + 2)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [center] = Layout::horizontal([Constraint::Length(50)])
+        .flex(Flex::Center)
+        .areas(center_y);
+
+    // Clear the area behind the overlay
+    frame.render_widget(Clear, center);
 
     let paragraph = Paragraph::new(lines).block(block);
     frame.render_widget(paragraph, center);
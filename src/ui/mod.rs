@@ -1,6 +1,7 @@
 pub mod bottom_bar;
 pub mod common;
 pub mod header_bar;
+pub mod health_overlay;
 pub mod help_overlay;
 pub mod mode_select;
 pub mod sections;
@@ -45,7 +46,9 @@ pub fn render(frame: &mut Frame, app: &App) {
 
     // Help overlay (on top of everything)
     if app.show_help {
-        help_overlay::render(frame, area);
+        help_overlay::render(frame, area, app.replay.is_some());
+    } else if app.show_health {
+        health_overlay::render(frame, area, &app.snapshot.health());
     }
 }
 
@@ -83,7 +86,7 @@ fn render_too_small(frame: &mut Frame) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{DiagnosticMode, Section};
+    use crate::types::{DiagnosticMode, Section, TimeWindow};
     use ratatui::{backend::TestBackend, Terminal};
 
     #[test]
@@ -110,6 +113,158 @@ mod tests {
         }
     }
 
+    #[test]
+    fn every_time_window_renders_spikes_and_titles_at_minimum_size() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Some(DiagnosticMode::Technician));
+        app.current_section = Section::Cpu;
+        for second in 0..(2 * 60 * 60) {
+            app.cpu_history
+                .push(if second == 1800 { 100.0 } else { 10.0 });
+        }
+
+        for window in [
+            TimeWindow::Minute,
+            TimeWindow::TenMinutes,
+            TimeWindow::Hour,
+            TimeWindow::SixHours,
+            TimeWindow::Day,
+        ] {
+            app.time_window = window;
+            terminal.draw(|frame| render(frame, &app)).unwrap();
+            let rendered = terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>();
+            assert!(
+                rendered.contains(&format!("Load History ({})", window.label())),
+                "{window:?} title"
+            );
+        }
+        assert_eq!(app.time_window.next(), TimeWindow::Minute);
+    }
+
+    #[test]
+    fn technician_overview_lists_drift_within_the_minimum_size() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Some(DiagnosticMode::Technician));
+        app.baseline = Some(crate::baseline::Baseline::new("lab", serde_json::json!({})));
+        let rendered = |terminal: &mut Terminal<TestBackend>, app: &App| {
+            terminal.draw(|frame| render(frame, app)).unwrap();
+            terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        };
+        assert!(rendered(&mut terminal, &app).contains("None since baseline 'lab'"));
+
+        for port in 0..8 {
+            app.snapshot
+                .warnings
+                .push(crate::collectors::DiagnosticWarning {
+                    source: crate::baseline::WARNING_SOURCE.into(),
+                    message: format!("New listening port TCP 0.0.0.0:{}", 8000 + port),
+                    severity: crate::collectors::WarningSeverity::Warning,
+                });
+        }
+        let text = rendered(&mut terminal, &app);
+        assert!(text.contains("8 change(s) since baseline 'lab'"));
+        assert!(text.contains("! New listening port TCP 0.0.0.0:8000"));
+        assert!(text.contains("more"));
+    }
+
+    #[test]
+    fn event_timeline_shows_the_newest_events_first() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Some(DiagnosticMode::Technician));
+        app.current_section = Section::Events;
+        app.snapshot.events = crate::events::EventLog::from(
+            (0..40)
+                .map(|n| crate::events::Event {
+                    unix_ms: 1_700_000_000_000 + n * 1000,
+                    kind: crate::events::EventKind::WarningRaised,
+                    severity: crate::collectors::WarningSeverity::Warning,
+                    source: "Network".into(),
+                    message: format!("Probe {n} failed"),
+                })
+                .collect::<Vec<_>>(),
+        );
+        terminal.draw(|frame| render(frame, &app)).unwrap();
+        let rendered = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+        assert!(rendered.contains("40 events"));
+        assert!(rendered.contains("2023-11-14 22:13:59"));
+        assert!(rendered.contains("Probe 39 failed"));
+        assert!(!rendered.contains("Probe 0 failed"));
+    }
+
+    #[test]
+    fn help_overlay_fits_the_minimum_terminal() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Some(DiagnosticMode::Technician));
+        app.show_help = true;
+        terminal.draw(|frame| render(frame, &app)).unwrap();
+        let rendered = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+        assert!(rendered.contains("Press ? or Esc to close"));
+        assert!(rendered.contains("Sparkline time window"));
+    }
+
+    #[test]
+    fn user_overview_explains_why_health_is_not_green() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let mut app = App::new(Some(DiagnosticMode::User));
+        app.snapshot.disk.partitions = vec![crate::collectors::disk::PartitionInfo {
+            name: "sda1".into(),
+            mount_point: "/".into(),
+            filesystem: "ext4".into(),
+            total_bytes: 100,
+            used_bytes: 80,
+            available_bytes: 20,
+            is_removable: false,
+            disk_type: crate::collectors::disk::DiskType::Ssd,
+        }];
+        let rendered = |terminal: &mut Terminal<TestBackend>, app: &App| {
+            terminal.draw(|frame| render(frame, app)).unwrap();
+            terminal
+                .backend()
+                .buffer()
+                .content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        };
+        assert!(rendered(&mut terminal, &app).contains("Needs attention (press w for why)"));
+
+        app.show_health = true;
+        let text = rendered(&mut terminal, &app);
+        assert!(text.contains("Why is this yellow?"));
+        assert!(text.contains("75/100"));
+        assert!(text.contains("Partition / is at 80% (warning from 75%)"));
+        assert!(text.contains("Press w or Esc to close"));
+    }
+
     #[test]
     fn renders_small_terminal_guard() {
         let backend = TestBackend::new(60, 18);
@@ -1,7 +1,7 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Paragraph, Sparkline};
+use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
 use crate::app::App;
@@ -31,7 +31,8 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
 
     // Status
     let cpu = &app.snapshot.cpu;
-    let status = HealthStatus::from_percent(cpu.total_usage as f64);
+    let thresholds = &app.snapshot.thresholds;
+    let status = HealthStatus::from_limits(cpu.total_usage as f64, thresholds.cpu);
     let unit = app.temp_unit;
     let temp_desc = app
         .snapshot
@@ -42,7 +43,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
                 "{} ({}) \u{2014} {}",
                 plain_language_temp(t),
                 format_temp(t, unit),
-                if t > TEMP_CPU_WARN {
+                if t >= thresholds.cpu_temperature.warning {
                     "This is expected when busy"
                 } else {
                     "Comfortable"
@@ -63,7 +64,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
     let status_lines = vec![
         Line::from(""),
         status_line(&status, "Status", plain_language_cpu(cpu.total_usage)),
-        health_gauge_line_simple("How busy", cpu.total_usage as f64, 20),
+        health_gauge_line_simple("How busy", cpu.total_usage as f64, thresholds.cpu, 20),
         Line::from(vec![
             Span::styled("  Temperature    ", Style::default().fg(COLOR_TEXT)),
             Span::styled(temp_desc, Style::default().fg(COLOR_DIM)),
@@ -77,15 +78,18 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(status_panel, chunks[0]);
 
     // Sparkline
-    let spark_data = app.cpu_history.as_u64_vec();
-    let sparkline_block = sub_block("Activity \u{2014} Last 60 Seconds");
-    let sparkline = Sparkline::default()
-        .block(sparkline_block)
-        .data(&spark_data)
-        .max(100)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_CPU));
-    frame.render_widget(sparkline, chunks[1]);
+    render_history(
+        frame,
+        chunks[1],
+        sub_block(&format!(
+            "Activity \u{2014} {}",
+            app.time_window.plain_label()
+        )),
+        &app.cpu_history,
+        app.time_window,
+        Some(100.0),
+        SPARK_CPU,
+    );
 
     // Top consumers
     let consumer_block = sub_block("What's Keeping It Busy");
@@ -150,8 +154,9 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("  Total Load  ", Style::default().fg(COLOR_DIM)),
             Span::styled(
                 gauge_bar(cpu.total_usage as f64, 20),
-                Style::default().fg(status_color(&HealthStatus::from_percent(
+                Style::default().fg(status_color(&HealthStatus::from_limits(
                     cpu.total_usage as f64,
+                    app.snapshot.thresholds.cpu,
                 ))),
             ),
             Span::styled(
@@ -187,7 +192,10 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
             // 16-char gauge: fits 50% horizontal split at 80-col minimum
             Span::styled(
                 gauge_bar(*usage as f64, 16),
-                Style::default().fg(status_color(&HealthStatus::from_percent(*usage as f64))),
+                Style::default().fg(status_color(&HealthStatus::from_limits(
+                    *usage as f64,
+                    app.snapshot.thresholds.cpu,
+                ))),
             ),
             Span::styled(
                 format!("  {} MHz", freq_val),
@@ -204,14 +212,15 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(core_panel, per_core_inner);
 
     // Load history sparkline
-    let spark_data = app.cpu_history.as_u64_vec();
-    let sparkline = Sparkline::default()
-        .block(sub_block("Load History (60s)"))
-        .data(&spark_data)
-        .max(100)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_CPU));
-    frame.render_widget(sparkline, core_chunks[1]);
+    render_history(
+        frame,
+        core_chunks[1],
+        sub_block(&format!("Load History ({})", app.time_window.label())),
+        &app.cpu_history,
+        app.time_window,
+        Some(100.0),
+        SPARK_CPU,
+    );
 
     // Process table
     let proc_block = sub_block("Top CPU Consumers");
@@ -32,7 +32,8 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
     } else {
         for (i, part) in app.snapshot.disk.partitions.iter().enumerate() {
             let pct = part.usage_percent();
-            let status = HealthStatus::from_percent(pct);
+            let limits = app.snapshot.thresholds.disk_for(part);
+            let status = HealthStatus::from_limits(pct, limits);
 
             let type_desc = match part.disk_type {
                 DiskType::Ssd => "Fast solid-state drive",
@@ -72,7 +73,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
                     Style::default().fg(COLOR_TEXT),
                 ),
             ]));
-            lines.push(gauge_line(&format!("    {}", name), pct, 20));
+            lines.push(gauge_line(&format!("    {}", name), pct, limits, 20));
 
             // Real health from disk_health collector
             let drive_health = if app.snapshot.disk_health.drives.len() == 1 {
@@ -137,7 +138,10 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
 
     for part in &app.snapshot.disk.partitions {
         let pct = part.usage_percent();
-        let color = status_color(&HealthStatus::from_percent(pct));
+        let color = status_color(&HealthStatus::from_limits(
+            pct,
+            app.snapshot.thresholds.disk_for(part),
+        ));
 
         part_lines.push(Line::from(vec![
             Span::styled(
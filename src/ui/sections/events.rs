@@ -0,0 +1,110 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::collectors::WarningSeverity;
+use crate::events::Event;
+use crate::types::DiagnosticMode;
+use crate::ui::common::*;
+
+pub fn render(frame: &mut Frame, app: &App, area: Rect, mode: DiagnosticMode) {
+    let events = &app.snapshot.events;
+    let title = match mode {
+        DiagnosticMode::User => "What Changed",
+        DiagnosticMode::Technician => "Event Timeline",
+    };
+    let outer = content_block(&format!(
+        "{title} \u{2014} {} event{}, newest first (UTC)",
+        events.len(),
+        if events.len() == 1 { "" } else { "s" }
+    ));
+    let inner = outer.inner(area);
+    frame.render_widget(outer, area);
+
+    let mut lines = vec![Line::from("")];
+    if events.is_empty() {
+        lines.push(Line::from(Span::styled(
+            match mode {
+                DiagnosticMode::User => "  Nothing has changed since SD-300 started.",
+                DiagnosticMode::Technician => {
+                    "  No transitions recorded yet. Warnings, device status, connectivity and heavy processes are tracked from startup."
+                }
+            },
+            Style::default().fg(COLOR_MUTED),
+        )));
+        frame.render_widget(Paragraph::new(lines), inner);
+        return;
+    }
+
+    if mode == DiagnosticMode::Technician {
+        lines.push(Line::from(Span::styled(
+            format!("  {:<19}  {:<7} {:<12} MESSAGE", "TIME", "LEVEL", "SOURCE"),
+            Style::default().fg(COLOR_DIM),
+        )));
+    }
+
+    let visible = (inner.height as usize).saturating_sub(lines.len());
+    let scroll = app
+        .events_scroll
+        .min(events.len().saturating_sub(visible.max(1)));
+    let width = inner.width as usize;
+    for event in events.iter().rev().skip(scroll).take(visible) {
+        lines.push(match mode {
+            DiagnosticMode::User => user_line(event, width),
+            DiagnosticMode::Technician => tech_line(event, width),
+        });
+    }
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn user_line(event: &Event, width: usize) -> Line<'static> {
+    let time = crate::replay::format_utc(event.unix_ms);
+    let (icon, color) = severity_style(&event.severity);
+    Line::from(vec![
+        Span::styled(
+            format!("  {}  ", &time[11..]),
+            Style::default().fg(COLOR_DIM),
+        ),
+        Span::styled(format!("{icon} "), Style::default().fg(color)),
+        Span::styled(
+            truncate_str(&event.message, width.saturating_sub(14)),
+            Style::default().fg(COLOR_TEXT),
+        ),
+    ])
+}
+
+fn tech_line(event: &Event, width: usize) -> Line<'static> {
+    let (_, color) = severity_style(&event.severity);
+    let level = match event.severity {
+        WarningSeverity::Info => "info",
+        WarningSeverity::Warning => "warning",
+        WarningSeverity::Error => "error",
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("  {}  ", crate::replay::format_utc(event.unix_ms)),
+            Style::default().fg(COLOR_DIM),
+        ),
+        Span::styled(format!("{level:<7} "), Style::default().fg(color)),
+        Span::styled(
+            format!("{:<12} ", truncate_str(&event.source, 12)),
+            Style::default().fg(COLOR_MUTED),
+        ),
+        Span::styled(
+            truncate_str(&event.message, width.saturating_sub(44)),
+            Style::default().fg(COLOR_TEXT),
+        ),
+    ])
+}
+
+fn severity_style(severity: &WarningSeverity) -> (&'static str, Color) {
+    match severity {
+        WarningSeverity::Info => ("\u{2022}", COLOR_INFO),
+        WarningSeverity::Warning => ("\u{26A0}", COLOR_WARN),
+        WarningSeverity::Error => ("\u{2717}", COLOR_CRIT),
+    }
+}
@@ -1,7 +1,7 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Paragraph, Sparkline};
+use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
 use crate::app::App;
@@ -66,7 +66,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
     let gpu = &app.snapshot.gpu;
     let util = gpu.utilization_percent;
     let status = if gpu.telemetry_available {
-        HealthStatus::from_percent(util as f64)
+        HealthStatus::from_limits(util as f64, app.snapshot.thresholds.gpu)
     } else {
         HealthStatus::Unknown
     };
@@ -114,7 +114,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
                     Style::default().fg(COLOR_DIM),
                 ),
             ]),
-            gauge_line("GPU", util as f64, 20),
+            gauge_line("GPU", util as f64, app.snapshot.thresholds.gpu, 20),
             Line::from(vec![
                 Span::styled("  Memory         ", Style::default().fg(COLOR_TEXT)),
                 Span::styled(
@@ -241,14 +241,15 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(info_panel, chunks[0]);
 
     // GPU history sparkline
-    let spark_data = app.gpu_history.as_u64_vec();
-    let sparkline = Sparkline::default()
-        .block(sub_block("GPU Utilization (60s)"))
-        .data(&spark_data)
-        .max(100)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_GPU));
-    frame.render_widget(sparkline, chunks[1]);
+    render_history(
+        frame,
+        chunks[1],
+        sub_block(&format!("GPU Utilization ({})", app.time_window.label())),
+        &app.gpu_history,
+        app.time_window,
+        Some(100.0),
+        SPARK_GPU,
+    );
 }
 
 fn simplify_gpu_name(name: &str) -> String {
@@ -1,7 +1,7 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Paragraph, Sparkline};
+use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
 use crate::app::App;
@@ -31,7 +31,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
 
     let mem = &app.snapshot.memory;
     let pct = mem.usage_percent();
-    let status = HealthStatus::from_percent(pct);
+    let status = HealthStatus::from_limits(pct, app.snapshot.thresholds.memory);
     let used_gb = mem.used_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
     let total_gb = mem.total_bytes as f64 / (1024.0 * 1024.0 * 1024.0);
 
@@ -47,7 +47,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(COLOR_TEXT),
             ),
         ]),
-        gauge_line("Usage", pct, 20),
+        gauge_line("Usage", pct, app.snapshot.thresholds.memory, 20),
         Line::from(vec![
             Span::styled("  Status        ", Style::default().fg(COLOR_TEXT)),
             Span::styled(
@@ -87,14 +87,15 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(status_panel, chunks[0]);
 
     // Sparkline
-    let spark_data = app.mem_history.as_u64_vec();
-    let sparkline = Sparkline::default()
-        .block(sub_block("Usage \u{2014} Last 60 Seconds"))
-        .data(&spark_data)
-        .max(100)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_MEMORY));
-    frame.render_widget(sparkline, chunks[1]);
+    render_history(
+        frame,
+        chunks[1],
+        sub_block(&format!("Usage \u{2014} {}", app.time_window.plain_label())),
+        &app.mem_history,
+        app.time_window,
+        Some(100.0),
+        SPARK_MEMORY,
+    );
 
     // Top consumers
     let consumer_block = sub_block("What's Using the Most Memory");
@@ -152,16 +153,18 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled("  RAM  ", Style::default().fg(COLOR_DIM)),
         Span::styled(
             gauge_bar(mem.usage_percent(), 20),
-            Style::default().fg(status_color(&HealthStatus::from_percent(
+            Style::default().fg(status_color(&HealthStatus::from_limits(
                 mem.usage_percent(),
+                app.snapshot.thresholds.memory,
             ))),
         ),
         Span::raw("    "),
         Span::styled("Swap ", Style::default().fg(COLOR_DIM)),
         Span::styled(
             gauge_bar(mem.swap_percent(), 20),
-            Style::default().fg(status_color(&HealthStatus::from_percent(
+            Style::default().fg(status_color(&HealthStatus::from_limits(
                 mem.swap_percent(),
+                app.snapshot.thresholds.memory,
             ))),
         ),
     ])];
@@ -175,23 +178,24 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(spark_area);
 
-    let mem_spark = app.mem_history.as_u64_vec();
-    let mem_sparkline = Sparkline::default()
-        .block(sub_block("RAM Usage"))
-        .data(&mem_spark)
-        .max(100)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_MEMORY));
-    frame.render_widget(mem_sparkline, spark_chunks[0]);
-
-    let swap_spark = app.swap_history.as_u64_vec();
-    let swap_sparkline = Sparkline::default()
-        .block(sub_block("Swap Usage"))
-        .data(&swap_spark)
-        .max(100)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_SWAP));
-    frame.render_widget(swap_sparkline, spark_chunks[1]);
+    render_history(
+        frame,
+        spark_chunks[0],
+        sub_block(&format!("RAM Usage ({})", app.time_window.label())),
+        &app.mem_history,
+        app.time_window,
+        Some(100.0),
+        SPARK_MEMORY,
+    );
+    render_history(
+        frame,
+        spark_chunks[1],
+        sub_block(&format!("Swap Usage ({})", app.time_window.label())),
+        &app.swap_history,
+        app.time_window,
+        Some(100.0),
+        SPARK_SWAP,
+    );
 
     let module_block = sub_block("Physical Memory Modules");
     let module_inner = module_block.inner(chunks[2]);
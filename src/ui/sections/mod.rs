@@ -1,6 +1,7 @@
 pub mod cpu;
 pub mod disk;
 pub mod drivers;
+pub mod events;
 pub mod gpu;
 pub mod memory;
 pub mod network;
@@ -28,5 +29,6 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         Section::Processes => processes::render(frame, app, area, mode),
         Section::Thermals => thermals::render(frame, app, area, mode),
         Section::Drivers => drivers::render(frame, app, area, mode),
+        Section::Events => events::render(frame, app, area, mode),
     }
 }
@@ -1,7 +1,7 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Cell, Paragraph, Row, Sparkline, Table};
+use ratatui::widgets::{Cell, Paragraph, Row, Table};
 use ratatui::Frame;
 
 use crate::app::App;
@@ -159,15 +159,18 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(status_panel, chunks[0]);
 
     // Sparkline
-    let spark_data = app.net_down_history.as_u64_vec();
-    let max_val = spark_data.iter().copied().max().unwrap_or(1).max(1);
-    let sparkline = Sparkline::default()
-        .block(sub_block("Download Speed (last 60 seconds)"))
-        .data(&spark_data)
-        .max(max_val)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_NET_DOWN));
-    frame.render_widget(sparkline, chunks[1]);
+    render_history(
+        frame,
+        chunks[1],
+        sub_block(&format!(
+            "Download Speed ({})",
+            app.time_window.plain_label().to_lowercase()
+        )),
+        &app.net_down_history,
+        app.time_window,
+        None,
+        SPARK_NET_DOWN,
+    );
 }
 
 fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
@@ -432,23 +435,22 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(spark_area);
 
-    let down_data = app.net_down_history.as_u64_vec();
-    let down_max = down_data.iter().copied().max().unwrap_or(1).max(1);
-    let down_sparkline = Sparkline::default()
-        .block(sub_block("Download"))
-        .data(&down_data)
-        .max(down_max)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_NET_DOWN));
-    frame.render_widget(down_sparkline, spark_chunks[0]);
-
-    let up_data = app.net_up_history.as_u64_vec();
-    let up_max = up_data.iter().copied().max().unwrap_or(1).max(1);
-    let up_sparkline = Sparkline::default()
-        .block(sub_block("Upload"))
-        .data(&up_data)
-        .max(up_max)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_NET_UP));
-    frame.render_widget(up_sparkline, spark_chunks[1]);
+    render_history(
+        frame,
+        spark_chunks[0],
+        sub_block(&format!("Download ({})", app.time_window.label())),
+        &app.net_down_history,
+        app.time_window,
+        None,
+        SPARK_NET_DOWN,
+    );
+    render_history(
+        frame,
+        spark_chunks[1],
+        sub_block(&format!("Upload ({})", app.time_window.label())),
+        &app.net_up_history,
+        app.time_window,
+        None,
+        SPARK_NET_UP,
+    );
 }
@@ -16,8 +16,14 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect, mode: DiagnosticMode) {
 }
 
 fn render_user(frame: &mut Frame, app: &App, area: Rect) {
-    // Outer content block
-    let outer = content_block("System Health");
+    // Outer content block, titled with the overall verdict
+    let title = match app.overall_health() {
+        HealthStatus::Good => "System Health: Good",
+        HealthStatus::Warning => "System Health: Needs attention (press w for why)",
+        HealthStatus::Critical => "System Health: Act now (press w for why)",
+        HealthStatus::Unknown => "System Health",
+    };
+    let outer = content_block(title);
     let inner = outer.inner(area);
     frame.render_widget(outer, area);
 
@@ -33,24 +39,19 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
     // System Health panel
     let cpu_pct = app.snapshot.cpu.total_usage;
     let mem_pct = app.snapshot.memory.usage_percent();
-    let disk_pct = app
-        .snapshot
-        .disk
-        .partitions
-        .first()
-        .map(|d| d.usage_percent())
-        .unwrap_or(0.0);
+    let first_disk = app.snapshot.disk.partitions.first();
+    let disk_pct = first_disk.map(|d| d.usage_percent()).unwrap_or(0.0);
     let gpu_pct = app.snapshot.gpu.utilization_percent;
+    let thresholds = &app.snapshot.thresholds;
 
-    let cpu_status = HealthStatus::from_percent(cpu_pct as f64);
-    let mem_status = HealthStatus::from_percent(mem_pct);
-    let disk_status = if app.snapshot.disk.partitions.is_empty() {
-        HealthStatus::Unknown
-    } else {
-        HealthStatus::from_percent(disk_pct)
+    let cpu_status = HealthStatus::from_limits(cpu_pct as f64, thresholds.cpu);
+    let mem_status = HealthStatus::from_limits(mem_pct, thresholds.memory);
+    let disk_status = match first_disk {
+        Some(disk) => HealthStatus::from_limits(disk_pct, thresholds.disk_for(disk)),
+        None => HealthStatus::Unknown,
     };
     let gpu_status = if app.snapshot.gpu.telemetry_available {
-        HealthStatus::from_percent(gpu_pct as f64)
+        HealthStatus::from_limits(gpu_pct as f64, thresholds.gpu)
     } else {
         HealthStatus::Unknown
     };
@@ -69,7 +70,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
     let thermal = &app.snapshot.thermals;
     let (temp_status, temp_desc) = if let Some(temp) = thermal.cpu_temp {
         (
-            temperature_health(temp, TEMP_CPU_WARN, TEMP_CPU_CRIT),
+            HealthStatus::from_limits(temp, thresholds.cpu_temperature),
             format!(
                 "Processor {} ({})",
                 plain_language_temp(temp).to_ascii_lowercase(),
@@ -78,7 +79,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
         )
     } else if let Some(temp) = thermal.gpu_temp {
         (
-            temperature_health(temp, TEMP_GPU_WARN, TEMP_GPU_CRIT),
+            HealthStatus::from_limits(temp, thresholds.gpu_temperature),
             format!(
                 "Graphics {} ({}); {}",
                 plain_language_temp(temp).to_ascii_lowercase(),
@@ -378,16 +379,18 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("  CPU ", Style::default().fg(COLOR_DIM)),
             Span::styled(
                 gauge_bar(app.snapshot.cpu.total_usage as f64, 20),
-                Style::default().fg(status_color(&HealthStatus::from_percent(
+                Style::default().fg(status_color(&HealthStatus::from_limits(
                     app.snapshot.cpu.total_usage as f64,
+                    app.snapshot.thresholds.cpu,
                 ))),
             ),
             Span::raw("   "),
             Span::styled("MEM ", Style::default().fg(COLOR_DIM)),
             Span::styled(
                 gauge_bar(app.snapshot.memory.usage_percent(), 20),
-                Style::default().fg(status_color(&HealthStatus::from_percent(
+                Style::default().fg(status_color(&HealthStatus::from_limits(
                     app.snapshot.memory.usage_percent(),
+                    app.snapshot.thresholds.memory,
                 ))),
             ),
         ]),
@@ -400,8 +403,9 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
                     format!("{:<20}", "N/A")
                 },
                 Style::default().fg(if app.snapshot.gpu.telemetry_available {
-                    status_color(&HealthStatus::from_percent(
+                    status_color(&HealthStatus::from_limits(
                         app.snapshot.gpu.utilization_percent as f64,
+                        app.snapshot.thresholds.gpu,
                     ))
                 } else {
                     COLOR_DIM
@@ -411,8 +415,9 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("SWP ", Style::default().fg(COLOR_DIM)),
             Span::styled(
                 gauge_bar(app.snapshot.memory.swap_percent(), 20),
-                Style::default().fg(status_color(&HealthStatus::from_percent(
+                Style::default().fg(status_color(&HealthStatus::from_limits(
                     app.snapshot.memory.swap_percent(),
+                    app.snapshot.thresholds.memory,
                 ))),
             ),
         ]),
@@ -431,8 +436,9 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
             ),
             Span::styled(
                 gauge_bar(part.usage_percent(), 20),
-                Style::default().fg(status_color(&HealthStatus::from_percent(
+                Style::default().fg(status_color(&HealthStatus::from_limits(
                     part.usage_percent(),
+                    app.snapshot.thresholds.disk_for(part),
                 ))),
             ),
             Span::styled(
@@ -468,15 +474,35 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
     let proc_inner = proc_block.inner(chunks[3]);
     frame.render_widget(proc_block, chunks[3]);
 
-    let mut proc_lines = vec![Line::from(vec![Span::styled(
-        format!(
-            "  {:<28} {:>6} {:>8} {:>8} {:>10}",
-            "PROCESS", "PID", "CPU%", "MEM%", "MEM"
-        ),
-        Style::default().fg(COLOR_DIM),
-    )])];
+    // An active baseline adds a summary line plus one line per drift item.
+    // They take the process rows first; without a baseline the panel is
+    // unchanged.
+    let drift: Vec<_> = app
+        .snapshot
+        .warnings
+        .iter()
+        .filter(|w| w.source == crate::baseline::WARNING_SOURCE)
+        .collect();
+    let process_rows = if app.baseline.is_some() {
+        (proc_inner.height as usize)
+            .saturating_sub(4 + drift.len())
+            .min(5)
+    } else {
+        5
+    };
 
-    for proc in app.snapshot.processes.list.iter().take(5) {
+    let mut proc_lines = Vec::new();
+    if process_rows > 0 {
+        proc_lines.push(Line::from(vec![Span::styled(
+            format!(
+                "  {:<28} {:>6} {:>8} {:>8} {:>10}",
+                "PROCESS", "PID", "CPU%", "MEM%", "MEM"
+            ),
+            Style::default().fg(COLOR_DIM),
+        )]));
+    }
+
+    for proc in app.snapshot.processes.list.iter().take(process_rows) {
         proc_lines.push(Line::from(Span::styled(
             format!(
                 "  {:<28} {:>6} {:>7.1}% {:>7.1}% {:>10}",
@@ -491,7 +517,9 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     // Driver + Thermal summary
-    proc_lines.push(Line::from(""));
+    if process_rows > 0 {
+        proc_lines.push(Line::from(""));
+    }
     let driver_ok = app.snapshot.drivers.attention_devices().next().is_none();
 
     let temp_str = app
@@ -517,20 +545,51 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
         Span::styled(temp_str, Style::default().fg(COLOR_TEXT)),
     ]));
 
+    if let Some(baseline) = &app.baseline {
+        let (summary, color) = if drift.is_empty() {
+            (
+                format!("None since baseline '{}'", baseline.name),
+                COLOR_GOOD,
+            )
+        } else {
+            (
+                format!(
+                    "{} change(s) since baseline '{}'",
+                    drift.len(),
+                    baseline.name
+                ),
+                COLOR_WARN,
+            )
+        };
+        proc_lines.push(Line::from(vec![
+            Span::styled("  DRIFT  ", Style::default().fg(COLOR_DIM)),
+            Span::styled(summary, Style::default().fg(color)),
+        ]));
+        let room = (proc_inner.height as usize).saturating_sub(proc_lines.len());
+        let shown = if drift.len() > room {
+            room.saturating_sub(1)
+        } else {
+            drift.len()
+        };
+        let width = (proc_inner.width as usize).saturating_sub(6);
+        for warning in drift.iter().take(shown) {
+            proc_lines.push(Line::from(Span::styled(
+                format!("    ! {}", truncate_str(&warning.message, width)),
+                Style::default().fg(COLOR_WARN),
+            )));
+        }
+        if shown < drift.len() && room > 0 {
+            proc_lines.push(Line::from(Span::styled(
+                format!("    ... and {} more", drift.len() - shown),
+                Style::default().fg(COLOR_DIM),
+            )));
+        }
+    }
+
     let proc_panel = Paragraph::new(proc_lines);
     frame.render_widget(proc_panel, proc_inner);
 }
 
-fn temperature_health(value: f64, warning: f64, critical: f64) -> HealthStatus {
-    if value < warning {
-        HealthStatus::Good
-    } else if value < critical {
-        HealthStatus::Warning
-    } else {
-        HealthStatus::Critical
-    }
-}
-
 fn cpu_temperature_gap(thermal: &crate::collectors::thermals::ThermalData) -> &'static str {
     use crate::observation::ObservationStatus;
 
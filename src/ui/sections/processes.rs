@@ -5,6 +5,8 @@ use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
 use crate::app::App;
+use crate::collectors::memory_growth::MemoryTrend;
+use crate::collectors::processes::ProcessInfo;
 use crate::types::{DiagnosticMode, ProcessSortKey};
 use crate::ui::common::*;
 
@@ -69,12 +71,14 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
         ProcessSortKey::Memory => "MEM%",
         ProcessSortKey::Pid => "PID",
         ProcessSortKey::Name => "Name",
+        ProcessSortKey::DiskIo => "Disk I/O",
+        ProcessSortKey::GpuMemory => "GPU MEM",
     };
 
     let header_lines = vec![
         Line::from(Span::styled(
             format!(
-                "  Sorted by {}    Sort: [c]pu  [M]emory  [p]id  [n]ame    Scroll: j/k or arrows",
+                "  Sorted by {}    Sort: [c]pu  [M]emory  [p]id  [n]ame  [d]isk  [g]pu    Scroll: j/k or arrows",
                 sort_indicator
             ),
             Style::default().fg(COLOR_MUTED),
@@ -82,8 +86,17 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
         Line::from(""),
         Line::from(Span::styled(
             format!(
-                "  {:<28} {:>6} {:>8} {:>8} {:>10} {:>8}",
-                "NAME", "PID", "CPU%", "MEM%", "MEMORY", "STATUS"
+                "  {:<28} {:>6} {:>8} {:>8} {:>10} {:>2} {:>10} {:>10} {:>10} {:>8}",
+                "NAME",
+                "PID",
+                "CPU%",
+                "MEM%",
+                "MEMORY",
+                "\u{0394}",
+                "READ/s",
+                "WRITE/s",
+                "GPU MEM",
+                "STATUS"
             ),
             Style::default().fg(COLOR_DIM).add_modifier(Modifier::BOLD),
         )),
@@ -91,20 +104,8 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
     let header_panel = Paragraph::new(header_lines);
     frame.render_widget(header_panel, chunks[0]);
 
-    // Sort processes
     let mut sorted_procs = app.snapshot.processes.list.clone();
-    match app.process_sort {
-        ProcessSortKey::Cpu => sorted_procs.sort_by(|a, b| {
-            b.cpu_percent
-                .partial_cmp(&a.cpu_percent)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        }),
-        ProcessSortKey::Memory => {
-            sorted_procs.sort_by_key(|proc| std::cmp::Reverse(proc.memory_bytes))
-        }
-        ProcessSortKey::Pid => sorted_procs.sort_by_key(|proc| proc.pid),
-        ProcessSortKey::Name => sorted_procs.sort_by_key(|proc| proc.name.to_lowercase()),
-    }
+    sort_for_table(&mut sorted_procs, app.process_sort);
 
     // Visible rows (reserve 1 line for scroll indicator)
     let visible_height = chunks[1].height.saturating_sub(1) as usize;
@@ -123,18 +124,41 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
             Style::default().fg(COLOR_TEXT)
         };
 
-        proc_lines.push(Line::from(Span::styled(
-            format!(
-                "  {:<28} {:>6} {:>7.1}% {:>7.1}% {:>10} {:>8}",
-                truncate_str(&proc.name, 28),
-                proc.pid,
-                proc.cpu_percent,
-                proc.memory_percent,
-                format_bytes(proc.memory_bytes),
-                truncate_str(&proc.status, 8)
+        // Memory trend fitted across refreshes; blank until enough samples exist
+        let trend = app.snapshot.memory_growth.trend(proc.pid);
+        let trend_style = match trend {
+            Some(MemoryTrend::Growing) => Style::default().fg(COLOR_WARN),
+            Some(MemoryTrend::Shrinking) => Style::default().fg(COLOR_GOOD),
+            _ => Style::default().fg(COLOR_DIM),
+        };
+
+        proc_lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  {:<28} {:>6} {:>7.1}% {:>7.1}% {:>10} ",
+                    truncate_str(&proc.name, 28),
+                    proc.pid,
+                    proc.cpu_percent,
+                    proc.memory_percent,
+                    format_bytes(proc.memory_bytes),
+                ),
+                style,
+            ),
+            Span::styled(
+                format!("{:>2}", trend.map(|trend| trend.arrow()).unwrap_or("")),
+                trend_style,
+            ),
+            Span::styled(
+                format!(
+                    " {:>10} {:>10} {:>10} {:>8}",
+                    quiet_if_zero(proc.disk_read_bytes_per_sec, format_throughput),
+                    quiet_if_zero(proc.disk_write_bytes_per_sec, format_throughput),
+                    quiet_if_zero(proc.gpu_memory_bytes.unwrap_or(0), format_bytes),
+                    truncate_str(&proc.status, 8)
+                ),
+                style,
             ),
-            style,
-        )));
+        ]));
     }
 
     // Scroll indicator
@@ -147,3 +171,35 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
     let proc_panel = Paragraph::new(proc_lines);
     frame.render_widget(proc_panel, chunks[1]);
 }
+
+/// The technician table's row order, shared with the export key so a saved
+/// table matches what was on screen.
+pub fn sort_for_table(processes: &mut [ProcessInfo], sort: ProcessSortKey) {
+    match sort {
+        ProcessSortKey::Cpu => processes.sort_by(|a, b| {
+            b.cpu_percent
+                .partial_cmp(&a.cpu_percent)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        }),
+        ProcessSortKey::Memory => {
+            processes.sort_by_key(|proc| std::cmp::Reverse(proc.memory_bytes))
+        }
+        ProcessSortKey::Pid => processes.sort_by_key(|proc| proc.pid),
+        ProcessSortKey::Name => processes.sort_by_key(|proc| proc.name.to_lowercase()),
+        ProcessSortKey::DiskIo => {
+            processes.sort_by_key(|proc| std::cmp::Reverse(proc.disk_bytes_per_sec()))
+        }
+        ProcessSortKey::GpuMemory => {
+            processes.sort_by_key(|proc| std::cmp::Reverse(proc.gpu_memory_bytes))
+        }
+    }
+}
+
+/// Most processes do no disk I/O and hold no GPU memory; keep those cells quiet.
+fn quiet_if_zero(value: u64, format: fn(u64) -> String) -> String {
+    if value == 0 {
+        "-".into()
+    } else {
+        format(value)
+    }
+}
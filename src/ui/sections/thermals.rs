@@ -1,7 +1,7 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Style;
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Paragraph, Sparkline};
+use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
 use crate::app::App;
@@ -45,13 +45,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
             plain_language_temp(temp),
             format_temp(temp, unit)
         );
-        let status = if temp < TEMP_CPU_WARN {
-            HealthStatus::Good
-        } else if temp < TEMP_CPU_CRIT {
-            HealthStatus::Warning
-        } else {
-            HealthStatus::Critical
-        };
+        let status = HealthStatus::from_limits(temp, app.snapshot.thresholds.cpu_temperature);
         lines.push(status_line(&status, "Processor", &desc));
     } else {
         lines.push(status_line(
@@ -68,13 +62,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
             plain_language_temp(temp),
             format_temp(temp, unit)
         );
-        let status = if temp < TEMP_GPU_WARN {
-            HealthStatus::Good
-        } else if temp < TEMP_GPU_CRIT {
-            HealthStatus::Warning
-        } else {
-            HealthStatus::Critical
-        };
+        let status = HealthStatus::from_limits(temp, app.snapshot.thresholds.gpu_temperature);
         lines.push(status_line(&status, "Graphics", &desc));
     } else {
         lines.push(status_line(
@@ -90,13 +78,10 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
         .filter(|sensor| sensor.kind == SensorKind::Other)
         .take(3)
     {
-        let status = if sensor.temperature < TEMP_CPU_WARN {
-            HealthStatus::Good
-        } else if sensor.temperature < TEMP_CPU_CRIT {
-            HealthStatus::Warning
-        } else {
-            HealthStatus::Critical
-        };
+        let status = HealthStatus::from_limits(
+            sensor.temperature,
+            app.snapshot.thresholds.sensor_for(&sensor.label),
+        );
         lines.push(status_line(
             &status,
             &truncate_str(&sensor.label, 16),
@@ -147,7 +132,7 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(COLOR_TEXT),
             ),
         ]));
-        lines.push(gauge_line("Battery", bat.percent, 20));
+        lines.push(gauge_line("Battery", bat.percent, BATTERY_GAUGE_LIMITS, 20));
     }
 
     // Power source
@@ -165,21 +150,22 @@ fn render_user(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(panel, chunks[0]);
 
     // Temperature history sparkline (now in user mode too)
-    let spark_data = app.temp_history.as_u64_vec();
     let history_label = if thermal.cpu_temp.is_some() {
-        "CPU Temperature (60s)"
+        "CPU Temperature"
     } else if thermal.gpu_temp.is_some() {
-        "GPU Temperature (60s)"
+        "GPU Temperature"
     } else {
-        "Temperature History (60s)"
+        "Temperature History"
     };
-    let sparkline = Sparkline::default()
-        .block(sub_block(history_label))
-        .data(&spark_data)
-        .max(100)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_TEMP));
-    frame.render_widget(sparkline, chunks[1]);
+    render_history(
+        frame,
+        chunks[1],
+        sub_block(&format!("{history_label} ({})", app.time_window.label())),
+        &app.temp_history,
+        app.time_window,
+        Some(100.0),
+        SPARK_TEMP,
+    );
 }
 
 fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
@@ -236,9 +222,12 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
             .map(|c| format_temp(c, unit))
             .unwrap_or_else(|| "N/A".into());
 
-        let color = if sensor.temperature > sensor.critical.unwrap_or(100.0) {
+        let limits = app.snapshot.thresholds.sensor_for(&sensor.label);
+        let color = if sensor.temperature > sensor.critical.unwrap_or(100.0)
+            || sensor.temperature >= limits.critical
+        {
             COLOR_CRIT
-        } else if sensor.temperature > TEMP_CPU_WARN {
+        } else if sensor.temperature >= limits.warning {
             COLOR_WARN
         } else {
             COLOR_TEXT
@@ -334,21 +323,22 @@ fn render_tech(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(sensor_panel, chunks[1]);
 
     // Temperature history sparkline
-    let spark_data = app.temp_history.as_u64_vec();
     let history_label = if thermal.cpu_temp.is_some() {
-        "CPU Temperature (60s)"
+        "CPU Temperature"
     } else if thermal.gpu_temp.is_some() {
-        "GPU Temperature (60s)"
+        "GPU Temperature"
     } else {
-        "Temperature History (60s)"
+        "Temperature History"
     };
-    let sparkline = Sparkline::default()
-        .block(sub_block(history_label))
-        .data(&spark_data)
-        .max(100)
-        .bar_set(sparkline_bar_set())
-        .style(Style::default().fg(SPARK_TEMP));
-    frame.render_widget(sparkline, chunks[2]);
+    render_history(
+        frame,
+        chunks[2],
+        sub_block(&format!("{history_label} ({})", app.time_window.label())),
+        &app.temp_history,
+        app.time_window,
+        Some(100.0),
+        SPARK_TEMP,
+    );
 }
 
 fn observation_gap<'a>(observation: &'a Observation, fallback: &'a str) -> &'a str {
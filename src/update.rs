@@ -2369,7 +2369,7 @@ fn tool_exists(tool: &str) -> bool {
 /// Resolve the trusted System32 image directly instead and fall back to a
 /// PATH-resolved PowerShell 7 only when the in-box shell is absent.
 #[cfg(windows)]
-fn windows_powershell_program() -> std::result::Result<std::ffi::OsString, String> {
+pub(crate) fn windows_powershell_program() -> std::result::Result<std::ffi::OsString, String> {
     if let Ok(powershell) =
         trusted_windows_system_executable(Path::new("WindowsPowerShell\\v1.0\\powershell.exe"))
     {
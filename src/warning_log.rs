@@ -0,0 +1,389 @@
+//! `--log-warnings`: diagnostic warnings in the system log.
+//!
+//! The background modes (`serve`, `push`, and `daemon`) can write each
+//! warning to the local syslog socket or the systemd journal when it is
+//! raised and again when it clears. Warnings are matched the way the event
+//! timeline matches them, on source and wording with readings masked, so a
+//! warning that persists is logged once however often collectors restate
+//! it; a change in severity logs it again. Warnings already active when the
+//! mode starts are logged as raised.
+//!
+//! Syslog lines use the daemon facility and carry the fields as logfmt
+//! (`state=raised severity=warning source="Disk Health" message="..."`);
+//! journal entries carry them as `SD300_WARNING_*` fields.
+
+use std::collections::BTreeMap;
+
+use crate::cli::{WarningLog, WarningLogArgs};
+use crate::collectors::{DiagnosticWarning, WarningSeverity};
+use crate::error::Result;
+use crate::events::warning_identity;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Raised,
+    Cleared,
+}
+
+impl Transition {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Raised => "raised",
+            Self::Cleared => "cleared",
+        }
+    }
+}
+
+/// Active warnings, for telling new and cleared ones from repeats.
+#[derive(Debug, Default)]
+pub struct WarningTracker {
+    active: BTreeMap<(String, String), DiagnosticWarning>,
+}
+
+impl WarningTracker {
+    pub fn observe(
+        &mut self,
+        warnings: &[DiagnosticWarning],
+    ) -> Vec<(Transition, DiagnosticWarning)> {
+        let current: BTreeMap<_, _> = warnings
+            .iter()
+            .map(|warning| {
+                (
+                    (warning.source.clone(), warning_identity(&warning.message)),
+                    warning.clone(),
+                )
+            })
+            .collect();
+        let previous = std::mem::replace(&mut self.active, current);
+        let mut changes = Vec::new();
+        for (key, warning) in &self.active {
+            if previous
+                .get(key)
+                .is_none_or(|before| before.severity != warning.severity)
+            {
+                changes.push((Transition::Raised, warning.clone()));
+            }
+        }
+        for (key, warning) in previous {
+            if !self.active.contains_key(&key) {
+                changes.push((Transition::Cleared, warning));
+            }
+        }
+        changes
+    }
+}
+
+fn severity_label(severity: &WarningSeverity) -> &'static str {
+    match severity {
+        WarningSeverity::Info => "info",
+        WarningSeverity::Warning => "warning",
+        WarningSeverity::Error => "error",
+    }
+}
+
+/// Syslog priority: `err`, `warning`, or `info` for raised warnings, and
+/// `info` once cleared.
+fn priority(transition: Transition, severity: &WarningSeverity) -> u8 {
+    match (transition, severity) {
+        (Transition::Cleared, _) | (_, WarningSeverity::Info) => 6,
+        (_, WarningSeverity::Warning) => 4,
+        (_, WarningSeverity::Error) => 3,
+    }
+}
+
+const FACILITY_DAEMON: u8 = 3;
+const IDENTIFIER: &str = "sd300";
+
+/// One datagram for the local syslog socket. The timestamp and host are
+/// left for the receiving daemon to add, as it does for local messages.
+fn syslog_datagram(transition: Transition, warning: &DiagnosticWarning, pid: u32) -> Vec<u8> {
+    format!(
+        "<{}>{IDENTIFIER}[{pid}]: state={} severity={} source={} message={}",
+        FACILITY_DAEMON * 8 + priority(transition, &warning.severity),
+        transition.label(),
+        severity_label(&warning.severity),
+        logfmt_quote(&warning.source),
+        logfmt_quote(&warning.message),
+    )
+    .into_bytes()
+}
+
+fn logfmt_quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' | '\r' => quoted.push(' '),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// One datagram in the journal's native protocol.
+fn journald_datagram(transition: Transition, warning: &DiagnosticWarning, pid: u32) -> Vec<u8> {
+    let message = match transition {
+        Transition::Raised => format!("{}: {}", warning.source, warning.message),
+        Transition::Cleared => format!("{}: Cleared: {}", warning.source, warning.message),
+    };
+    let mut datagram = Vec::new();
+    for (field, value) in [
+        ("MESSAGE", message.as_str()),
+        (
+            "PRIORITY",
+            &priority(transition, &warning.severity).to_string(),
+        ),
+        ("SYSLOG_FACILITY", &FACILITY_DAEMON.to_string()),
+        ("SYSLOG_IDENTIFIER", IDENTIFIER),
+        ("SYSLOG_PID", &pid.to_string()),
+        ("SD300_WARNING_STATE", transition.label()),
+        ("SD300_WARNING_SEVERITY", severity_label(&warning.severity)),
+        ("SD300_WARNING_SOURCE", &warning.source),
+    ] {
+        datagram.extend_from_slice(field.as_bytes());
+        if value.contains('\n') {
+            // Multi-line values are sent length-prefixed.
+            datagram.push(b'\n');
+            datagram.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        } else {
+            datagram.push(b'=');
+        }
+        datagram.extend_from_slice(value.as_bytes());
+        datagram.push(b'\n');
+    }
+    datagram
+}
+
+/// Forwards warning transitions to the configured log.
+pub struct WarningForwarder {
+    tracker: WarningTracker,
+    log: WarningLog,
+    #[cfg(unix)]
+    socket: std::os::unix::net::UnixDatagram,
+    /// Set while sends fail, so an outage is reported once.
+    #[cfg(unix)]
+    failing: bool,
+}
+
+#[cfg(unix)]
+const SYSLOG_SOCKETS: [&str; 3] = ["/dev/log", "/var/run/syslog", "/var/run/log"];
+#[cfg(unix)]
+const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+
+impl WarningForwarder {
+    /// The forwarder `args` ask for, if any, connected to its log.
+    pub fn from_args(args: &WarningLogArgs) -> Result<Option<Self>> {
+        let Some(log) = args.log_warnings else {
+            return Ok(None);
+        };
+        Self::connect(log, None).map(Some)
+    }
+
+    #[cfg(unix)]
+    fn connect(log: WarningLog, path: Option<&std::path::Path>) -> Result<Self> {
+        use crate::error::AppError;
+        use std::os::unix::net::UnixDatagram;
+
+        let candidates: Vec<&std::path::Path> = match (path, log) {
+            (Some(path), _) => vec![path],
+            (None, WarningLog::Syslog) => SYSLOG_SOCKETS.iter().map(std::path::Path::new).collect(),
+            (None, WarningLog::Journald) => vec![std::path::Path::new(JOURNAL_SOCKET)],
+        };
+        let socket = UnixDatagram::unbound()?;
+        let connected = candidates
+            .iter()
+            .any(|candidate| socket.connect(candidate).is_ok());
+        if !connected {
+            return Err(AppError::platform(format!(
+                "cannot reach the {} socket ({})",
+                match log {
+                    WarningLog::Syslog => "syslog",
+                    WarningLog::Journald => "journal",
+                },
+                candidates
+                    .iter()
+                    .map(|candidate| candidate.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        // A stalled log daemon must not stall collection; a full socket
+        // buffer drops the message instead.
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            tracker: WarningTracker::default(),
+            log,
+            socket,
+            failing: false,
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn connect(_log: WarningLog, _path: Option<&std::path::Path>) -> Result<Self> {
+        Err(crate::error::AppError::platform(
+            "--log-warnings is available on Linux and macOS",
+        ))
+    }
+
+    /// Log the warnings raised or cleared since the previous call.
+    pub fn observe(&mut self, warnings: &[DiagnosticWarning]) {
+        let pid = std::process::id();
+        for (transition, warning) in self.tracker.observe(warnings) {
+            let datagram = match self.log {
+                WarningLog::Syslog => syslog_datagram(transition, &warning, pid),
+                WarningLog::Journald => journald_datagram(transition, &warning, pid),
+            };
+            self.send(&datagram);
+        }
+    }
+
+    #[cfg(unix)]
+    fn send(&mut self, datagram: &[u8]) {
+        match self.socket.send(datagram) {
+            Ok(_) => self.failing = false,
+            Err(error) if !self.failing => {
+                eprintln!("Could not log a warning: {error}");
+                self.failing = true;
+            }
+            Err(_) => {}
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn send(&mut self, _datagram: &[u8]) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warning(source: &str, message: &str, severity: WarningSeverity) -> DiagnosticWarning {
+        DiagnosticWarning {
+            source: source.into(),
+            message: message.into(),
+            severity,
+        }
+    }
+
+    #[test]
+    fn persistent_warnings_are_reported_once_until_they_change_or_clear() {
+        let mut tracker = WarningTracker::default();
+        let hot =
+            |celsius: &str, severity| warning("Thermals", &format!("CPU at {celsius} C"), severity);
+
+        let changes = tracker.observe(&[hot("91", WarningSeverity::Warning)]);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0, Transition::Raised);
+
+        assert!(tracker
+            .observe(&[hot("93", WarningSeverity::Warning)])
+            .is_empty());
+
+        let changes = tracker.observe(&[hot("99", WarningSeverity::Error)]);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].1.severity, WarningSeverity::Error);
+
+        let changes = tracker.observe(&[]);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0, Transition::Cleared);
+        assert_eq!(changes[0].1.message, "CPU at 99 C");
+    }
+
+    #[test]
+    fn partitions_over_threshold_are_tracked_apart() {
+        let mut tracker = WarningTracker::default();
+        let full = |mount: &str, percent: &str| {
+            warning(
+                "Thresholds",
+                &format!("Partition {mount} is at {percent}% (warning from 80%)"),
+                WarningSeverity::Warning,
+            )
+        };
+
+        let changes = tracker.observe(&[full("/mnt/disk1", "92"), full("/mnt/disk2", "85")]);
+        assert_eq!(changes.len(), 2);
+        assert!(changes
+            .iter()
+            .all(|(transition, _)| *transition == Transition::Raised));
+
+        assert!(tracker
+            .observe(&[full("/mnt/disk1", "93"), full("/mnt/disk2", "86")])
+            .is_empty());
+
+        let changes = tracker.observe(&[full("/mnt/disk1", "93")]);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0, Transition::Cleared);
+        assert!(changes[0].1.message.starts_with("Partition /mnt/disk2 "));
+    }
+
+    #[test]
+    fn syslog_lines_carry_priority_and_logfmt_fields() {
+        let raised = warning(
+            "Disk Health",
+            "Drive \"nvme0\" failing",
+            WarningSeverity::Error,
+        );
+        assert_eq!(
+            String::from_utf8(syslog_datagram(Transition::Raised, &raised, 42)).unwrap(),
+            "<27>sd300[42]: state=raised severity=error source=\"Disk Health\" message=\"Drive \\\"nvme0\\\" failing\""
+        );
+        let cleared = syslog_datagram(Transition::Cleared, &raised, 42);
+        assert!(cleared.starts_with(b"<30>sd300[42]: state=cleared severity=error "));
+    }
+
+    #[test]
+    fn journal_entries_carry_structured_fields() {
+        let raised = warning("Network", "Gateway\nunreachable", WarningSeverity::Warning);
+        let datagram = journald_datagram(Transition::Raised, &raised, 7);
+        let text = String::from_utf8_lossy(&datagram);
+        assert!(text.contains("PRIORITY=4\n"));
+        assert!(text.contains("SYSLOG_IDENTIFIER=sd300\n"));
+        assert!(text.contains("SD300_WARNING_SOURCE=Network\n"));
+        assert!(text.contains("SD300_WARNING_SEVERITY=warning\n"));
+        assert!(text.contains("SD300_WARNING_STATE=raised\n"));
+        let message = "Network: Gateway\nunreachable";
+        let mut framed = b"MESSAGE\n".to_vec();
+        framed.extend_from_slice(&(message.len() as u64).to_le_bytes());
+        framed.extend_from_slice(message.as_bytes());
+        assert!(datagram.starts_with(&framed));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn forwards_each_transition_once_to_the_log_socket() {
+        use std::os::unix::net::UnixDatagram;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log");
+        let receiver = UnixDatagram::bind(&path).unwrap();
+        receiver
+            .set_read_timeout(Some(std::time::Duration::from_secs(5)))
+            .unwrap();
+        let mut forwarder = WarningForwarder::connect(WarningLog::Syslog, Some(&path)).unwrap();
+
+        let active = [warning(
+            "Drivers",
+            "2 devices need attention",
+            WarningSeverity::Warning,
+        )];
+        forwarder.observe(&active);
+        forwarder.observe(&active);
+        forwarder.observe(&[]);
+
+        let mut buffer = [0u8; 1024];
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            let read = receiver.recv(&mut buffer).unwrap();
+            received.push(String::from_utf8_lossy(&buffer[..read]).into_owned());
+        }
+        assert!(received[0].contains("state=raised"));
+        assert!(received[1].contains("state=cleared"));
+        receiver.set_nonblocking(true).unwrap();
+        assert!(receiver.recv(&mut buffer).is_err());
+    }
+}
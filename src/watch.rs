@@ -0,0 +1,149 @@
+//! `sd300 watch --json`: live report sections as NDJSON on stdout.
+//!
+//! Every tick writes one line. Like the desktop engine's topics, each section
+//! keeps its own sequence number, which advances only when the section's
+//! redacted value changes, and a line carries only the sections that changed
+//! since the previous line. The first line carries every watched section, so
+//! a reader that folds lines into a map always holds the full current state.
+//! A closed pipe (`sd300 watch --json | head`) ends the stream quietly.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::cli::WatchArgs;
+use crate::error::{AppError, Result};
+use crate::live::LiveCollector;
+use crate::record::{report_sections, selected_sections, unix_ms};
+use crate::report::{DiagnosticReport, SCHEMA_VERSION};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WatchLine {
+    pub sequence: u64,
+    pub captured_unix_ms: u64,
+    pub schema_version: u32,
+    /// Changed sections by report key, each as `{ "sequence", "data" }`.
+    pub sections: Map<String, Value>,
+}
+
+/// Turns successive section values into change-only lines.
+#[derive(Debug, Default)]
+pub struct WatchStream {
+    lines: u64,
+    previous: Map<String, Value>,
+    sequences: BTreeMap<String, u64>,
+}
+
+impl WatchStream {
+    pub fn next_line(&mut self, captured_unix_ms: u64, sections: Map<String, Value>) -> WatchLine {
+        self.lines += 1;
+        let mut changed = Map::new();
+        for (key, value) in &sections {
+            if self.previous.get(key) == Some(value) {
+                continue;
+            }
+            let sequence = self.sequences.entry(key.clone()).or_default();
+            *sequence += 1;
+            changed.insert(
+                key.clone(),
+                serde_json::json!({ "sequence": *sequence, "data": value }),
+            );
+        }
+        self.previous = sections;
+        WatchLine {
+            sequence: self.lines,
+            captured_unix_ms,
+            schema_version: SCHEMA_VERSION,
+            sections: changed,
+        }
+    }
+}
+
+pub async fn run(args: &WatchArgs) -> Result<()> {
+    let sections = selected_sections(&args.sections);
+    let mut live = LiveCollector::start();
+    let mut stream = WatchStream::default();
+
+    let mut line_tick = tokio::time::interval(Duration::from_secs(args.interval));
+    line_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let interrupt = tokio::signal::ctrl_c();
+    tokio::pin!(interrupt);
+
+    loop {
+        tokio::select! {
+            _ = live.refresh() => {}
+            _ = line_tick.tick() => {
+                let report = DiagnosticReport::from_snapshot(&live.snapshot, args.include_sensitive);
+                let line = stream.next_line(unix_ms(), report_sections(&report, &sections)?);
+                match write_line(&line) {
+                    Err(AppError::Io(error)) if error.kind() == std::io::ErrorKind::BrokenPipe => {
+                        return Ok(());
+                    }
+                    result => result?,
+                }
+            }
+            _ = &mut interrupt => return Ok(()),
+        }
+    }
+}
+
+fn write_line(line: &WatchLine) -> Result<()> {
+    let mut bytes = serde_json::to_vec(line)
+        .map_err(|error| AppError::platform(format!("JSON serialization failed: {error}")))?;
+    bytes.push(b'\n');
+    let mut stdout = std::io::stdout().lock();
+    stdout.write_all(&bytes)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sections(cpu: f64, drivers: &str) -> Map<String, Value> {
+        let mut sections = Map::new();
+        sections.insert("cpu".into(), json!({ "total_usage": cpu }));
+        sections.insert("drivers".into(), json!({ "scan_status": drivers }));
+        sections
+    }
+
+    #[test]
+    fn lines_carry_only_changed_sections_with_their_own_sequences() {
+        let mut stream = WatchStream::default();
+
+        let first = stream.next_line(1_000, sections(10.0, "scanning"));
+        assert_eq!(first.sequence, 1);
+        assert_eq!(first.schema_version, SCHEMA_VERSION);
+        assert_eq!(first.sections["cpu"]["sequence"], 1);
+        assert_eq!(first.sections["drivers"]["data"]["scan_status"], "scanning");
+
+        let second = stream.next_line(2_000, sections(20.0, "scanning"));
+        assert_eq!(second.sequence, 2);
+        assert_eq!(second.captured_unix_ms, 2_000);
+        assert_eq!(second.sections.len(), 1);
+        assert_eq!(second.sections["cpu"]["sequence"], 2);
+
+        let quiet = stream.next_line(3_000, sections(20.0, "scanning"));
+        assert!(quiet.sections.is_empty());
+
+        let fourth = stream.next_line(4_000, sections(20.0, "success"));
+        assert_eq!(fourth.sequence, 4);
+        assert_eq!(fourth.sections["drivers"]["sequence"], 2);
+        assert!(!fourth.sections.contains_key("cpu"));
+    }
+
+    #[test]
+    fn lines_serialize_to_one_json_object() {
+        let mut stream = WatchStream::default();
+        let line = serde_json::to_string(&stream.next_line(5, sections(1.0, "success"))).unwrap();
+        assert!(!line.contains('\n'));
+        let value: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["sequence"], 1);
+        assert_eq!(value["sections"]["cpu"]["data"]["total_usage"], 1.0);
+    }
+}
@@ -0,0 +1,784 @@
+//! Webhook notifications on health transitions.
+//!
+//! Each entry in the `shared.webhooks` list of settings.json is an endpoint
+//! the background modes (`serve`, `push`, and `daemon`) notify when the
+//! overall health changes or a warning is raised or cleared. Warnings are
+//! matched as `--log-warnings` matches them, so a warning that persists is
+//! sent once.
+//!
+//! The body is a JSON template whose strings may contain `{{placeholders}}`;
+//! without one a built-in payload with a Slack- and Teams-compatible `text`
+//! field is sent. The computer name stays out of notifications unless the
+//! webhook sets `include_sensitive`, so give each machine a `label`.
+//!
+//! A webhook sends at most one notification per `min_interval_seconds`.
+//! While it waits, health changes collapse into the latest state and warning
+//! changes are counted; the next notification reports the count as
+//! `{{suppressed}}`. Delivery runs off the collection loop and retries
+//! failed requests (no connection, HTTP 408, 429, or 5xx) with exponential
+//! backoff up to `max_attempts`; any other 4xx is not retried. Plain http is
+//! posted directly; https goes through curl, or PowerShell on Windows, the
+//! way update downloads do, so a webhook URL never appears on a command line.
+
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+use crate::collectors::{DiagnosticWarning, SystemSnapshot, WarningSeverity};
+use crate::push::{post, HttpEndpoint};
+use crate::record::unix_ms;
+use crate::replay::format_utc;
+use crate::settings::{self, WebhookSettings};
+use crate::types::HealthStatus;
+use crate::warning_log::{Transition, WarningTracker};
+
+/// Names a template may use, each written as `{{name}}`.
+pub const PLACEHOLDERS: [&str; 10] = [
+    "event",
+    "state",
+    "previous",
+    "severity",
+    "source",
+    "message",
+    "text",
+    "machine",
+    "time",
+    "suppressed",
+];
+
+/// Wait before the first retry; each further retry waits twice as long.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(2);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// Time allowed for one https request, including the TLS handshake.
+const HTTPS_TIMEOUT_SECS: u64 = 10;
+
+const REDACTED: &str = "[redacted]";
+
+/// A change worth notifying about.
+#[derive(Debug, Clone)]
+pub enum Event {
+    Health {
+        previous: HealthStatus,
+        current: HealthStatus,
+    },
+    Warning {
+        transition: Transition,
+        warning: DiagnosticWarning,
+    },
+}
+
+fn health_label(status: HealthStatus) -> &'static str {
+    match status {
+        HealthStatus::Good => "good",
+        HealthStatus::Warning => "warning",
+        HealthStatus::Critical => "critical",
+        HealthStatus::Unknown => "unknown",
+    }
+}
+
+fn severity_label(severity: &WarningSeverity) -> &'static str {
+    match severity {
+        WarningSeverity::Info => "info",
+        WarningSeverity::Warning => "warning",
+        WarningSeverity::Error => "error",
+    }
+}
+
+fn severity_rank(severity: &WarningSeverity) -> u8 {
+    match severity {
+        WarningSeverity::Info => 0,
+        WarningSeverity::Warning => 1,
+        WarningSeverity::Error => 2,
+    }
+}
+
+/// Everything a template can refer to for one notification.
+struct Fields<'a> {
+    event: &'a Event,
+    machine: &'a str,
+    hostname: &'a str,
+    include_sensitive: bool,
+    captured_unix_ms: u64,
+    suppressed: u64,
+}
+
+impl Fields<'_> {
+    fn value(&self, name: &str) -> Option<String> {
+        let value = match (name, self.event) {
+            ("event", Event::Health { .. }) => "health".into(),
+            ("event", Event::Warning { .. }) => "warning".into(),
+            ("state", Event::Health { current, .. }) => health_label(*current).into(),
+            ("state", Event::Warning { transition, .. }) => transition.label().into(),
+            ("previous", Event::Health { previous, .. }) => health_label(*previous).into(),
+            ("previous", Event::Warning { transition, .. }) => match transition {
+                Transition::Raised => "cleared".into(),
+                Transition::Cleared => "raised".into(),
+            },
+            ("severity", Event::Health { current, .. }) => match current {
+                HealthStatus::Critical => "error".into(),
+                HealthStatus::Warning => "warning".into(),
+                HealthStatus::Good | HealthStatus::Unknown => "info".into(),
+            },
+            ("severity", Event::Warning { warning, .. }) => {
+                severity_label(&warning.severity).into()
+            }
+            ("source", Event::Health { .. }) => "Overall health".into(),
+            ("source", Event::Warning { warning, .. }) => warning.source.clone(),
+            ("message", _) => self.message(),
+            ("text", _) => self.text(),
+            ("machine", _) => self.machine.into(),
+            ("time", _) => format!("{} UTC", format_utc(self.captured_unix_ms)),
+            ("suppressed", _) => self.suppressed.to_string(),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn message(&self) -> String {
+        match self.event {
+            Event::Health { previous, current } => format!(
+                "Overall health is {} (was {})",
+                health_label(*current),
+                health_label(*previous)
+            ),
+            Event::Warning { warning, .. } => {
+                if self.include_sensitive || self.hostname.is_empty() {
+                    warning.message.clone()
+                } else {
+                    warning.message.replace(self.hostname, REDACTED)
+                }
+            }
+        }
+    }
+
+    /// One line for chat clients, which show only `text`.
+    fn text(&self) -> String {
+        let mut text = match self.event {
+            Event::Health { .. } => format!("{}: {}", self.machine, self.message()),
+            Event::Warning {
+                transition: Transition::Raised,
+                warning,
+            } => format!("{}: {}: {}", self.machine, warning.source, self.message()),
+            Event::Warning {
+                transition: Transition::Cleared,
+                warning,
+            } => format!(
+                "{}: Cleared: {}: {}",
+                self.machine,
+                warning.source,
+                self.message()
+            ),
+        };
+        if self.suppressed > 0 {
+            text.push_str(&format!(
+                " ({} more change{} held back by the rate limit)",
+                self.suppressed,
+                if self.suppressed == 1 { "" } else { "s" }
+            ));
+        }
+        text
+    }
+}
+
+fn default_template() -> Value {
+    json!({
+        "text": "{{text}}",
+        "event": "{{event}}",
+        "state": "{{state}}",
+        "previous": "{{previous}}",
+        "severity": "{{severity}}",
+        "source": "{{source}}",
+        "message": "{{message}}",
+        "machine": "{{machine}}",
+        "time": "{{time}}",
+        "suppressed": "{{suppressed}}",
+    })
+}
+
+/// The placeholders in `text`, in order, without their braces.
+fn placeholders(text: &str) -> impl Iterator<Item = (usize, usize, &str)> {
+    let mut from = 0;
+    std::iter::from_fn(move || {
+        let start = from + text[from..].find("{{")?;
+        let end = start + 2 + text[start + 2..].find("}}")?;
+        from = end + 2;
+        Some((start, end + 2, text[start + 2..end].trim()))
+    })
+}
+
+/// The first placeholder in `template` that is not one of [`PLACEHOLDERS`].
+pub fn unknown_placeholder(template: &Value) -> Option<String> {
+    match template {
+        Value::String(text) => placeholders(text)
+            .map(|(_, _, name)| name)
+            .find(|name| !PLACEHOLDERS.contains(name))
+            .map(str::to_string),
+        Value::Array(items) => items.iter().find_map(unknown_placeholder),
+        Value::Object(map) => map.values().find_map(unknown_placeholder),
+        _ => None,
+    }
+}
+
+/// `template` with its placeholders filled in. Strings stay strings, so
+/// values are escaped by serialization, except that a string that is only
+/// `{{suppressed}}` becomes a number.
+fn fill(template: &Value, fields: &Fields) -> Value {
+    match template {
+        Value::String(text) if text == "{{suppressed}}" => Value::from(fields.suppressed),
+        Value::String(text) => {
+            let mut filled = String::with_capacity(text.len());
+            let mut copied = 0;
+            for (start, end, name) in placeholders(text) {
+                if let Some(value) = fields.value(name) {
+                    filled.push_str(&text[copied..start]);
+                    filled.push_str(&value);
+                    copied = end;
+                }
+            }
+            filled.push_str(&text[copied..]);
+            Value::String(filled)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|item| fill(item, fields)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), fill(value, fields)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Where a webhook's requests go.
+#[derive(Debug, Clone)]
+enum Target {
+    Http(HttpEndpoint),
+    Https(String),
+}
+
+impl Target {
+    fn parse(url: &str) -> std::result::Result<Self, String> {
+        if url.starts_with("https://") {
+            Ok(Self::Https(url.to_string()))
+        } else {
+            HttpEndpoint::parse(url)
+                .map(Self::Http)
+                .map_err(|error| error.to_string())
+        }
+    }
+}
+
+/// Rate-limit state for one webhook.
+struct Hook {
+    settings: WebhookSettings,
+    /// The health last sent, which the next health notification compares to.
+    notified_health: HealthStatus,
+    quiet_until: Option<Instant>,
+    suppressed: u64,
+}
+
+impl Hook {
+    fn new(settings: WebhookSettings) -> Self {
+        Self {
+            settings,
+            notified_health: HealthStatus::Good,
+            quiet_until: None,
+            suppressed: 0,
+        }
+    }
+
+    /// The notifications to send now for this observation.
+    fn plan(
+        &mut self,
+        health: HealthStatus,
+        changes: &[(Transition, DiagnosticWarning)],
+        hostname: &str,
+        now: Instant,
+        captured_unix_ms: u64,
+    ) -> Vec<Value> {
+        let min_rank = severity_rank(&self.settings.min_severity);
+        let mut events: Vec<Event> = changes
+            .iter()
+            .filter(|(_, warning)| severity_rank(&warning.severity) >= min_rank)
+            .map(|(transition, warning)| Event::Warning {
+                transition: *transition,
+                warning: warning.clone(),
+            })
+            .collect();
+        if self.quiet_until.is_some_and(|until| now < until) {
+            self.suppressed += events.len() as u64;
+            return Vec::new();
+        }
+        if health != self.notified_health {
+            events.insert(
+                0,
+                Event::Health {
+                    previous: self.notified_health,
+                    current: health,
+                },
+            );
+            self.notified_health = health;
+        }
+        if events.is_empty() {
+            return Vec::new();
+        }
+
+        let interval = Duration::from_secs(self.settings.min_interval_seconds);
+        let sending = if interval.is_zero() { events.len() } else { 1 };
+        self.suppressed += (events.len() - sending) as u64;
+        self.quiet_until = Some(now + interval);
+
+        let machine = match (&self.settings.label, self.settings.include_sensitive) {
+            (Some(label), _) => label.as_str(),
+            (None, true) if !hostname.is_empty() => hostname,
+            _ => REDACTED,
+        };
+        let template = self
+            .settings
+            .template
+            .clone()
+            .unwrap_or_else(default_template);
+        events
+            .iter()
+            .take(sending)
+            .map(|event| {
+                fill(
+                    &template,
+                    &Fields {
+                        event,
+                        machine,
+                        hostname,
+                        include_sensitive: self.settings.include_sensitive,
+                        captured_unix_ms,
+                        suppressed: std::mem::take(&mut self.suppressed),
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// Watches snapshots and hands each webhook's notifications to its delivery
+/// task.
+pub struct Notifier {
+    tracker: WarningTracker,
+    hooks: Vec<(Hook, mpsc::UnboundedSender<Value>)>,
+}
+
+impl Notifier {
+    /// The configured webhooks, each with its delivery task started, or
+    /// `None` when there are none. Must be called from within a Tokio runtime.
+    pub fn from_settings() -> Option<Self> {
+        Self::start(settings::load_shared().webhooks)
+    }
+
+    fn start(webhooks: Vec<WebhookSettings>) -> Option<Self> {
+        let mut hooks = Vec::new();
+        for (index, settings) in webhooks.into_iter().enumerate() {
+            let name = display_name(index, &settings.url);
+            let target = match Target::parse(&settings.url) {
+                Ok(target) => target,
+                Err(error) => {
+                    eprintln!("Skipping {name}: {error}");
+                    continue;
+                }
+            };
+            let (sender, receiver) = mpsc::unbounded_channel();
+            tokio::spawn(delivery_loop(
+                target,
+                settings.max_attempts,
+                INITIAL_BACKOFF,
+                receiver,
+                name,
+            ));
+            hooks.push((Hook::new(settings), sender));
+        }
+        if hooks.is_empty() {
+            return None;
+        }
+        eprintln!(
+            "Notifying {} webhook{} of health changes.",
+            hooks.len(),
+            if hooks.len() == 1 { "" } else { "s" }
+        );
+        Some(Self {
+            tracker: WarningTracker::default(),
+            hooks,
+        })
+    }
+
+    /// Queue notifications for what changed since the previous call.
+    pub fn observe(&mut self, snapshot: &SystemSnapshot) {
+        let changes = self.tracker.observe(&snapshot.warnings);
+        let health = snapshot.overall_health();
+        let now = Instant::now();
+        let captured = unix_ms();
+        for (hook, sender) in &mut self.hooks {
+            for body in hook.plan(health, &changes, &snapshot.system.hostname, now, captured) {
+                let _ = sender.send(body);
+            }
+        }
+    }
+}
+
+/// How a webhook is named in messages: its host only, because the rest of
+/// a webhook URL is usually its secret.
+fn display_name(index: usize, url: &str) -> String {
+    let host = url
+        .split_once("://")
+        .map_or(url, |(_, rest)| rest)
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or_default();
+    format!("webhook {} ({host})", index + 1)
+}
+
+async fn delivery_loop(
+    target: Target,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    mut bodies: mpsc::UnboundedReceiver<Value>,
+    name: String,
+) {
+    while let Some(body) = bodies.recv().await {
+        if let Err(reason) = deliver(&target, &body, max_attempts, initial_backoff).await {
+            eprintln!("Could not notify {name}: {reason}");
+        }
+    }
+}
+
+/// Send `body`, retrying transient failures with exponential backoff.
+async fn deliver(
+    target: &Target,
+    body: &Value,
+    max_attempts: u32,
+    initial_backoff: Duration,
+) -> std::result::Result<(), String> {
+    let body = body.to_string();
+    let mut backoff = initial_backoff;
+    let mut attempt = 1;
+    loop {
+        let failure = match send(target, &body).await {
+            Ok(200..=299) => return Ok(()),
+            Ok(status @ (408 | 429 | 500..)) => format!("HTTP {status}"),
+            Ok(status) => return Err(format!("HTTP {status}; the notification was dropped")),
+            Err(reason) => reason,
+        };
+        if attempt >= max_attempts {
+            return Err(format!("{failure} after {attempt} attempts"));
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+        attempt += 1;
+    }
+}
+
+async fn send(target: &Target, body: &str) -> std::result::Result<u16, String> {
+    match target {
+        Target::Http(endpoint) => post(endpoint, "application/json", None, body).await,
+        Target::Https(url) => {
+            let (url, body) = (url.clone(), body.to_string());
+            tokio::task::spawn_blocking(move || post_https(&url, &body))
+                .await
+                .map_err(|error| error.to_string())?
+        }
+    }
+}
+
+/// POST through curl, with the URL and body passed in a config on stdin.
+#[cfg(unix)]
+fn post_https(url: &str, body: &str) -> std::result::Result<u16, String> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let quote = |value: &str| value.replace('\\', "\\\\").replace('"', "\\\"");
+    let config = format!(
+        "url = \"{}\"\nheader = \"Content-Type: application/json\"\ndata-binary = \"{}\"\n",
+        quote(url),
+        quote(body)
+    );
+    let mut child = Command::new("curl")
+        .args([
+            "--proto",
+            "=https",
+            "--tlsv1.2",
+            "-sS",
+            "--max-time",
+            &HTTPS_TIMEOUT_SECS.to_string(),
+            "-o",
+            "/dev/null",
+            "-w",
+            "%{http_code}",
+            "-A",
+            concat!("sd300/", env!("CARGO_PKG_VERSION")),
+            "-K",
+            "-",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("curl is required for https webhooks: {error}"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(config.as_bytes())
+            .map_err(|error| error.to_string())?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|error| error.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.trim().to_string());
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|_| "curl did not report an HTTP status".into())
+}
+
+/// POST through PowerShell, with the URL and body passed in its environment.
+#[cfg(windows)]
+fn post_https(url: &str, body: &str) -> std::result::Result<u16, String> {
+    use std::process::Command;
+
+    let program = crate::update::windows_powershell_program()
+        .map_err(|_| "PowerShell is required for https webhooks".to_string())?;
+    let script = format!(
+        "$ProgressPreference='SilentlyContinue'; try {{ $r = Invoke-WebRequest -UseBasicParsing -Method Post -TimeoutSec {HTTPS_TIMEOUT_SECS} -ContentType 'application/json; charset=utf-8' -Uri $env:SD300_WEBHOOK_URL -Body ([Text.Encoding]::UTF8.GetBytes($env:SD300_WEBHOOK_BODY)); [int]$r.StatusCode }} catch {{ if ($_.Exception.Response) {{ [int]$_.Exception.Response.StatusCode }} else {{ [Console]::Error.WriteLine($_.Exception.Message); exit 1 }} }}"
+    );
+    let output = Command::new(program)
+        .env_remove("PSModulePath")
+        .env("SD300_WEBHOOK_URL", url)
+        .env("SD300_WEBHOOK_BODY", body)
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-ExecutionPolicy",
+            "Bypass",
+            "-Command",
+            &script,
+        ])
+        .output()
+        .map_err(|error| error.to_string())?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(stderr.trim().to_string());
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .map_err(|_| "PowerShell did not report an HTTP status".into())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn post_https(_url: &str, _body: &str) -> std::result::Result<u16, String> {
+    Err("https webhooks are not supported on this platform".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn warning(source: &str, message: &str, severity: WarningSeverity) -> DiagnosticWarning {
+        DiagnosticWarning {
+            source: source.into(),
+            message: message.into(),
+            severity,
+        }
+    }
+
+    fn hook(min_interval_seconds: u64) -> Hook {
+        Hook::new(WebhookSettings {
+            url: "http://127.0.0.1:9/hook".into(),
+            min_interval_seconds,
+            ..WebhookSettings::default()
+        })
+    }
+
+    #[test]
+    fn templates_fill_placeholders_and_reject_unknown_ones() {
+        let template = json!({
+            "blocks": [{ "text": "*{{ state }}* on {{machine}} at {{time}}" }],
+            "count": "{{suppressed}}",
+            "literal": "{{not closed",
+            "fixed": 3,
+        });
+        assert_eq!(unknown_placeholder(&template), None);
+        assert_eq!(
+            unknown_placeholder(&json!({ "a": ["{{text}}", "{{hostname}}"] })).as_deref(),
+            Some("hostname")
+        );
+
+        let event = Event::Health {
+            previous: HealthStatus::Good,
+            current: HealthStatus::Critical,
+        };
+        let filled = fill(
+            &template,
+            &Fields {
+                event: &event,
+                machine: "lab-\"07\"",
+                hostname: "lab07.local",
+                include_sensitive: false,
+                captured_unix_ms: 86_400_000,
+                suppressed: 2,
+            },
+        );
+        assert_eq!(
+            filled,
+            json!({
+                "blocks": [{ "text": "*critical* on lab-\"07\" at 1970-01-02 00:00:00 UTC" }],
+                "count": 2,
+                "literal": "{{not closed",
+                "fixed": 3,
+            })
+        );
+    }
+
+    #[test]
+    fn notifications_are_redacted_unless_the_webhook_opts_in() {
+        let changes = [(
+            Transition::Raised,
+            warning(
+                "Network",
+                "lab07.local lost its lease",
+                WarningSeverity::Error,
+            ),
+        )];
+        let now = Instant::now();
+
+        let mut redacted = hook(0);
+        let bodies = redacted.plan(HealthStatus::Good, &changes, "lab07.local", now, 0);
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(bodies[0]["machine"], REDACTED);
+        assert_eq!(bodies[0]["message"], "[redacted] lost its lease");
+        assert!(!bodies[0].to_string().contains("lab07"));
+
+        let mut labelled = hook(0);
+        labelled.settings.label = Some("bench 3".into());
+        let bodies = labelled.plan(HealthStatus::Good, &changes, "lab07.local", now, 0);
+        assert_eq!(
+            bodies[0]["text"],
+            "bench 3: Network: [redacted] lost its lease"
+        );
+
+        let mut sensitive = hook(0);
+        sensitive.settings.include_sensitive = true;
+        let bodies = sensitive.plan(HealthStatus::Good, &changes, "lab07.local", now, 0);
+        assert_eq!(bodies[0]["machine"], "lab07.local");
+        assert_eq!(bodies[0]["message"], "lab07.local lost its lease");
+    }
+
+    #[test]
+    fn the_rate_limit_collapses_health_and_counts_held_back_warnings() {
+        let start = Instant::now();
+        let mut hook = hook(60);
+        let raised = [
+            (
+                Transition::Raised,
+                warning("Disk", "Drive C: is 97% full", WarningSeverity::Warning),
+            ),
+            (
+                Transition::Raised,
+                warning("Drivers", "Scan needs elevation", WarningSeverity::Info),
+            ),
+        ];
+
+        // Health first; the warning waits for the next window.
+        let bodies = hook.plan(HealthStatus::Critical, &raised, "", start, 0);
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(bodies[0]["event"], "health");
+        assert_eq!(bodies[0]["state"], "critical");
+        assert_eq!(bodies[0]["previous"], "good");
+        assert_eq!(bodies[0]["suppressed"], 1);
+
+        // Inside the window nothing is sent; flapping health collapses.
+        let cleared = [(raised[0].0, raised[0].1.clone())];
+        let later = start + Duration::from_secs(30);
+        assert!(hook
+            .plan(HealthStatus::Good, &cleared, "", later, 0)
+            .is_empty());
+        assert!(hook
+            .plan(HealthStatus::Critical, &[], "", later, 0)
+            .is_empty());
+
+        // Health ended where it was sent, so the next change goes out.
+        let after = start + Duration::from_secs(61);
+        let cleared = [(
+            Transition::Cleared,
+            warning("Disk", "Drive C: is 97% full", WarningSeverity::Warning),
+        )];
+        let bodies = hook.plan(HealthStatus::Critical, &cleared, "", after, 0);
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(bodies[0]["state"], "cleared");
+        assert_eq!(bodies[0]["suppressed"], 1);
+        assert!(bodies[0]["text"]
+            .as_str()
+            .unwrap()
+            .ends_with("(1 more change held back by the rate limit)"));
+    }
+
+    #[tokio::test]
+    async fn delivery_retries_a_local_stand_in_until_it_accepts() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks/lab", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::<String>::new()));
+        let recorded = Arc::clone(&requests);
+        let server = tokio::spawn(async move {
+            for status in ["503 Service Unavailable", "429 Too Many Requests", "200 OK"] {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                loop {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length: usize = head
+                            .lines()
+                            .find_map(|line| line.strip_prefix("Content-Length: "))
+                            .unwrap()
+                            .parse()
+                            .unwrap();
+                        if body.len() >= length {
+                            break;
+                        }
+                    }
+                }
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8(request).unwrap());
+                let response = format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n");
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let target = Target::parse(&url).unwrap();
+        let body = json!({ "text": "lab: Overall health is critical (was good)" });
+        deliver(&target, &body, 3, Duration::from_millis(10))
+            .await
+            .unwrap();
+        server.await.unwrap();
+
+        let requests = std::mem::take(&mut *requests.lock().unwrap());
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2].starts_with("POST /hooks/lab HTTP/1.1\r\n"));
+        assert!(requests[2].contains("Content-Type: application/json\r\n"));
+        let sent = requests[2].split_once("\r\n\r\n").unwrap().1;
+        assert_eq!(serde_json::from_str::<Value>(sent).unwrap(), body);
+
+        // A closed port fails every attempt and says how many were made.
+        let closed = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", closed.local_addr().unwrap());
+        drop(closed);
+        let error = deliver(&Target::parse(&url).unwrap(), &body, 2, Duration::ZERO)
+            .await
+            .unwrap_err();
+        assert!(error.ends_with("after 2 attempts"), "{error}");
+    }
+}
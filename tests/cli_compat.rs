@@ -3,7 +3,9 @@ use std::process::{Command, Output};
 use std::sync::{Mutex, OnceLock};
 
 use clap::Parser;
-use sd_300::cli::{ActionArgs, Cli, Command as CliCommand, ReportArgs, UpdateActionArgs};
+use sd_300::cli::{
+    ActionArgs, Cli, Command as CliCommand, ReportArgs, SnapshotArgs, UpdateActionArgs,
+};
 use serde_json::Value;
 
 const V2_LONG_HELP: &str = include_str!("fixtures/v2.0.6/help.stdout");
@@ -40,15 +42,82 @@ fn normalize_cli_text(bytes: &[u8]) -> String {
     normalized
 }
 
+/// Help lines added after v2.0.6, matched by their trimmed prefix.
+const ADDITIVE_HELP_PREFIXES: &[&str] = &[
+    "gui ",
+    "sd300 gui ",
+    "d / g ",
+    "record ",
+    "sd300 record ",
+    "history ",
+    "sd300 history ",
+    "diff ",
+    "sd300 diff ",
+    "baseline ",
+    "sd300 baseline ",
+    "--replay <FILE>",
+    "Replay an `sd300 record`",
+    "sd300 --replay ",
+    "Space ",
+    ", . / ",
+    "- + / ",
+    "t ",
+    "0 ",
+    "w ",
+    "--summary-on-exit <PATH>",
+    "Write whole-session statistics",
+    "sd300 --tech --summary-on-exit ",
+    "serve ",
+    "sd300 serve ",
+    "push ",
+    "sd300 push ",
+    "daemon ",
+    "sd300 daemon ",
+    "check ",
+    "sd300 check ",
+    "sd300 snapshot --format ",
+    "export ",
+    "sd300 export ",
+    "e ",
+    "schema ",
+    "sd300 schema ",
+    "watch ",
+    "sd300 watch ",
+];
+
+/// Report keys added after v2.0.6, by contract pointer. Everything else at
+/// those paths must still match the golden exactly.
+const ADDITIVE_REPORT_KEYS: &[(&str, &[&str])] = &[
+    ("", &["events", "health", "thresholds"]),
+    ("/thermals/battery", &["design_capacity_mwh"]),
+    ("/drivers/services", &["expected_running"]),
+    (
+        "/processes/list",
+        &[
+            "disk_read_bytes_per_sec",
+            "disk_write_bytes_per_sec",
+            "gpu_memory_bytes",
+        ],
+    ),
+];
+
 fn strip_additive_gui_help(text: &str) -> String {
-    let mut normalized = text
-        .lines()
-        .filter(|line| {
-            let trimmed = line.trim_start();
-            !trimmed.starts_with("gui ") && !trimmed.starts_with("sd300 gui ")
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
+    let mut kept: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if ADDITIVE_HELP_PREFIXES
+            .iter()
+            .any(|prefix| trimmed.starts_with(prefix))
+        {
+            continue;
+        }
+        // A removed long-help option leaves its blank separator behind.
+        if line.is_empty() && kept.last().is_some_and(|last| last.is_empty()) {
+            continue;
+        }
+        kept.push(line);
+    }
+    let mut normalized = kept.join("\n");
     normalized.push('\n');
     normalized
 }
@@ -77,6 +146,18 @@ fn sorted_keys(value: &Value) -> Vec<String> {
     keys
 }
 
+fn expected_keys_at(pointer: &str, value: &Value) -> Vec<String> {
+    let mut keys = expected_keys(value);
+    if let Some((_, added)) = ADDITIVE_REPORT_KEYS
+        .iter()
+        .find(|(additive_pointer, _)| *additive_pointer == pointer)
+    {
+        keys.extend(added.iter().map(|key| (*key).to_owned()));
+        keys.sort();
+    }
+    keys
+}
+
 fn expected_keys(value: &Value) -> Vec<String> {
     value
         .as_array()
@@ -201,6 +282,21 @@ fn deterministic_parse_errors_match_v2_0_6_stdout_stderr_and_exit_codes() {
     }
 }
 
+#[test]
+fn check_reports_argument_errors_as_unknown() {
+    let output = run(&["check", "--bogus"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(output.stderr.is_empty());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "SD300 UNKNOWN - unexpected argument '--bogus' found\n"
+    );
+
+    let output = run(&["check", "--timeout", "soon"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("SD300 UNKNOWN - "));
+}
+
 #[test]
 fn v2_command_and_legacy_flag_parser_contract_is_unchanged() {
     let bare = Cli::try_parse_from(["sd300"]).expect("bare TUI launch should parse");
@@ -243,9 +339,13 @@ fn v2_command_and_legacy_flag_parser_contract_is_unchanged() {
         Cli::try_parse_from(["sd300", "snapshot", "--json"]).expect("snapshot --json should parse");
     assert_eq!(
         snapshot.command,
-        Some(CliCommand::Snapshot(ReportArgs {
-            json: true,
-            include_sensitive: false,
+        Some(CliCommand::Snapshot(SnapshotArgs {
+            report: ReportArgs {
+                json: true,
+                include_sensitive: false,
+            },
+            format: None,
+            max_chars: None,
         }))
     );
 
@@ -282,7 +382,7 @@ fn snapshot_json_preserves_the_v2_0_6_schema_and_default_redaction() {
             .unwrap_or_else(|| panic!("snapshot is missing v2 path {pointer}"));
         assert_eq!(
             sorted_keys(actual),
-            expected_keys(keys),
+            expected_keys_at(pointer, keys),
             "keys at {pointer}"
         );
     }
@@ -294,7 +394,7 @@ fn snapshot_json_preserves_the_v2_0_6_schema_and_default_redaction() {
         if let Some(actual) = report.pointer(pointer).filter(|value| !value.is_null()) {
             assert_eq!(
                 sorted_keys(actual),
-                expected_keys(keys),
+                expected_keys_at(pointer, keys),
                 "keys at {pointer}"
             );
         }
@@ -312,7 +412,7 @@ fn snapshot_json_preserves_the_v2_0_6_schema_and_default_redaction() {
         for value in values {
             assert_eq!(
                 sorted_keys(value),
-                expected_keys(keys),
+                expected_keys_at(pointer, keys),
                 "item keys at {pointer}"
             );
         }
@@ -360,6 +460,91 @@ fn snapshot_json_preserves_the_v2_0_6_schema_and_default_redaction() {
     }
 }
 
+#[test]
+fn published_schema_validates_live_snapshots() {
+    let schema = run(&["schema"]);
+    assert_eq!(schema.status.code(), Some(0));
+    let schema = parse_single_json(&schema.stdout);
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    let validator = jsonschema::validator_for(&schema).expect("published schema should compile");
+
+    let _guard = collector_command_lock();
+    let output = run(&["snapshot", "--json", "--include-sensitive"]);
+    assert_eq!(output.status.code(), Some(0));
+    let report = parse_single_json(&output.stdout);
+    let errors = validator
+        .iter_errors(&report)
+        .map(|error| format!("{}: {error}", error.instance_path()))
+        .collect::<Vec<_>>();
+    assert!(errors.is_empty(), "{errors:#?}");
+}
+
+#[test]
+fn watch_streams_redacted_lines_and_exits_cleanly_when_the_reader_leaves() {
+    use std::io::{BufRead, BufReader};
+
+    let _guard = collector_command_lock();
+    let mut child = sd300()
+        .args(["watch", "--json", "--sections", "system,cpu"])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .expect("sd300 watch should start");
+    let mut first = String::new();
+    BufReader::new(child.stdout.take().expect("stdout is piped"))
+        .read_line(&mut first)
+        .expect("watch should write a line");
+    let line: Value = serde_json::from_str(&first).expect("each line is one JSON object");
+    assert_eq!(line["sequence"], 1);
+    assert_eq!(line["sections"]["system"]["data"]["hostname"], "[redacted]");
+    assert_eq!(line["sections"]["cpu"]["sequence"], 1);
+
+    let output = child.wait_with_output().expect("watch should exit");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn record_writes_the_end_line_when_terminated() {
+    let _guard = collector_command_lock();
+    let dir = std::env::temp_dir().join(format!("sd300-record-term-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("temp dir");
+    let output = dir.join("recording.ndjson");
+    let _ = std::fs::remove_file(&output);
+    let mut child = sd300()
+        .args(["record", "--sections", "cpu", "--output"])
+        .arg(&output)
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .expect("sd300 record should start");
+
+    let started = std::time::Instant::now();
+    while std::fs::read_to_string(&output).map_or(true, |text| text.lines().count() < 2) {
+        assert!(
+            started.elapsed() < std::time::Duration::from_secs(30),
+            "record never wrote its keyframe"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    let killed = Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .status()
+        .expect("kill should run");
+    assert!(killed.success());
+    assert_eq!(child.wait().expect("record should exit").code(), Some(0));
+
+    let text = std::fs::read_to_string(&output).expect("recording should exist");
+    let last: Value = serde_json::from_str(text.lines().last().expect("recording has lines"))
+        .expect("each line is one JSON object");
+    assert_eq!(last["type"], "end");
+    assert_eq!(last["reason"], "terminated");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
 #[test]
 fn capabilities_json_preserves_v2_0_6_order_shape_and_single_value_stdout() {
     let _guard = collector_command_lock();